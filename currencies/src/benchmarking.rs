@@ -0,0 +1,141 @@
+//! Benchmarks for the currencies module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use sp_runtime::traits::{One, Saturating};
+
+const SEED: u32 = 0;
+
+/// Returns an amount well above the existential deposit of `currency_id`.
+fn amount<T: Config>(currency_id: CurrencyIdOf<T>) -> BalanceOf<T> {
+	<Pallet<T> as MultiCurrency<T::AccountId>>::minimum_balance(currency_id)
+		.saturating_add(One::one())
+		.saturating_mul(100u32.into())
+}
+
+/// Endows `who` with `amount` of `currency_id`.
+fn endow<T: Config>(currency_id: CurrencyIdOf<T>, who: &T::AccountId, amount: BalanceOf<T>) {
+	assert_ok!(<Pallet<T> as MultiCurrency<T::AccountId>>::deposit(
+		currency_id,
+		who,
+		amount
+	));
+}
+
+/// Creates a swap of the benchmark currency against the native currency
+/// between two endowed accounts, returning its id.
+fn setup_swap<T: Config>(maker: &T::AccountId, taker: &T::AccountId) -> SwapId {
+	let give_currency_id = T::BenchmarkCurrencyId::get();
+	let take_currency_id = T::GetNativeCurrencyId::get();
+	let give_amount = amount::<T>(give_currency_id);
+	let take_amount = amount::<T>(take_currency_id);
+	endow::<T>(give_currency_id, maker, give_amount.saturating_mul(2u32.into()));
+	endow::<T>(
+		take_currency_id,
+		maker,
+		take_amount.saturating_add(T::SwapDeposit::get()),
+	);
+	endow::<T>(take_currency_id, taker, take_amount.saturating_mul(2u32.into()));
+
+	let swap_id = NextSwapId::<T>::get();
+	assert_ok!(Pallet::<T>::create_swap(
+		RawOrigin::Signed(maker.clone()).into(),
+		T::Lookup::unlookup(taker.clone()),
+		give_currency_id,
+		give_amount,
+		take_currency_id,
+		take_amount,
+		frame_system::Pallet::<T>::block_number().saturating_add(10u32.into()),
+	));
+	swap_id
+}
+
+fn assert_last_event<T: Config>(event: Event<T>) {
+	frame_system::Pallet::<T>::assert_last_event(<T as Config>::RuntimeEvent::from(event).into());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn create_swap() {
+		let give_currency_id = T::BenchmarkCurrencyId::get();
+		let take_currency_id = T::GetNativeCurrencyId::get();
+		let give_amount = amount::<T>(give_currency_id);
+		let take_amount = amount::<T>(take_currency_id);
+		let maker: T::AccountId = whitelisted_caller();
+		let taker: T::AccountId = account("taker", 0, SEED);
+		endow::<T>(give_currency_id, &maker, give_amount.saturating_mul(2u32.into()));
+		endow::<T>(
+			take_currency_id,
+			&maker,
+			take_amount.saturating_add(T::SwapDeposit::get()),
+		);
+		let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+		let swap_id = NextSwapId::<T>::get();
+
+		// the offered balance and the deposit are reserved in two currencies,
+		// the worst case
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(maker.clone()),
+			T::Lookup::unlookup(taker.clone()),
+			give_currency_id,
+			give_amount,
+			take_currency_id,
+			take_amount,
+			expires_at,
+		);
+
+		assert_last_event::<T>(Event::SwapCreated {
+			swap_id,
+			maker,
+			taker,
+			give_currency_id,
+			give_amount,
+			take_currency_id,
+			take_amount,
+			expires_at,
+		});
+	}
+
+	#[benchmark]
+	fn accept_swap() {
+		let maker: T::AccountId = account("maker", 0, SEED);
+		let taker: T::AccountId = whitelisted_caller();
+		let swap_id = setup_swap::<T>(&maker, &taker);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(taker), swap_id);
+
+		assert_last_event::<T>(Event::SwapAccepted { swap_id });
+	}
+
+	#[benchmark]
+	fn cancel_swap() {
+		let maker: T::AccountId = whitelisted_caller();
+		let taker: T::AccountId = account("taker", 0, SEED);
+		let swap_id = setup_swap::<T>(&maker, &taker);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(maker), swap_id);
+
+		assert_last_event::<T>(Event::SwapCancelled { swap_id });
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		{
+			// events aren't recorded in the genesis block
+			let mut ext = crate::mock::ExtBuilder::default().build();
+			ext.execute_with(|| frame_system::Pallet::<crate::mock::Runtime>::set_block_number(1));
+			ext
+		},
+		crate::mock::Runtime
+	);
+}
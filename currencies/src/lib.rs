@@ -15,6 +15,11 @@
 //! A native currency ID could be set by `Config::GetNativeCurrencyId`, to
 //! identify the native currency.
 //!
+//! The non-native currencies are held by `Config::MultiCurrency`, unless
+//! `Config::CurrencyBackend` classifies them as held by a third
+//! `Config::ExternalCurrency` backend, like assets living in smart-contract
+//! storage.
+//!
 //! ### Implementations
 //!
 //! The currencies module provides implementations for following traits.
@@ -23,6 +28,12 @@
 //! - `MultiCurrencyExtended` - Extended `MultiCurrency` with additional helper
 //!   types and methods, like updating balance
 //! by a given signed integer amount.
+//! - `fungibles::{Inspect, Mutate, Unbalanced, Balanced, InspectHold,
+//!   MutateHold}` - The FRAME fungibles traits, delegated to the `fungible`
+//!   impls of `Config::NativeCurrency` and the `fungibles` impls of
+//!   `Config::MultiCurrency` and `Config::ExternalCurrency`. Like the
+//!   `MultiCurrency` family, every entry point changing a balance fails if the
+//!   currency is paused.
 //!
 //! ## Interface
 //!
@@ -35,6 +46,16 @@
 //! `Config::NativeCurrency`.
 //! - `update_balance` - Update balance by signed integer amount, in a given
 //!   currency, root origin required.
+//! - `pause_currency` - Pause all balance changes of a currency, except for
+//!   accounts in `Config::PauseWhitelist`, `PauseOrigin` required.
+//! - `unpause_currency` - Resume a paused currency, `PauseOrigin` required.
+//! - `create_swap` - Offer to swap some balance of a currency against some
+//!   balance of another currency held by a given counterparty, the offered
+//!   balance and `Config::SwapDeposit` are reserved until the swap is settled
+//!   or cancelled.
+//! - `accept_swap` - Settle both legs of a swap offered to the caller.
+//! - `cancel_swap` - Cancel a swap and unreserve the offered balance and the
+//!   deposit, by the maker at any time or by anyone once expired.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -42,27 +63,33 @@
 use frame_support::{
 	pallet_prelude::*,
 	traits::{
-		Currency as PalletCurrency, ExistenceRequirement, Get, Imbalance, LockableCurrency as PalletLockableCurrency,
-		NamedReservableCurrency as PalletNamedReservableCurrency, ReservableCurrency as PalletReservableCurrency,
-		WithdrawReasons,
+		tokens::{
+			fungible, fungibles, DepositConsequence, Fortitude, Precision, Preservation, Provenance, Restriction,
+			WithdrawConsequence,
+		},
+		Contains, Currency as PalletCurrency, ExistenceRequirement, Get, Imbalance,
+		LockableCurrency as PalletLockableCurrency, NamedReservableCurrency as PalletNamedReservableCurrency,
+		ReservableCurrency as PalletReservableCurrency, WithdrawReasons,
 	},
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 use orml_traits::{
-	arithmetic::{Signed, SimpleArithmetic},
+	arithmetic::{Signed, SignedBalance, SimpleArithmetic},
 	currency::TransferAll,
-	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency,
-	LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
-	NamedBasicReservableCurrency, NamedMultiReservableCurrency,
+	BalanceStatus, BasicCurrency, BasicCurrencyExtended, BasicLockableCurrency, BasicReservableCurrency, GetByKey,
+	LockIdentifier, MultiCurrency, MultiCurrencyBackend, MultiCurrencyExtended, MultiLockableCurrency,
+	MultiReservableCurrency, NamedBasicReservableCurrency, NamedMultiReservableCurrency,
 };
 use orml_utilities::with_transaction_result;
 use parity_scale_codec::Codec;
 use sp_runtime::{
 	traits::{CheckedSub, MaybeSerializeDeserialize, StaticLookup, Zero},
-	DispatchError, DispatchResult,
+	DispatchError, DispatchResult, RuntimeDebug,
 };
-use sp_std::{fmt::Debug, marker, result};
+use sp_std::{fmt::Debug, marker, result, vec::Vec};
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod mock;
 mod tests;
 mod weights;
@@ -70,6 +97,46 @@ mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The backend holding the balances of a currency.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum Backend {
+	/// `Config::NativeCurrency`.
+	Native,
+	/// `Config::MultiCurrency`.
+	MultiCurrency,
+	/// `Config::ExternalCurrency`.
+	External,
+}
+
+/// Swap index.
+pub type SwapId = u64;
+
+/// A two-party swap offer, `maker` gives `give_amount` of `give_currency_id`
+/// to `taker` against `take_amount` of `take_currency_id`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Swap<AccountId, CurrencyId, Balance, BlockNumber> {
+	/// The account offering the swap, `give_amount` is reserved from it.
+	pub maker: AccountId,
+	/// The only account which can accept the swap.
+	pub taker: AccountId,
+	pub give_currency_id: CurrencyId,
+	pub give_amount: Balance,
+	pub take_currency_id: CurrencyId,
+	pub take_amount: Balance,
+	/// The swap can't be accepted from this block on.
+	pub expires_at: BlockNumber,
+	/// The deposit reserved from `maker` in the native currency.
+	pub deposit: Balance,
+}
+
+/// Classifies every currency as held by `Config::MultiCurrency`.
+pub struct AllMultiCurrency;
+impl<CurrencyId> GetByKey<CurrencyId, Backend> for AllMultiCurrency {
+	fn get(_: &CurrencyId) -> Backend {
+		Backend::MultiCurrency
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -83,14 +150,14 @@ pub mod module {
 	pub(crate) type ReserveIdentifierOf<T> = <<T as Config>::MultiCurrency as NamedMultiReservableCurrency<
 		<T as frame_system::Config>::AccountId,
 	>>::ReserveIdentifier;
+	pub(crate) type SwapOf<T> =
+		Swap<<T as frame_system::Config>::AccountId, CurrencyIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
-		type MultiCurrency: TransferAll<Self::AccountId>
-			+ MultiCurrencyExtended<Self::AccountId>
-			+ MultiLockableCurrency<Self::AccountId>
-			+ MultiReservableCurrency<Self::AccountId>
-			+ NamedMultiReservableCurrency<Self::AccountId>;
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		type MultiCurrency: MultiCurrencyBackend<Self::AccountId>;
 
 		type NativeCurrency: BasicCurrencyExtended<Self::AccountId, Balance = BalanceOf<Self>, Amount = AmountOf<Self>>
 			+ BasicLockableCurrency<Self::AccountId, Balance = BalanceOf<Self>>
@@ -100,8 +167,44 @@ pub mod module {
 		#[pallet::constant]
 		type GetNativeCurrencyId: Get<CurrencyIdOf<Self>>;
 
+		/// The backend of the currencies classified as `Backend::External`.
+		type ExternalCurrency: MultiCurrencyBackend<
+			Self::AccountId,
+			CurrencyId = CurrencyIdOf<Self>,
+			Balance = BalanceOf<Self>,
+			Amount = AmountOf<Self>,
+			ReserveIdentifier = ReserveIdentifierOf<Self>,
+		>;
+
+		/// Classifies the non-native currencies by the backend holding them.
+		/// `GetNativeCurrencyId` is always held by `NativeCurrency`.
+		type CurrencyBackend: GetByKey<CurrencyIdOf<Self>, Backend>;
+
+		/// The origin which may pause and unpause currencies.
+		type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The accounts which can still move the balances of paused
+		/// currencies, i.e. the treasury.
+		type PauseWhitelist: Contains<Self::AccountId>;
+
+		/// The named reserve holding the balances offered in swaps.
+		#[pallet::constant]
+		type SwapReserveId: Get<ReserveIdentifierOf<Self>>;
+
+		/// The deposit reserved in the native currency from the maker of a
+		/// swap, until the swap is accepted or cancelled. Expired swaps can be
+		/// cancelled by anyone, which returns the deposit to the maker.
+		#[pallet::constant]
+		type SwapDeposit: Get<BalanceOf<Self>>;
+
 		/// Weight information for extrinsics in this module.
 		type WeightInfo: WeightInfo;
+
+		/// A currency other than the native one, the benchmarks swap it
+		/// against the native currency. Accounts must be able to hold it
+		/// without restriction.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkCurrencyId: Get<CurrencyIdOf<Self>>;
 	}
 
 	#[pallet::error]
@@ -112,13 +215,69 @@ pub mod module {
 		BalanceTooLow,
 		/// Deposit result is not expected
 		DepositFailed,
-	}
+		/// The currency is paused
+		CurrencyPaused,
+		/// The swap doesn't exist
+		SwapNotFound,
+		/// The swap has expired, or expires before it is created
+		SwapExpired,
+		/// The caller is not allowed to accept or cancel the swap
+		NotSwapParty,
+		/// The maker of a swap can't be its taker
+		SwapWithSelf,
+		/// The amount of a swap leg is zero
+		ZeroSwapAmount,
+		/// Both legs of a swap are in the same currency
+		SameSwapCurrency,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A currency was paused.
+		CurrencyPaused { currency_id: CurrencyIdOf<T> },
+		/// A currency was unpaused.
+		CurrencyUnpaused { currency_id: CurrencyIdOf<T> },
+		/// A swap was offered.
+		SwapCreated {
+			swap_id: SwapId,
+			maker: T::AccountId,
+			taker: T::AccountId,
+			give_currency_id: CurrencyIdOf<T>,
+			give_amount: BalanceOf<T>,
+			take_currency_id: CurrencyIdOf<T>,
+			take_amount: BalanceOf<T>,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// Both legs of a swap were settled.
+		SwapAccepted { swap_id: SwapId },
+		/// A swap was cancelled and the offered balance unreserved.
+		SwapCancelled { swap_id: SwapId },
+	}
+
+	/// The paused currencies, the balances of a paused currency can only be
+	/// changed by the accounts in `PauseWhitelist`.
+	#[pallet::storage]
+	pub type PausedCurrencies<T: Config> = StorageMap<_, Twox64Concat, CurrencyIdOf<T>, (), OptionQuery>;
+
+	/// The next swap id.
+	#[pallet::storage]
+	pub type NextSwapId<T: Config> = StorageValue<_, SwapId, ValueQuery>;
+
+	/// The open swaps.
+	#[pallet::storage]
+	pub type Swaps<T: Config> = StorageMap<_, Twox64Concat, SwapId, SwapOf<T>, OptionQuery>;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -152,6 +311,7 @@ pub mod module {
 		) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(dest)?;
+			Self::ensure_not_paused(T::GetNativeCurrencyId::get(), &from)?;
 			T::NativeCurrency::transfer(&from, &to, amount)
 		}
 
@@ -170,6 +330,265 @@ pub mod module {
 			let dest = T::Lookup::lookup(who)?;
 			<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)
 		}
+
+		/// Pause all balance changes of `currency_id`, except for the accounts
+		/// in `PauseWhitelist`.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::pause_currency())]
+		pub fn pause_currency(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			PausedCurrencies::<T>::insert(currency_id, ());
+			Self::deposit_event(Event::CurrencyPaused { currency_id });
+			Ok(())
+		}
+
+		/// Resume the balance changes of a paused `currency_id`.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::unpause_currency())]
+		pub fn unpause_currency(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResult {
+			T::PauseOrigin::ensure_origin(origin)?;
+			PausedCurrencies::<T>::remove(currency_id);
+			Self::deposit_event(Event::CurrencyUnpaused { currency_id });
+			Ok(())
+		}
+
+		/// Offer `taker` to swap `give_amount` of `give_currency_id` against
+		/// `take_amount` of `take_currency_id`, until `expires_at`.
+		///
+		/// `give_amount` and `SwapDeposit` are reserved until the swap is
+		/// accepted or cancelled.
+		///
+		/// The dispatch origin for this call must be `Signed` by the maker.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::create_swap())]
+		pub fn create_swap(
+			origin: OriginFor<T>,
+			taker: <T::Lookup as StaticLookup>::Source,
+			give_currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] give_amount: BalanceOf<T>,
+			take_currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] take_amount: BalanceOf<T>,
+			expires_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let maker = ensure_signed(origin)?;
+			let taker = T::Lookup::lookup(taker)?;
+			ensure!(maker != taker, Error::<T>::SwapWithSelf);
+			ensure!(
+				!give_amount.is_zero() && !take_amount.is_zero(),
+				Error::<T>::ZeroSwapAmount
+			);
+			ensure!(give_currency_id != take_currency_id, Error::<T>::SameSwapCurrency);
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::SwapExpired
+			);
+
+			<Self as NamedMultiReservableCurrency<T::AccountId>>::reserve_named(
+				&T::SwapReserveId::get(),
+				give_currency_id,
+				&maker,
+				give_amount,
+			)?;
+			let deposit = T::SwapDeposit::get();
+			if !deposit.is_zero() {
+				<Self as NamedMultiReservableCurrency<T::AccountId>>::reserve_named(
+					&T::SwapReserveId::get(),
+					T::GetNativeCurrencyId::get(),
+					&maker,
+					deposit,
+				)?;
+			}
+
+			let swap_id = NextSwapId::<T>::mutate(|id| {
+				let current = *id;
+				*id = id.saturating_add(1);
+				current
+			});
+			Swaps::<T>::insert(
+				swap_id,
+				Swap {
+					maker: maker.clone(),
+					taker: taker.clone(),
+					give_currency_id,
+					give_amount,
+					take_currency_id,
+					take_amount,
+					expires_at,
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::SwapCreated {
+				swap_id,
+				maker,
+				taker,
+				give_currency_id,
+				give_amount,
+				take_currency_id,
+				take_amount,
+				expires_at,
+			});
+			Ok(())
+		}
+
+		/// Settle both legs of the swap `swap_id` atomically.
+		///
+		/// The dispatch origin for this call must be `Signed` by the taker of
+		/// the swap, before the swap expires.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::accept_swap())]
+		pub fn accept_swap(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let swap = Swaps::<T>::get(swap_id).ok_or(Error::<T>::SwapNotFound)?;
+			ensure!(who == swap.taker, Error::<T>::NotSwapParty);
+			ensure!(
+				swap.expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::SwapExpired
+			);
+
+			Self::do_accept_swap(&swap)?;
+			Swaps::<T>::remove(swap_id);
+
+			Self::deposit_event(Event::SwapAccepted { swap_id });
+			Ok(())
+		}
+
+		/// Cancel the swap `swap_id` and unreserve the offered balance and the
+		/// deposit of the maker.
+		///
+		/// The dispatch origin for this call must be `Signed` by the maker of
+		/// the swap, or by any account once the swap has expired, so expired
+		/// swaps can be cleaned up by anyone.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::cancel_swap())]
+		pub fn cancel_swap(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let swap = Swaps::<T>::get(swap_id).ok_or(Error::<T>::SwapNotFound)?;
+			ensure!(
+				who == swap.maker || swap.expires_at <= frame_system::Pallet::<T>::block_number(),
+				Error::<T>::NotSwapParty
+			);
+			Self::ensure_not_paused(swap.give_currency_id, &swap.maker)?;
+
+			Self::unreserve_swap_deposit(&swap)?;
+			<Self as NamedMultiReservableCurrency<T::AccountId>>::unreserve_named(
+				&T::SwapReserveId::get(),
+				swap.give_currency_id,
+				&swap.maker,
+				swap.give_amount,
+			);
+			Swaps::<T>::remove(swap_id);
+
+			Self::deposit_event(Event::SwapCancelled { swap_id });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The backend holding the balances of `currency_id`.
+	pub fn backend(currency_id: CurrencyIdOf<T>) -> Backend {
+		if currency_id == T::GetNativeCurrencyId::get() {
+			Backend::Native
+		} else {
+			T::CurrencyBackend::get(&currency_id)
+		}
+	}
+
+	/// Whether the balances of `who` under `currency_id` can't be changed
+	/// because the currency is paused.
+	pub fn is_paused(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> bool {
+		PausedCurrencies::<T>::contains_key(currency_id) && !T::PauseWhitelist::contains(who)
+	}
+
+	fn ensure_not_paused(currency_id: CurrencyIdOf<T>, who: &T::AccountId) -> DispatchResult {
+		ensure!(!Self::is_paused(currency_id, who), Error::<T>::CurrencyPaused);
+		Ok(())
+	}
+
+	/// Unreserve the deposit of the maker of `swap`, which fails if the native
+	/// currency is paused for the maker.
+	fn unreserve_swap_deposit(swap: &SwapOf<T>) -> DispatchResult {
+		let remaining = <Self as NamedMultiReservableCurrency<T::AccountId>>::unreserve_named(
+			&T::SwapReserveId::get(),
+			T::GetNativeCurrencyId::get(),
+			&swap.maker,
+			swap.deposit,
+		);
+		ensure!(remaining.is_zero(), Error::<T>::CurrencyPaused);
+		Ok(())
+	}
+
+	/// Unreserve the balance offered and the deposit of the maker, and
+	/// transfer both legs of `swap`. Nothing is changed if any leg fails.
+	fn do_accept_swap(swap: &SwapOf<T>) -> DispatchResult {
+		Self::ensure_not_paused(swap.give_currency_id, &swap.maker)?;
+		with_transaction_result(|| {
+			let remaining = <Self as NamedMultiReservableCurrency<T::AccountId>>::unreserve_named(
+				&T::SwapReserveId::get(),
+				swap.give_currency_id,
+				&swap.maker,
+				swap.give_amount,
+			);
+			ensure!(remaining.is_zero(), Error::<T>::BalanceTooLow);
+			Self::unreserve_swap_deposit(swap)?;
+
+			<Self as MultiCurrency<T::AccountId>>::transfer(
+				swap.give_currency_id,
+				&swap.maker,
+				&swap.taker,
+				swap.give_amount,
+			)?;
+			<Self as MultiCurrency<T::AccountId>>::transfer(
+				swap.take_currency_id,
+				&swap.taker,
+				&swap.maker,
+				swap.take_amount,
+			)
+		})
+	}
+}
+
+#[cfg(any(feature = "std", feature = "try-runtime"))]
+impl<T: Config> Pallet<T> {
+	/// Check that the open swaps are below `NextSwapId`, and that the swap
+	/// reserve of each maker equals the sum of the balances it offers and of
+	/// its deposits.
+	pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		let next_swap_id = NextSwapId::<T>::get();
+		let mut offered: Vec<(T::AccountId, CurrencyIdOf<T>, BalanceOf<T>)> = Vec::new();
+		for (swap_id, swap) in Swaps::<T>::iter() {
+			ensure!(swap_id < next_swap_id, "Swap id is not below NextSwapId");
+			for (currency_id, reserved) in [
+				(swap.give_currency_id, swap.give_amount),
+				(T::GetNativeCurrencyId::get(), swap.deposit),
+			] {
+				match offered
+					.iter_mut()
+					.find(|(maker, id, _)| *maker == swap.maker && *id == currency_id)
+				{
+					Some((_, _, amount)) => *amount = amount.saturating_add(reserved),
+					None => offered.push((swap.maker.clone(), currency_id, reserved)),
+				}
+			}
+		}
+
+		for (maker, currency_id, amount) in offered {
+			ensure!(
+				<Self as NamedMultiReservableCurrency<T::AccountId>>::reserved_balance_named(
+					&T::SwapReserveId::get(),
+					currency_id,
+					&maker
+				) == amount,
+				"Swap reserve is not the sum of the offered balances"
+			);
+		}
+
+		Ok(())
 	}
 }
 
@@ -178,42 +597,43 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	type Balance = BalanceOf<T>;
 
 	fn minimum_balance(currency_id: Self::CurrencyId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::minimum_balance()
-		} else {
-			T::MultiCurrency::minimum_balance(currency_id)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::minimum_balance(),
+			Backend::MultiCurrency => T::MultiCurrency::minimum_balance(currency_id),
+			Backend::External => T::ExternalCurrency::minimum_balance(currency_id),
 		}
 	}
 
 	fn total_issuance(currency_id: Self::CurrencyId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::total_issuance()
-		} else {
-			T::MultiCurrency::total_issuance(currency_id)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::total_issuance(),
+			Backend::MultiCurrency => T::MultiCurrency::total_issuance(currency_id),
+			Backend::External => T::ExternalCurrency::total_issuance(currency_id),
 		}
 	}
 
 	fn total_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::total_balance(who)
-		} else {
-			T::MultiCurrency::total_balance(currency_id, who)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::total_balance(who),
+			Backend::MultiCurrency => T::MultiCurrency::total_balance(currency_id, who),
+			Backend::External => T::ExternalCurrency::total_balance(currency_id, who),
 		}
 	}
 
 	fn free_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::free_balance(who)
-		} else {
-			T::MultiCurrency::free_balance(currency_id, who)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::free_balance(who),
+			Backend::MultiCurrency => T::MultiCurrency::free_balance(currency_id, who),
+			Backend::External => T::ExternalCurrency::free_balance(currency_id, who),
 		}
 	}
 
 	fn ensure_can_withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::ensure_can_withdraw(who, amount)
-		} else {
-			T::MultiCurrency::ensure_can_withdraw(currency_id, who, amount)
+		Self::ensure_not_paused(currency_id, who)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::ensure_can_withdraw(who, amount),
+			Backend::MultiCurrency => T::MultiCurrency::ensure_can_withdraw(currency_id, who, amount),
+			Backend::External => T::ExternalCurrency::ensure_can_withdraw(currency_id, who, amount),
 		}
 	}
 
@@ -226,10 +646,11 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() || from == to {
 			return Ok(());
 		}
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::transfer(from, to, amount)
-		} else {
-			T::MultiCurrency::transfer(currency_id, from, to, amount)
+		Self::ensure_not_paused(currency_id, from)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::transfer(from, to, amount),
+			Backend::MultiCurrency => T::MultiCurrency::transfer(currency_id, from, to, amount),
+			Backend::External => T::ExternalCurrency::transfer(currency_id, from, to, amount),
 		}
 	}
 
@@ -237,10 +658,11 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::deposit(who, amount)
-		} else {
-			T::MultiCurrency::deposit(currency_id, who, amount)
+		Self::ensure_not_paused(currency_id, who)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::deposit(who, amount),
+			Backend::MultiCurrency => T::MultiCurrency::deposit(currency_id, who, amount),
+			Backend::External => T::ExternalCurrency::deposit(currency_id, who, amount),
 		}
 	}
 
@@ -248,26 +670,33 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 		if amount.is_zero() {
 			return Ok(());
 		}
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::withdraw(who, amount)
-		} else {
-			T::MultiCurrency::withdraw(currency_id, who, amount)
+		Self::ensure_not_paused(currency_id, who)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::withdraw(who, amount),
+			Backend::MultiCurrency => T::MultiCurrency::withdraw(currency_id, who, amount),
+			Backend::External => T::ExternalCurrency::withdraw(currency_id, who, amount),
 		}
 	}
 
 	fn can_slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> bool {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::can_slash(who, amount)
-		} else {
-			T::MultiCurrency::can_slash(currency_id, who, amount)
+		if Self::is_paused(currency_id, who) {
+			return false;
+		}
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::can_slash(who, amount),
+			Backend::MultiCurrency => T::MultiCurrency::can_slash(currency_id, who, amount),
+			Backend::External => T::ExternalCurrency::can_slash(currency_id, who, amount),
 		}
 	}
 
 	fn slash(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::slash(who, amount)
-		} else {
-			T::MultiCurrency::slash(currency_id, who, amount)
+		if Self::is_paused(currency_id, who) {
+			return amount;
+		}
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::slash(who, amount),
+			Backend::MultiCurrency => T::MultiCurrency::slash(currency_id, who, amount),
+			Backend::External => T::ExternalCurrency::slash(currency_id, who, amount),
 		}
 	}
 }
@@ -276,10 +705,11 @@ impl<T: Config> MultiCurrencyExtended<T::AccountId> for Pallet<T> {
 	type Amount = AmountOf<T>;
 
 	fn update_balance(currency_id: Self::CurrencyId, who: &T::AccountId, by_amount: Self::Amount) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::update_balance(who, by_amount)
-		} else {
-			T::MultiCurrency::update_balance(currency_id, who, by_amount)
+		Self::ensure_not_paused(currency_id, who)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::update_balance(who, by_amount),
+			Backend::MultiCurrency => T::MultiCurrency::update_balance(currency_id, who, by_amount),
+			Backend::External => T::ExternalCurrency::update_balance(currency_id, who, by_amount),
 		}
 	}
 }
@@ -293,10 +723,10 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::set_lock(lock_id, who, amount)
-		} else {
-			T::MultiCurrency::set_lock(lock_id, currency_id, who, amount)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::set_lock(lock_id, who, amount),
+			Backend::MultiCurrency => T::MultiCurrency::set_lock(lock_id, currency_id, who, amount),
+			Backend::External => T::ExternalCurrency::set_lock(lock_id, currency_id, who, amount),
 		}
 	}
 
@@ -306,60 +736,70 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::extend_lock(lock_id, who, amount)
-		} else {
-			T::MultiCurrency::extend_lock(lock_id, currency_id, who, amount)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::extend_lock(lock_id, who, amount),
+			Backend::MultiCurrency => T::MultiCurrency::extend_lock(lock_id, currency_id, who, amount),
+			Backend::External => T::ExternalCurrency::extend_lock(lock_id, currency_id, who, amount),
 		}
 	}
 
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::remove_lock(lock_id, who)
-		} else {
-			T::MultiCurrency::remove_lock(lock_id, currency_id, who)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::remove_lock(lock_id, who),
+			Backend::MultiCurrency => T::MultiCurrency::remove_lock(lock_id, currency_id, who),
+			Backend::External => T::ExternalCurrency::remove_lock(lock_id, currency_id, who),
 		}
 	}
 }
 
 impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	fn can_reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> bool {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::can_reserve(who, value)
-		} else {
-			T::MultiCurrency::can_reserve(currency_id, who, value)
+		if Self::is_paused(currency_id, who) {
+			return false;
+		}
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::can_reserve(who, value),
+			Backend::MultiCurrency => T::MultiCurrency::can_reserve(currency_id, who, value),
+			Backend::External => T::ExternalCurrency::can_reserve(currency_id, who, value),
 		}
 	}
 
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::slash_reserved(who, value)
-		} else {
-			T::MultiCurrency::slash_reserved(currency_id, who, value)
+		if Self::is_paused(currency_id, who) {
+			return value;
+		}
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::slash_reserved(who, value),
+			Backend::MultiCurrency => T::MultiCurrency::slash_reserved(currency_id, who, value),
+			Backend::External => T::ExternalCurrency::slash_reserved(currency_id, who, value),
 		}
 	}
 
 	fn reserved_balance(currency_id: Self::CurrencyId, who: &T::AccountId) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::reserved_balance(who)
-		} else {
-			T::MultiCurrency::reserved_balance(currency_id, who)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::reserved_balance(who),
+			Backend::MultiCurrency => T::MultiCurrency::reserved_balance(currency_id, who),
+			Backend::External => T::ExternalCurrency::reserved_balance(currency_id, who),
 		}
 	}
 
 	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::reserve(who, value)
-		} else {
-			T::MultiCurrency::reserve(currency_id, who, value)
+		Self::ensure_not_paused(currency_id, who)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::reserve(who, value),
+			Backend::MultiCurrency => T::MultiCurrency::reserve(currency_id, who, value),
+			Backend::External => T::ExternalCurrency::reserve(currency_id, who, value),
 		}
 	}
 
 	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::unreserve(who, value)
-		} else {
-			T::MultiCurrency::unreserve(currency_id, who, value)
+		if Self::is_paused(currency_id, who) {
+			return value;
+		}
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::unreserve(who, value),
+			Backend::MultiCurrency => T::MultiCurrency::unreserve(currency_id, who, value),
+			Backend::External => T::ExternalCurrency::unreserve(currency_id, who, value),
 		}
 	}
 
@@ -370,10 +810,15 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		value: Self::Balance,
 		status: BalanceStatus,
 	) -> result::Result<Self::Balance, DispatchError> {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::repatriate_reserved(slashed, beneficiary, value, status)
-		} else {
-			T::MultiCurrency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)
+		Self::ensure_not_paused(currency_id, slashed)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::repatriate_reserved(slashed, beneficiary, value, status),
+			Backend::MultiCurrency => {
+				T::MultiCurrency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)
+			}
+			Backend::External => {
+				T::ExternalCurrency::repatriate_reserved(currency_id, slashed, beneficiary, value, status)
+			}
 		}
 	}
 }
@@ -387,10 +832,13 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		value: Self::Balance,
 	) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::slash_reserved_named(id, who, value)
-		} else {
-			T::MultiCurrency::slash_reserved_named(id, currency_id, who, value)
+		if Self::is_paused(currency_id, who) {
+			return value;
+		}
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::slash_reserved_named(id, who, value),
+			Backend::MultiCurrency => T::MultiCurrency::slash_reserved_named(id, currency_id, who, value),
+			Backend::External => T::ExternalCurrency::slash_reserved_named(id, currency_id, who, value),
 		}
 	}
 
@@ -399,10 +847,10 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 		currency_id: Self::CurrencyId,
 		who: &T::AccountId,
 	) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::reserved_balance_named(id, who)
-		} else {
-			T::MultiCurrency::reserved_balance_named(id, currency_id, who)
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::reserved_balance_named(id, who),
+			Backend::MultiCurrency => T::MultiCurrency::reserved_balance_named(id, currency_id, who),
+			Backend::External => T::ExternalCurrency::reserved_balance_named(id, currency_id, who),
 		}
 	}
 
@@ -412,10 +860,11 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		value: Self::Balance,
 	) -> DispatchResult {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::reserve_named(id, who, value)
-		} else {
-			T::MultiCurrency::reserve_named(id, currency_id, who, value)
+		Self::ensure_not_paused(currency_id, who)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::reserve_named(id, who, value),
+			Backend::MultiCurrency => T::MultiCurrency::reserve_named(id, currency_id, who, value),
+			Backend::External => T::ExternalCurrency::reserve_named(id, currency_id, who, value),
 		}
 	}
 
@@ -425,10 +874,13 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		value: Self::Balance,
 	) -> Self::Balance {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::unreserve_named(id, who, value)
-		} else {
-			T::MultiCurrency::unreserve_named(id, currency_id, who, value)
+		if Self::is_paused(currency_id, who) {
+			return value;
+		}
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::unreserve_named(id, who, value),
+			Backend::MultiCurrency => T::MultiCurrency::unreserve_named(id, currency_id, who, value),
+			Backend::External => T::ExternalCurrency::unreserve_named(id, currency_id, who, value),
 		}
 	}
 
@@ -440,10 +892,15 @@ impl<T: Config> NamedMultiReservableCurrency<T::AccountId> for Pallet<T> {
 		value: Self::Balance,
 		status: BalanceStatus,
 	) -> result::Result<Self::Balance, DispatchError> {
-		if currency_id == T::GetNativeCurrencyId::get() {
-			T::NativeCurrency::repatriate_reserved_named(id, slashed, beneficiary, value, status)
-		} else {
-			T::MultiCurrency::repatriate_reserved_named(id, currency_id, slashed, beneficiary, value, status)
+		Self::ensure_not_paused(currency_id, slashed)?;
+		match Self::backend(currency_id) {
+			Backend::Native => T::NativeCurrency::repatriate_reserved_named(id, slashed, beneficiary, value, status),
+			Backend::MultiCurrency => {
+				T::MultiCurrency::repatriate_reserved_named(id, currency_id, slashed, beneficiary, value, status)
+			}
+			Backend::External => {
+				T::ExternalCurrency::repatriate_reserved_named(id, currency_id, slashed, beneficiary, value, status)
+			}
 		}
 	}
 }
@@ -702,14 +1159,9 @@ where
 	type Amount = Amount;
 
 	fn update_balance(who: &AccountId, by_amount: Self::Amount) -> DispatchResult {
-		let by_balance = by_amount
-			.abs()
-			.try_into()
-			.map_err(|_| Error::<T>::AmountIntoBalanceFailed)?;
-		if by_amount.is_positive() {
-			Self::deposit(who, by_balance)
-		} else {
-			Self::withdraw(who, by_balance)
+		match SignedBalance::try_from_amount(by_amount).ok_or(Error::<T>::AmountIntoBalanceFailed)? {
+			SignedBalance::Positive(by_balance) => Self::deposit(who, by_balance),
+			SignedBalance::Negative(by_balance) => Self::withdraw(who, by_balance),
 		}
 	}
 }
@@ -812,14 +1264,723 @@ where
 	}
 }
 
+// Adapt `frame_support::traits::fungible::Inspect`
+impl<T, AccountId, Currency, Amount, Moment> fungible::Inspect<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::Inspect<AccountId>,
+{
+	type Balance = <Currency as fungible::Inspect<AccountId>>::Balance;
+
+	fn total_issuance() -> Self::Balance {
+		Currency::total_issuance()
+	}
+
+	fn active_issuance() -> Self::Balance {
+		Currency::active_issuance()
+	}
+
+	fn minimum_balance() -> Self::Balance {
+		Currency::minimum_balance()
+	}
+
+	fn total_balance(who: &AccountId) -> Self::Balance {
+		Currency::total_balance(who)
+	}
+
+	fn balance(who: &AccountId) -> Self::Balance {
+		Currency::balance(who)
+	}
+
+	fn reducible_balance(who: &AccountId, preservation: Preservation, force: Fortitude) -> Self::Balance {
+		Currency::reducible_balance(who, preservation, force)
+	}
+
+	fn can_deposit(who: &AccountId, amount: Self::Balance, provenance: Provenance) -> DepositConsequence {
+		Currency::can_deposit(who, amount, provenance)
+	}
+
+	fn can_withdraw(who: &AccountId, amount: Self::Balance) -> WithdrawConsequence<Self::Balance> {
+		Currency::can_withdraw(who, amount)
+	}
+}
+
+// Adapt `frame_support::traits::fungible::Unbalanced`
+impl<T, AccountId, Currency, Amount, Moment> fungible::Unbalanced<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::Unbalanced<AccountId>,
+{
+	fn handle_dust(dust: fungible::Dust<AccountId, Self>) {
+		Currency::handle_dust(fungible::Dust(dust.0))
+	}
+
+	fn write_balance(who: &AccountId, amount: Self::Balance) -> Result<Option<Self::Balance>, DispatchError> {
+		Currency::write_balance(who, amount)
+	}
+
+	fn set_total_issuance(amount: Self::Balance) {
+		Currency::set_total_issuance(amount)
+	}
+
+	fn decrease_balance(
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::decrease_balance(who, amount, precision, preservation, force)
+	}
+
+	fn increase_balance(
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::increase_balance(who, amount, precision)
+	}
+
+	fn deactivate(amount: Self::Balance) {
+		Currency::deactivate(amount)
+	}
+
+	fn reactivate(amount: Self::Balance) {
+		Currency::reactivate(amount)
+	}
+}
+
+// Adapt `frame_support::traits::fungible::Mutate`
+impl<T, AccountId, Currency, Amount, Moment> fungible::Mutate<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	AccountId: Eq,
+	Currency: fungible::Mutate<AccountId>,
+{
+	fn mint_into(who: &AccountId, amount: Self::Balance) -> Result<Self::Balance, DispatchError> {
+		Currency::mint_into(who, amount)
+	}
+
+	fn burn_from(
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::burn_from(who, amount, precision, force)
+	}
+
+	fn transfer(
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		preservation: Preservation,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::transfer(source, dest, amount, preservation)
+	}
+}
+
+// Adapt `frame_support::traits::fungible::InspectHold`
+impl<T, AccountId, Currency, Amount, Moment> fungible::InspectHold<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::InspectHold<AccountId>,
+{
+	type Reason = <Currency as fungible::InspectHold<AccountId>>::Reason;
+
+	fn total_balance_on_hold(who: &AccountId) -> Self::Balance {
+		Currency::total_balance_on_hold(who)
+	}
+
+	fn reducible_total_balance_on_hold(who: &AccountId, force: Fortitude) -> Self::Balance {
+		Currency::reducible_total_balance_on_hold(who, force)
+	}
+
+	fn balance_on_hold(reason: &Self::Reason, who: &AccountId) -> Self::Balance {
+		Currency::balance_on_hold(reason, who)
+	}
+
+	fn hold_available(reason: &Self::Reason, who: &AccountId) -> bool {
+		Currency::hold_available(reason, who)
+	}
+
+	fn can_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> bool {
+		Currency::can_hold(reason, who, amount)
+	}
+}
+
+// Adapt `frame_support::traits::fungible::UnbalancedHold`
+impl<T, AccountId, Currency, Amount, Moment> fungible::UnbalancedHold<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::UnbalancedHold<AccountId>,
+{
+	fn set_balance_on_hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		Currency::set_balance_on_hold(reason, who, amount)
+	}
+}
+
+// Adapt `frame_support::traits::fungible::MutateHold`
+impl<T, AccountId, Currency, Amount, Moment> fungible::MutateHold<AccountId>
+	for BasicCurrencyAdapter<T, Currency, Amount, Moment>
+where
+	Currency: fungible::MutateHold<AccountId>,
+{
+	fn hold(reason: &Self::Reason, who: &AccountId, amount: Self::Balance) -> DispatchResult {
+		Currency::hold(reason, who, amount)
+	}
+
+	fn release(
+		reason: &Self::Reason,
+		who: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::release(reason, who, amount, precision)
+	}
+
+	fn transfer_on_hold(
+		reason: &Self::Reason,
+		source: &AccountId,
+		dest: &AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		restriction: Restriction,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		Currency::transfer_on_hold(reason, source, dest, amount, precision, restriction, force)
+	}
+}
+
 impl<T: Config> TransferAll<T::AccountId> for Pallet<T> {
 	fn transfer_all(source: &T::AccountId, dest: &T::AccountId) -> DispatchResult {
+		// the backends move every currency of `source`, so none of them may be
+		// paused
+		for currency_id in PausedCurrencies::<T>::iter_keys() {
+			if !<Self as MultiCurrency<_>>::free_balance(currency_id, source).is_zero() {
+				Self::ensure_not_paused(currency_id, source)?;
+			}
+		}
+
 		with_transaction_result(|| {
 			// transfer non-native free to dest
 			T::MultiCurrency::transfer_all(source, dest)?;
+			T::ExternalCurrency::transfer_all(source, dest)?;
 
 			// transfer all free to dest
 			T::NativeCurrency::transfer(source, dest, T::NativeCurrency::free_balance(source))
 		})
 	}
 }
+
+type HoldReasonOf<T> =
+	<<T as Config>::MultiCurrency as fungibles::InspectHold<<T as frame_system::Config>::AccountId>>::Reason;
+
+// The `fungibles` impls route to the `fungible` impls of
+// `Config::NativeCurrency`, and to the `fungibles` impls of
+// `Config::MultiCurrency` and `Config::ExternalCurrency`. A paused currency
+// can't be deposited or withdrawn, except by the `PauseWhitelist`.
+impl<T> fungibles::Inspect<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	T::MultiCurrency: fungibles::Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::ExternalCurrency: fungibles::Inspect<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::NativeCurrency: fungible::Inspect<T::AccountId, Balance = BalanceOf<T>>,
+{
+	type AssetId = CurrencyIdOf<T>;
+	type Balance = BalanceOf<T>;
+
+	fn total_issuance(asset_id: Self::AssetId) -> Self::Balance {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Inspect<_>>::total_issuance(),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Inspect<_>>::total_issuance(asset_id),
+			Backend::External => <T::ExternalCurrency as fungibles::Inspect<_>>::total_issuance(asset_id),
+		}
+	}
+
+	fn active_issuance(asset_id: Self::AssetId) -> Self::Balance {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Inspect<_>>::active_issuance(),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Inspect<_>>::active_issuance(asset_id),
+			Backend::External => <T::ExternalCurrency as fungibles::Inspect<_>>::active_issuance(asset_id),
+		}
+	}
+
+	fn minimum_balance(asset_id: Self::AssetId) -> Self::Balance {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Inspect<_>>::minimum_balance(),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Inspect<_>>::minimum_balance(asset_id),
+			Backend::External => <T::ExternalCurrency as fungibles::Inspect<_>>::minimum_balance(asset_id),
+		}
+	}
+
+	fn total_balance(asset_id: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Inspect<_>>::total_balance(who),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Inspect<_>>::total_balance(asset_id, who),
+			Backend::External => <T::ExternalCurrency as fungibles::Inspect<_>>::total_balance(asset_id, who),
+		}
+	}
+
+	fn balance(asset_id: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Inspect<_>>::balance(who),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Inspect<_>>::balance(asset_id, who),
+			Backend::External => <T::ExternalCurrency as fungibles::Inspect<_>>::balance(asset_id, who),
+		}
+	}
+
+	fn reducible_balance(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Self::Balance {
+		if Self::is_paused(asset_id, who) {
+			return Zero::zero();
+		}
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Inspect<_>>::reducible_balance(who, preservation, force),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::Inspect<_>>::reducible_balance(asset_id, who, preservation, force)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::Inspect<_>>::reducible_balance(asset_id, who, preservation, force)
+			}
+		}
+	}
+
+	fn can_deposit(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		provenance: Provenance,
+	) -> DepositConsequence {
+		if Self::is_paused(asset_id, who) {
+			return DepositConsequence::Blocked;
+		}
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Inspect<_>>::can_deposit(who, amount, provenance),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::Inspect<_>>::can_deposit(asset_id, who, amount, provenance)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::Inspect<_>>::can_deposit(asset_id, who, amount, provenance)
+			}
+		}
+	}
+
+	fn can_withdraw(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> WithdrawConsequence<Self::Balance> {
+		if Self::is_paused(asset_id, who) {
+			return WithdrawConsequence::Frozen;
+		}
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Inspect<_>>::can_withdraw(who, amount),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Inspect<_>>::can_withdraw(asset_id, who, amount),
+			Backend::External => <T::ExternalCurrency as fungibles::Inspect<_>>::can_withdraw(asset_id, who, amount),
+		}
+	}
+
+	fn asset_exists(asset_id: Self::AssetId) -> bool {
+		match Self::backend(asset_id) {
+			Backend::Native => true,
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Inspect<_>>::asset_exists(asset_id),
+			Backend::External => <T::ExternalCurrency as fungibles::Inspect<_>>::asset_exists(asset_id),
+		}
+	}
+}
+
+impl<T> fungibles::Unbalanced<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	T::MultiCurrency: fungibles::Unbalanced<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::ExternalCurrency: fungibles::Unbalanced<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::NativeCurrency: fungible::Unbalanced<T::AccountId, Balance = BalanceOf<T>>,
+{
+	fn handle_dust(dust: fungibles::Dust<T::AccountId, Self>) {
+		match Self::backend(dust.0) {
+			Backend::Native => <T::NativeCurrency as fungible::Unbalanced<_>>::handle_dust(fungible::Dust(dust.1)),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::Unbalanced<_>>::handle_dust(fungibles::Dust(dust.0, dust.1))
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::Unbalanced<_>>::handle_dust(fungibles::Dust(dust.0, dust.1))
+			}
+		}
+	}
+
+	fn write_balance(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Option<Self::Balance>, DispatchError> {
+		Self::ensure_not_paused(asset_id, who)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Unbalanced<_>>::write_balance(who, amount),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::Unbalanced<_>>::write_balance(asset_id, who, amount)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::Unbalanced<_>>::write_balance(asset_id, who, amount)
+			}
+		}
+	}
+
+	fn set_total_issuance(asset_id: Self::AssetId, amount: Self::Balance) {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Unbalanced<_>>::set_total_issuance(amount),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::Unbalanced<_>>::set_total_issuance(asset_id, amount)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::Unbalanced<_>>::set_total_issuance(asset_id, amount)
+			}
+		}
+	}
+
+	fn decrease_balance(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		preservation: Preservation,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_paused(asset_id, who)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Unbalanced<_>>::decrease_balance(
+				who,
+				amount,
+				precision,
+				preservation,
+				force,
+			),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Unbalanced<_>>::decrease_balance(
+				asset_id,
+				who,
+				amount,
+				precision,
+				preservation,
+				force,
+			),
+			Backend::External => <T::ExternalCurrency as fungibles::Unbalanced<_>>::decrease_balance(
+				asset_id,
+				who,
+				amount,
+				precision,
+				preservation,
+				force,
+			),
+		}
+	}
+
+	fn increase_balance(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_paused(asset_id, who)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Unbalanced<_>>::increase_balance(who, amount, precision),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::Unbalanced<_>>::increase_balance(asset_id, who, amount, precision)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::Unbalanced<_>>::increase_balance(asset_id, who, amount, precision)
+			}
+		}
+	}
+
+	fn deactivate(asset_id: Self::AssetId, amount: Self::Balance) {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Unbalanced<_>>::deactivate(amount),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Unbalanced<_>>::deactivate(asset_id, amount),
+			Backend::External => <T::ExternalCurrency as fungibles::Unbalanced<_>>::deactivate(asset_id, amount),
+		}
+	}
+
+	fn reactivate(asset_id: Self::AssetId, amount: Self::Balance) {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Unbalanced<_>>::reactivate(amount),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Unbalanced<_>>::reactivate(asset_id, amount),
+			Backend::External => <T::ExternalCurrency as fungibles::Unbalanced<_>>::reactivate(asset_id, amount),
+		}
+	}
+}
+
+impl<T> fungibles::Mutate<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	T::MultiCurrency: fungibles::Mutate<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::ExternalCurrency: fungibles::Mutate<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::NativeCurrency: fungible::Mutate<T::AccountId, Balance = BalanceOf<T>>,
+{
+	fn mint_into(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_paused(asset_id, who)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Mutate<_>>::mint_into(who, amount),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::Mutate<_>>::mint_into(asset_id, who, amount),
+			Backend::External => <T::ExternalCurrency as fungibles::Mutate<_>>::mint_into(asset_id, who, amount),
+		}
+	}
+
+	fn burn_from(
+		asset_id: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_paused(asset_id, who)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Mutate<_>>::burn_from(who, amount, precision, force),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::Mutate<_>>::burn_from(asset_id, who, amount, precision, force)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::Mutate<_>>::burn_from(asset_id, who, amount, precision, force)
+			}
+		}
+	}
+
+	fn transfer(
+		asset_id: Self::AssetId,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		preservation: Preservation,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_paused(asset_id, source)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::Mutate<_>>::transfer(source, dest, amount, preservation),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::Mutate<_>>::transfer(asset_id, source, dest, amount, preservation)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::Mutate<_>>::transfer(asset_id, source, dest, amount, preservation)
+			}
+		}
+	}
+}
+
+impl<T> fungibles::Balanced<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	T::MultiCurrency: fungibles::Unbalanced<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::ExternalCurrency: fungibles::Unbalanced<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::NativeCurrency: fungible::Unbalanced<T::AccountId, Balance = BalanceOf<T>>,
+{
+	type OnDropDebt = fungibles::IncreaseIssuance<T::AccountId, Self>;
+	type OnDropCredit = fungibles::DecreaseIssuance<T::AccountId, Self>;
+}
+
+impl<T> fungibles::InspectHold<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	T::MultiCurrency: fungibles::InspectHold<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::ExternalCurrency: fungibles::InspectHold<
+		T::AccountId,
+		AssetId = CurrencyIdOf<T>,
+		Balance = BalanceOf<T>,
+		Reason = HoldReasonOf<T>,
+	>,
+	T::NativeCurrency: fungible::InspectHold<T::AccountId, Balance = BalanceOf<T>, Reason = HoldReasonOf<T>>,
+{
+	type Reason = HoldReasonOf<T>;
+
+	fn total_balance_on_hold(asset_id: Self::AssetId, who: &T::AccountId) -> Self::Balance {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::InspectHold<_>>::total_balance_on_hold(who),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::InspectHold<_>>::total_balance_on_hold(asset_id, who)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::InspectHold<_>>::total_balance_on_hold(asset_id, who)
+			}
+		}
+	}
+
+	fn reducible_total_balance_on_hold(asset_id: Self::AssetId, who: &T::AccountId, force: Fortitude) -> Self::Balance {
+		match Self::backend(asset_id) {
+			Backend::Native => {
+				<T::NativeCurrency as fungible::InspectHold<_>>::reducible_total_balance_on_hold(who, force)
+			}
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::InspectHold<_>>::reducible_total_balance_on_hold(asset_id, who, force)
+			}
+			Backend::External => <T::ExternalCurrency as fungibles::InspectHold<_>>::reducible_total_balance_on_hold(
+				asset_id, who, force,
+			),
+		}
+	}
+
+	fn balance_on_hold(asset_id: Self::AssetId, reason: &Self::Reason, who: &T::AccountId) -> Self::Balance {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::InspectHold<_>>::balance_on_hold(reason, who),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::InspectHold<_>>::balance_on_hold(asset_id, reason, who)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::InspectHold<_>>::balance_on_hold(asset_id, reason, who)
+			}
+		}
+	}
+
+	fn hold_available(asset_id: Self::AssetId, reason: &Self::Reason, who: &T::AccountId) -> bool {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::InspectHold<_>>::hold_available(reason, who),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::InspectHold<_>>::hold_available(asset_id, reason, who)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::InspectHold<_>>::hold_available(asset_id, reason, who)
+			}
+		}
+	}
+
+	fn can_hold(asset_id: Self::AssetId, reason: &Self::Reason, who: &T::AccountId, amount: Self::Balance) -> bool {
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::InspectHold<_>>::can_hold(reason, who, amount),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::InspectHold<_>>::can_hold(asset_id, reason, who, amount)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::InspectHold<_>>::can_hold(asset_id, reason, who, amount)
+			}
+		}
+	}
+}
+
+impl<T> fungibles::UnbalancedHold<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	T::MultiCurrency: fungibles::UnbalancedHold<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::ExternalCurrency: fungibles::UnbalancedHold<
+		T::AccountId,
+		AssetId = CurrencyIdOf<T>,
+		Balance = BalanceOf<T>,
+		Reason = HoldReasonOf<T>,
+	>,
+	T::NativeCurrency: fungible::UnbalancedHold<T::AccountId, Balance = BalanceOf<T>, Reason = HoldReasonOf<T>>,
+{
+	fn set_balance_on_hold(
+		asset_id: Self::AssetId,
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::ensure_not_paused(asset_id, who)?;
+		match Self::backend(asset_id) {
+			Backend::Native => {
+				<T::NativeCurrency as fungible::UnbalancedHold<_>>::set_balance_on_hold(reason, who, amount)
+			}
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::UnbalancedHold<_>>::set_balance_on_hold(asset_id, reason, who, amount)
+			}
+			Backend::External => <T::ExternalCurrency as fungibles::UnbalancedHold<_>>::set_balance_on_hold(
+				asset_id, reason, who, amount,
+			),
+		}
+	}
+}
+
+impl<T> fungibles::MutateHold<T::AccountId> for Pallet<T>
+where
+	T: Config,
+	T::MultiCurrency: fungibles::MutateHold<T::AccountId, AssetId = CurrencyIdOf<T>, Balance = BalanceOf<T>>,
+	T::ExternalCurrency: fungibles::MutateHold<
+		T::AccountId,
+		AssetId = CurrencyIdOf<T>,
+		Balance = BalanceOf<T>,
+		Reason = HoldReasonOf<T>,
+	>,
+	T::NativeCurrency: fungible::MutateHold<T::AccountId, Balance = BalanceOf<T>, Reason = HoldReasonOf<T>>,
+{
+	fn hold(
+		asset_id: Self::AssetId,
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::ensure_not_paused(asset_id, who)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::MutateHold<_>>::hold(reason, who, amount),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::MutateHold<_>>::hold(asset_id, reason, who, amount)
+			}
+			Backend::External => <T::ExternalCurrency as fungibles::MutateHold<_>>::hold(asset_id, reason, who, amount),
+		}
+	}
+
+	fn release(
+		asset_id: Self::AssetId,
+		reason: &Self::Reason,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_paused(asset_id, who)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::MutateHold<_>>::release(reason, who, amount, precision),
+			Backend::MultiCurrency => {
+				<T::MultiCurrency as fungibles::MutateHold<_>>::release(asset_id, reason, who, amount, precision)
+			}
+			Backend::External => {
+				<T::ExternalCurrency as fungibles::MutateHold<_>>::release(asset_id, reason, who, amount, precision)
+			}
+		}
+	}
+
+	fn transfer_on_hold(
+		asset_id: Self::AssetId,
+		reason: &Self::Reason,
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+		restriction: Restriction,
+		force: Fortitude,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_paused(asset_id, source)?;
+		match Self::backend(asset_id) {
+			Backend::Native => <T::NativeCurrency as fungible::MutateHold<_>>::transfer_on_hold(
+				reason,
+				source,
+				dest,
+				amount,
+				precision,
+				restriction,
+				force,
+			),
+			Backend::MultiCurrency => <T::MultiCurrency as fungibles::MutateHold<_>>::transfer_on_hold(
+				asset_id,
+				reason,
+				source,
+				dest,
+				amount,
+				precision,
+				restriction,
+				force,
+			),
+			Backend::External => <T::ExternalCurrency as fungibles::MutateHold<_>>::transfer_on_hold(
+				asset_id,
+				reason,
+				source,
+				dest,
+				amount,
+				precision,
+				restriction,
+				force,
+			),
+		}
+	}
+}
@@ -8,12 +8,13 @@ use frame_support::{
 	traits::{ConstU32, ConstU64, Everything, Nothing},
 	PalletId,
 };
-use orml_traits::{currency::MutationHooks, parameter_type_with_key};
+use orml_traits::{arithmetic::SignedBalance, currency::MutationHooks, parameter_type_with_key};
 use sp_core::H256;
 use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup},
 	AccountId32, BuildStorage,
 };
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 
 use crate as currencies;
 
@@ -59,9 +60,9 @@ impl pallet_balances::Config for Runtime {
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = ReserveIdentifier;
 	type WeightInfo = ();
-	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeHoldReason = ();
 	type FreezeIdentifier = [u8; 8];
-	type MaxHolds = ();
+	type MaxHolds = ConstU32<1>;
 	type MaxFreezes = ();
 }
 
@@ -86,6 +87,10 @@ where
 	type PostDeposit = ();
 	type PreTransfer = ();
 	type PostTransfer = ();
+	type PreReserve = ();
+	type PostReserve = ();
+	type OnUnreserve = ();
+	type TransferFee = ();
 	type OnNewTokenAccount = ();
 	type OnKilledTokenAccount = ();
 }
@@ -102,20 +107,430 @@ impl orml_tokens::Config for Runtime {
 	type MaxReserves = ConstU32<100_000>;
 	type ReserveIdentifier = ReserveIdentifier;
 	type DustRemovalWhitelist = Nothing;
+	type RuntimeHoldReason = ();
+	type MaxHolds = ConstU32<1>;
+	type RuntimeFreezeReason = ();
+	type MaxFreezes = ();
+	type Sufficiency = orml_tokens::AllSufficient;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransfersPerBatch = ();
+	type MaxIssuance = orml_tokens::NoIssuanceCap;
+	type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxSnapshots = ConstU32<10>;
+	type ApprovalDeposit = orml_tokens::NoApprovalDeposit;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkCurrencyId = ConstU32<X_TOKEN_ID>;
 }
 
 pub const NATIVE_CURRENCY_ID: CurrencyId = 1;
 pub const X_TOKEN_ID: CurrencyId = 2;
+pub const CONTRACT_TOKEN_ID: CurrencyId = 3;
+
+thread_local! {
+	static CONTRACT_BALANCES: RefCell<BTreeMap<(CurrencyId, AccountId), (Balance, Balance)>> = RefCell::new(BTreeMap::new());
+	static CONTRACT_LOCKS: RefCell<BTreeMap<(CurrencyId, AccountId, LockIdentifier), Balance>> = RefCell::new(BTreeMap::new());
+	static CONTRACT_NAMED_RESERVES: RefCell<BTreeMap<(ReserveIdentifier, CurrencyId, AccountId), Balance>> = RefCell::new(BTreeMap::new());
+}
+
+/// A currency backend mocking assets held in smart-contract storage.
+pub struct MockContractCurrency;
+impl MockContractCurrency {
+	fn balances(currency_id: CurrencyId, who: &AccountId) -> (Balance, Balance) {
+		CONTRACT_BALANCES.with(|v| v.borrow().get(&(currency_id, who.clone())).copied().unwrap_or_default())
+	}
+
+	fn set_balances(currency_id: CurrencyId, who: &AccountId, free: Balance, reserved: Balance) {
+		CONTRACT_BALANCES.with(|v| v.borrow_mut().insert((currency_id, who.clone()), (free, reserved)));
+	}
+
+	fn frozen(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		CONTRACT_LOCKS.with(|v| {
+			v.borrow()
+				.iter()
+				.filter(|((c, a, _), _)| *c == currency_id && a == who)
+				.map(|(_, amount)| *amount)
+				.max()
+				.unwrap_or_default()
+		})
+	}
+
+	fn named_reserve(id: &ReserveIdentifier, currency_id: CurrencyId, who: &AccountId) -> Balance {
+		CONTRACT_NAMED_RESERVES.with(|v| {
+			v.borrow()
+				.get(&(*id, currency_id, who.clone()))
+				.copied()
+				.unwrap_or_default()
+		})
+	}
+
+	fn set_named_reserve(id: &ReserveIdentifier, currency_id: CurrencyId, who: &AccountId, amount: Balance) {
+		CONTRACT_NAMED_RESERVES.with(|v| v.borrow_mut().insert((*id, currency_id, who.clone()), amount));
+	}
+}
+
+impl MultiCurrency<AccountId> for MockContractCurrency {
+	type CurrencyId = CurrencyId;
+	type Balance = Balance;
+
+	fn minimum_balance(_currency_id: CurrencyId) -> Balance {
+		0
+	}
+
+	fn total_issuance(currency_id: CurrencyId) -> Balance {
+		CONTRACT_BALANCES.with(|v| {
+			v.borrow()
+				.iter()
+				.filter(|((c, _), _)| *c == currency_id)
+				.map(|(_, (free, reserved))| free + reserved)
+				.sum()
+		})
+	}
+
+	fn total_balance(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		let (free, reserved) = Self::balances(currency_id, who);
+		free + reserved
+	}
+
+	fn free_balance(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		Self::balances(currency_id, who).0
+	}
+
+	fn ensure_can_withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		let new_balance = Self::free_balance(currency_id, who)
+			.checked_sub(amount)
+			.ok_or(Error::<Runtime>::BalanceTooLow)?;
+		ensure!(
+			new_balance >= Self::frozen(currency_id, who),
+			Error::<Runtime>::BalanceTooLow
+		);
+		Ok(())
+	}
+
+	fn transfer(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) -> DispatchResult {
+		Self::withdraw(currency_id, from, amount)?;
+		Self::deposit(currency_id, to, amount)
+	}
+
+	fn deposit(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		let (free, reserved) = Self::balances(currency_id, who);
+		Self::set_balances(currency_id, who, free + amount, reserved);
+		Ok(())
+	}
+
+	fn withdraw(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		Self::ensure_can_withdraw(currency_id, who, amount)?;
+		let (free, reserved) = Self::balances(currency_id, who);
+		Self::set_balances(currency_id, who, free - amount, reserved);
+		Ok(())
+	}
+
+	fn can_slash(currency_id: CurrencyId, who: &AccountId, value: Balance) -> bool {
+		Self::free_balance(currency_id, who) >= value
+	}
+
+	fn slash(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> Balance {
+		let (free, reserved) = Self::balances(currency_id, who);
+		let actual = free.min(amount);
+		Self::set_balances(currency_id, who, free - actual, reserved);
+		amount - actual
+	}
+}
+
+impl MultiCurrencyExtended<AccountId> for MockContractCurrency {
+	type Amount = i64;
+
+	fn update_balance(currency_id: CurrencyId, who: &AccountId, by_amount: i64) -> DispatchResult {
+		match SignedBalance::try_from_amount(by_amount).ok_or(Error::<Runtime>::AmountIntoBalanceFailed)? {
+			SignedBalance::Positive(by_balance) => Self::deposit(currency_id, who, by_balance),
+			SignedBalance::Negative(by_balance) => Self::withdraw(currency_id, who, by_balance),
+		}
+	}
+}
+
+impl MultiLockableCurrency<AccountId> for MockContractCurrency {
+	type Moment = u64;
+
+	fn set_lock(lock_id: LockIdentifier, currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		CONTRACT_LOCKS.with(|v| v.borrow_mut().insert((currency_id, who.clone(), lock_id), amount));
+		Ok(())
+	}
+
+	fn extend_lock(
+		lock_id: LockIdentifier,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		CONTRACT_LOCKS.with(|v| {
+			let mut locks = v.borrow_mut();
+			let lock = locks.entry((currency_id, who.clone(), lock_id)).or_default();
+			*lock = (*lock).max(amount);
+		});
+		Ok(())
+	}
+
+	fn remove_lock(lock_id: LockIdentifier, currency_id: CurrencyId, who: &AccountId) -> DispatchResult {
+		CONTRACT_LOCKS.with(|v| v.borrow_mut().remove(&(currency_id, who.clone(), lock_id)));
+		Ok(())
+	}
+}
+
+impl MultiReservableCurrency<AccountId> for MockContractCurrency {
+	fn can_reserve(currency_id: CurrencyId, who: &AccountId, value: Balance) -> bool {
+		Self::ensure_can_withdraw(currency_id, who, value).is_ok()
+	}
+
+	fn slash_reserved(currency_id: CurrencyId, who: &AccountId, value: Balance) -> Balance {
+		let (free, reserved) = Self::balances(currency_id, who);
+		let actual = reserved.min(value);
+		Self::set_balances(currency_id, who, free, reserved - actual);
+		value - actual
+	}
+
+	fn reserved_balance(currency_id: CurrencyId, who: &AccountId) -> Balance {
+		Self::balances(currency_id, who).1
+	}
+
+	fn reserve(currency_id: CurrencyId, who: &AccountId, value: Balance) -> DispatchResult {
+		Self::ensure_can_withdraw(currency_id, who, value)?;
+		let (free, reserved) = Self::balances(currency_id, who);
+		Self::set_balances(currency_id, who, free - value, reserved + value);
+		Ok(())
+	}
+
+	fn unreserve(currency_id: CurrencyId, who: &AccountId, value: Balance) -> Balance {
+		let (free, reserved) = Self::balances(currency_id, who);
+		let actual = reserved.min(value);
+		Self::set_balances(currency_id, who, free + actual, reserved - actual);
+		value - actual
+	}
+
+	fn repatriate_reserved(
+		currency_id: CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Balance,
+		status: BalanceStatus,
+	) -> result::Result<Balance, DispatchError> {
+		let actual = Self::reserved_balance(currency_id, slashed).min(value);
+		let (free, reserved) = Self::balances(currency_id, slashed);
+		Self::set_balances(currency_id, slashed, free, reserved - actual);
+		let (free, reserved) = Self::balances(currency_id, beneficiary);
+		match status {
+			BalanceStatus::Free => Self::set_balances(currency_id, beneficiary, free + actual, reserved),
+			BalanceStatus::Reserved => Self::set_balances(currency_id, beneficiary, free, reserved + actual),
+		}
+		Ok(value - actual)
+	}
+}
+
+impl NamedMultiReservableCurrency<AccountId> for MockContractCurrency {
+	type ReserveIdentifier = ReserveIdentifier;
+
+	fn slash_reserved_named(
+		id: &ReserveIdentifier,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		value: Balance,
+	) -> Balance {
+		let named = Self::named_reserve(id, currency_id, who);
+		let actual = named.min(value) - Self::slash_reserved(currency_id, who, named.min(value));
+		Self::set_named_reserve(id, currency_id, who, named - actual);
+		value - actual
+	}
+
+	fn reserved_balance_named(id: &ReserveIdentifier, currency_id: CurrencyId, who: &AccountId) -> Balance {
+		Self::named_reserve(id, currency_id, who)
+	}
+
+	fn reserve_named(
+		id: &ReserveIdentifier,
+		currency_id: CurrencyId,
+		who: &AccountId,
+		value: Balance,
+	) -> DispatchResult {
+		Self::reserve(currency_id, who, value)?;
+		Self::set_named_reserve(id, currency_id, who, Self::named_reserve(id, currency_id, who) + value);
+		Ok(())
+	}
+
+	fn unreserve_named(id: &ReserveIdentifier, currency_id: CurrencyId, who: &AccountId, value: Balance) -> Balance {
+		let named = Self::named_reserve(id, currency_id, who);
+		let actual = named.min(value) - Self::unreserve(currency_id, who, named.min(value));
+		Self::set_named_reserve(id, currency_id, who, named - actual);
+		value - actual
+	}
+
+	fn repatriate_reserved_named(
+		id: &ReserveIdentifier,
+		currency_id: CurrencyId,
+		slashed: &AccountId,
+		beneficiary: &AccountId,
+		value: Balance,
+		status: BalanceStatus,
+	) -> result::Result<Balance, DispatchError> {
+		let named = Self::named_reserve(id, currency_id, slashed);
+		let actual =
+			named.min(value) - Self::repatriate_reserved(currency_id, slashed, beneficiary, named.min(value), status)?;
+		Self::set_named_reserve(id, currency_id, slashed, named - actual);
+		if status == BalanceStatus::Reserved {
+			let beneficiary_named = Self::named_reserve(id, currency_id, beneficiary);
+			Self::set_named_reserve(id, currency_id, beneficiary, beneficiary_named + actual);
+		}
+		Ok(value - actual)
+	}
+}
+
+impl TransferAll<AccountId> for MockContractCurrency {
+	fn transfer_all(source: &AccountId, dest: &AccountId) -> DispatchResult {
+		let currency_ids: Vec<CurrencyId> = CONTRACT_BALANCES.with(|v| {
+			v.borrow()
+				.keys()
+				.filter(|(_, who)| who == source)
+				.map(|(currency_id, _)| *currency_id)
+				.collect()
+		});
+		for currency_id in currency_ids {
+			let amount = Self::free_balance(currency_id, source).saturating_sub(Self::frozen(currency_id, source));
+			Self::transfer(currency_id, source, dest, amount)?;
+		}
+		Ok(())
+	}
+}
+
+impl fungibles::Inspect<AccountId> for MockContractCurrency {
+	type AssetId = CurrencyId;
+	type Balance = Balance;
+
+	fn total_issuance(asset_id: CurrencyId) -> Balance {
+		<Self as MultiCurrency<_>>::total_issuance(asset_id)
+	}
+
+	fn minimum_balance(_asset_id: CurrencyId) -> Balance {
+		0
+	}
+
+	fn total_balance(asset_id: CurrencyId, who: &AccountId) -> Balance {
+		<Self as MultiCurrency<_>>::total_balance(asset_id, who)
+	}
+
+	fn balance(asset_id: CurrencyId, who: &AccountId) -> Balance {
+		Self::free_balance(asset_id, who)
+	}
+
+	fn reducible_balance(asset_id: CurrencyId, who: &AccountId, _: Preservation, _: Fortitude) -> Balance {
+		Self::free_balance(asset_id, who).saturating_sub(Self::frozen(asset_id, who))
+	}
+
+	fn can_deposit(asset_id: CurrencyId, _who: &AccountId, amount: Balance, _: Provenance) -> DepositConsequence {
+		match <Self as MultiCurrency<_>>::total_issuance(asset_id).checked_add(amount) {
+			Some(_) => DepositConsequence::Success,
+			None => DepositConsequence::Overflow,
+		}
+	}
+
+	fn can_withdraw(asset_id: CurrencyId, who: &AccountId, amount: Balance) -> WithdrawConsequence<Balance> {
+		if Self::free_balance(asset_id, who) < amount {
+			WithdrawConsequence::BalanceLow
+		} else if Self::ensure_can_withdraw(asset_id, who, amount).is_err() {
+			WithdrawConsequence::Frozen
+		} else {
+			WithdrawConsequence::Success
+		}
+	}
+
+	fn asset_exists(_asset_id: CurrencyId) -> bool {
+		true
+	}
+}
+
+impl fungibles::Unbalanced<AccountId> for MockContractCurrency {
+	fn handle_dust(_dust: fungibles::Dust<AccountId, Self>) {}
+
+	fn write_balance(asset_id: CurrencyId, who: &AccountId, amount: Balance) -> Result<Option<Balance>, DispatchError> {
+		let (_, reserved) = Self::balances(asset_id, who);
+		Self::set_balances(asset_id, who, amount, reserved);
+		Ok(None)
+	}
+
+	// The issuance is the sum of the balances.
+	fn set_total_issuance(_asset_id: CurrencyId, _amount: Balance) {}
+}
+
+impl fungibles::Mutate<AccountId> for MockContractCurrency {}
+
+// The reserved balance is all on hold.
+impl fungibles::InspectHold<AccountId> for MockContractCurrency {
+	type Reason = ();
+
+	fn total_balance_on_hold(asset_id: CurrencyId, who: &AccountId) -> Balance {
+		Self::reserved_balance(asset_id, who)
+	}
+
+	fn reducible_total_balance_on_hold(asset_id: CurrencyId, who: &AccountId, _: Fortitude) -> Balance {
+		Self::reserved_balance(asset_id, who)
+	}
+
+	fn balance_on_hold(asset_id: CurrencyId, _reason: &(), who: &AccountId) -> Balance {
+		Self::reserved_balance(asset_id, who)
+	}
+
+	fn hold_available(_asset_id: CurrencyId, _reason: &(), _who: &AccountId) -> bool {
+		true
+	}
+
+	fn can_hold(asset_id: CurrencyId, _reason: &(), who: &AccountId, amount: Balance) -> bool {
+		Self::can_reserve(asset_id, who, amount)
+	}
+}
+
+impl fungibles::UnbalancedHold<AccountId> for MockContractCurrency {
+	fn set_balance_on_hold(asset_id: CurrencyId, _reason: &(), who: &AccountId, amount: Balance) -> DispatchResult {
+		let (free, _) = Self::balances(asset_id, who);
+		Self::set_balances(asset_id, who, free, amount);
+		Ok(())
+	}
+}
+
+impl fungibles::MutateHold<AccountId> for MockContractCurrency {}
+
+parameter_type_with_key! {
+	pub CurrencyBackend: |currency_id: CurrencyId| -> Backend {
+		if *currency_id == CONTRACT_TOKEN_ID {
+			Backend::External
+		} else {
+			Backend::MultiCurrency
+		}
+	};
+}
 
 parameter_types! {
 	pub const GetNativeCurrencyId: CurrencyId = NATIVE_CURRENCY_ID;
+	pub const SwapReserveId: ReserveIdentifier = *b"orml/swp";
+	pub const SwapDeposit: Balance = 5;
+}
+
+pub struct MockPauseWhitelist;
+impl Contains<AccountId> for MockPauseWhitelist {
+	fn contains(a: &AccountId) -> bool {
+		*a == TREASURY_ACCOUNT
+	}
 }
 
 impl Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
 	type MultiCurrency = Tokens;
 	type NativeCurrency = AdaptedBasicCurrency;
 	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type ExternalCurrency = MockContractCurrency;
+	type CurrencyBackend = CurrencyBackend;
+	type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+	type PauseWhitelist = MockPauseWhitelist;
+	type SwapReserveId = SwapReserveId;
+	type SwapDeposit = SwapDeposit;
 	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkCurrencyId = ConstU32<X_TOKEN_ID>;
 }
 pub type NativeCurrency = NativeCurrencyOf<Runtime>;
 pub type AdaptedBasicCurrency = BasicCurrencyAdapter<Runtime, PalletBalances, i64, u64>;
@@ -134,6 +549,7 @@ construct_runtime!(
 pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
 pub const BOB: AccountId = AccountId32::new([2u8; 32]);
 pub const EVA: AccountId = AccountId32::new([5u8; 32]);
+pub const TREASURY_ACCOUNT: AccountId = AccountId32::new([6u8; 32]);
 pub const ID_1: LockIdentifier = *b"1       ";
 pub const RID_1: ReserveIdentifier = [1u8; 8];
 pub const RID_2: ReserveIdentifier = [2u8; 8];
@@ -143,6 +559,21 @@ pub struct ExtBuilder {
 	balances: Vec<(AccountId, CurrencyId, Balance)>,
 }
 
+/// Test externalities checking the invariants of tokens and currencies after
+/// each execution.
+pub struct TestExternalities(sp_io::TestExternalities);
+
+impl TestExternalities {
+	pub fn execute_with<R>(&mut self, execute: impl FnOnce() -> R) -> R {
+		self.0.execute_with(|| {
+			let result = execute();
+			Tokens::do_try_state().expect("token accounts invariants should hold");
+			Currencies::do_try_state().expect("swaps invariants should hold");
+			result
+		})
+	}
+}
+
 impl ExtBuilder {
 	pub fn balances(mut self, balances: Vec<(AccountId, CurrencyId, Balance)>) -> Self {
 		self.balances = balances;
@@ -158,7 +589,7 @@ impl ExtBuilder {
 		])
 	}
 
-	pub fn build(self) -> sp_io::TestExternalities {
+	pub fn build(self) -> TestExternalities {
 		let mut t = frame_system::GenesisConfig::<Runtime>::default()
 			.build_storage()
 			.unwrap();
@@ -185,6 +616,6 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
-		t.into()
+		TestExternalities(t.into())
 	}
 }
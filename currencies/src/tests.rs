@@ -3,7 +3,12 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::tokens::{
+		fungibles, DepositConsequence, Fortitude, Precision, Preservation, Provenance, Restriction, WithdrawConsequence,
+	},
+};
 use mock::*;
 use sp_runtime::traits::BadOrigin;
 
@@ -372,6 +377,7 @@ fn call_event_should_work() {
 				from: ALICE,
 				to: BOB,
 				amount: 50,
+				fee: 0,
 			}));
 
 			assert_ok!(<Currencies as MultiCurrency<AccountId>>::transfer(
@@ -384,6 +390,7 @@ fn call_event_should_work() {
 				from: ALICE,
 				to: BOB,
 				amount: 10,
+				fee: 0,
 			}));
 
 			assert_ok!(<Currencies as MultiCurrency<AccountId>>::deposit(
@@ -407,3 +414,531 @@ fn call_event_should_work() {
 			}));
 		});
 }
+
+#[test]
+fn pause_currency_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(Currencies::pause_currency(Some(ALICE).into(), X_TOKEN_ID), BadOrigin);
+			assert_ok!(Currencies::pause_currency(RuntimeOrigin::root(), X_TOKEN_ID));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::CurrencyPaused {
+				currency_id: X_TOKEN_ID,
+			}));
+
+			assert_noop!(
+				Currencies::transfer(Some(ALICE).into(), BOB, X_TOKEN_ID, 50),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_noop!(
+				Currencies::update_balance(RuntimeOrigin::root(), ALICE, X_TOKEN_ID, 10),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_noop!(
+				Currencies::reserve(X_TOKEN_ID, &ALICE, 10),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert!(!Currencies::can_reserve(X_TOKEN_ID, &ALICE, 10));
+			assert_eq!(Currencies::slash(X_TOKEN_ID, &ALICE, 10), 10);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 100);
+
+			// other currencies are not affected
+			assert_ok!(Currencies::transfer_native_currency(Some(ALICE).into(), BOB, 10));
+
+			// the whitelisted accounts can still move the paused currency
+			assert_ok!(Currencies::deposit(X_TOKEN_ID, &TREASURY_ACCOUNT, 50));
+			assert_ok!(Currencies::transfer(Some(TREASURY_ACCOUNT).into(), BOB, X_TOKEN_ID, 20));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 120);
+
+			assert_ok!(Currencies::pause_currency(RuntimeOrigin::root(), NATIVE_CURRENCY_ID));
+			assert_noop!(
+				Currencies::transfer_native_currency(Some(ALICE).into(), BOB, 10),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_noop!(
+				<Currencies as TransferAll<AccountId>>::transfer_all(&ALICE, &BOB),
+				Error::<Runtime>::CurrencyPaused
+			);
+
+			assert_ok!(Currencies::unpause_currency(RuntimeOrigin::root(), X_TOKEN_ID));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::CurrencyUnpaused {
+				currency_id: X_TOKEN_ID,
+			}));
+			assert_ok!(Currencies::transfer(Some(ALICE).into(), BOB, X_TOKEN_ID, 50));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 50);
+		});
+}
+
+#[test]
+fn external_backend_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_eq!(Currencies::backend(NATIVE_CURRENCY_ID), Backend::Native);
+			assert_eq!(Currencies::backend(X_TOKEN_ID), Backend::MultiCurrency);
+			assert_eq!(Currencies::backend(CONTRACT_TOKEN_ID), Backend::External);
+
+			assert_ok!(Currencies::deposit(CONTRACT_TOKEN_ID, &ALICE, 100));
+			assert_eq!(MockContractCurrency::free_balance(CONTRACT_TOKEN_ID, &ALICE), 100);
+			assert_eq!(Tokens::free_balance(CONTRACT_TOKEN_ID, &ALICE), 0);
+
+			assert_ok!(Currencies::transfer(Some(ALICE).into(), BOB, CONTRACT_TOKEN_ID, 30));
+			assert_ok!(Currencies::update_balance(
+				RuntimeOrigin::root(),
+				BOB,
+				CONTRACT_TOKEN_ID,
+				-10
+			));
+			assert_eq!(Currencies::free_balance(CONTRACT_TOKEN_ID, &ALICE), 70);
+			assert_eq!(Currencies::free_balance(CONTRACT_TOKEN_ID, &BOB), 20);
+			assert_eq!(Currencies::total_issuance(CONTRACT_TOKEN_ID), 90);
+
+			assert_ok!(Currencies::set_lock(ID_1, CONTRACT_TOKEN_ID, &ALICE, 50));
+			assert_noop!(
+				Currencies::transfer(Some(ALICE).into(), BOB, CONTRACT_TOKEN_ID, 30),
+				Error::<Runtime>::BalanceTooLow
+			);
+			assert_ok!(Currencies::remove_lock(ID_1, CONTRACT_TOKEN_ID, &ALICE));
+
+			assert_ok!(Currencies::reserve_named(&RID_1, CONTRACT_TOKEN_ID, &ALICE, 20));
+			assert_eq!(
+				Currencies::reserved_balance_named(&RID_1, CONTRACT_TOKEN_ID, &ALICE),
+				20
+			);
+			assert_eq!(
+				Currencies::repatriate_reserved_named(&RID_1, CONTRACT_TOKEN_ID, &ALICE, &BOB, 5, BalanceStatus::Free),
+				Ok(0)
+			);
+			assert_eq!(Currencies::unreserve_named(&RID_1, CONTRACT_TOKEN_ID, &ALICE, 20), 5);
+			assert_eq!(Currencies::reserved_balance(CONTRACT_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(CONTRACT_TOKEN_ID, &ALICE), 65);
+			assert_eq!(Currencies::free_balance(CONTRACT_TOKEN_ID, &BOB), 25);
+
+			assert_ok!(<Currencies as TransferAll<AccountId>>::transfer_all(&ALICE, &EVA));
+			assert_eq!(Currencies::free_balance(CONTRACT_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(CONTRACT_TOKEN_ID, &EVA), 65);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &EVA), 100);
+
+			// the other backends are left untouched
+			assert_eq!(Tokens::total_issuance(CONTRACT_TOKEN_ID), 0);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &BOB), 100);
+		});
+}
+
+#[test]
+fn fungibles_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::balance(NATIVE_CURRENCY_ID, &ALICE),
+				100
+			);
+			assert_eq!(<Currencies as fungibles::Inspect<_>>::balance(X_TOKEN_ID, &ALICE), 100);
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::minimum_balance(NATIVE_CURRENCY_ID),
+				2
+			);
+
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::mint_into(
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				10
+			));
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::mint_into(X_TOKEN_ID, &ALICE, 10));
+			assert_eq!(PalletBalances::free_balance(&ALICE), 110);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &ALICE), 110);
+
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::transfer(
+				NATIVE_CURRENCY_ID,
+				&ALICE,
+				&BOB,
+				10,
+				Preservation::Expendable
+			));
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::burn_from(
+				X_TOKEN_ID,
+				&ALICE,
+				10,
+				Precision::Exact,
+				Fortitude::Polite
+			));
+			assert_eq!(PalletBalances::free_balance(&ALICE), 100);
+			assert_eq!(PalletBalances::free_balance(&BOB), 110);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &ALICE), 100);
+			assert_eq!(<Currencies as fungibles::Inspect<_>>::total_issuance(X_TOKEN_ID), 200);
+
+			assert_ok!(<Currencies as fungibles::MutateHold<_>>::hold(
+				NATIVE_CURRENCY_ID,
+				&(),
+				&ALICE,
+				20
+			));
+			assert_ok!(<Currencies as fungibles::MutateHold<_>>::hold(
+				X_TOKEN_ID,
+				&(),
+				&ALICE,
+				20
+			));
+			assert_eq!(PalletBalances::reserved_balance(&ALICE), 20);
+			assert_eq!(Tokens::reserved_balance(X_TOKEN_ID, &ALICE), 20);
+			assert_eq!(
+				<Currencies as fungibles::InspectHold<_>>::balance_on_hold(NATIVE_CURRENCY_ID, &(), &ALICE),
+				20
+			);
+
+			assert_eq!(
+				<Currencies as fungibles::MutateHold<_>>::transfer_on_hold(
+					NATIVE_CURRENCY_ID,
+					&(),
+					&ALICE,
+					&BOB,
+					5,
+					Precision::Exact,
+					Restriction::Free,
+					Fortitude::Polite
+				),
+				Ok(5)
+			);
+			assert_eq!(
+				<Currencies as fungibles::MutateHold<_>>::release(X_TOKEN_ID, &(), &ALICE, 20, Precision::Exact),
+				Ok(20)
+			);
+			assert_eq!(PalletBalances::free_balance(&BOB), 115);
+			assert_eq!(PalletBalances::reserved_balance(&ALICE), 15);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &ALICE), 100);
+
+			// currencies held by the external backend
+			assert!(<Currencies as fungibles::Inspect<_>>::asset_exists(CONTRACT_TOKEN_ID));
+			assert_ok!(<Currencies as fungibles::Mutate<_>>::mint_into(
+				CONTRACT_TOKEN_ID,
+				&ALICE,
+				10
+			));
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::total_issuance(CONTRACT_TOKEN_ID),
+				Currencies::total_issuance(CONTRACT_TOKEN_ID)
+			);
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::balance(CONTRACT_TOKEN_ID, &ALICE),
+				10
+			);
+			assert_ok!(<Currencies as fungibles::MutateHold<_>>::hold(
+				CONTRACT_TOKEN_ID,
+				&(),
+				&ALICE,
+				4
+			));
+			assert_eq!(Currencies::reserved_balance(CONTRACT_TOKEN_ID, &ALICE), 4);
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::reducible_balance(
+					CONTRACT_TOKEN_ID,
+					&ALICE,
+					Preservation::Expendable,
+					Fortitude::Polite
+				),
+				6
+			);
+
+			assert_ok!(Currencies::pause_currency(RuntimeOrigin::root(), X_TOKEN_ID));
+			assert_noop!(
+				<Currencies as fungibles::Mutate<_>>::mint_into(X_TOKEN_ID, &ALICE, 10),
+				Error::<Runtime>::CurrencyPaused
+			);
+		});
+}
+
+#[test]
+fn fungibles_should_fail_if_paused() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Currencies as fungibles::MutateHold<_>>::hold(
+				X_TOKEN_ID,
+				&(),
+				&ALICE,
+				20
+			));
+			assert_ok!(Currencies::pause_currency(RuntimeOrigin::root(), X_TOKEN_ID));
+
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::reducible_balance(
+					X_TOKEN_ID,
+					&ALICE,
+					Preservation::Expendable,
+					Fortitude::Polite
+				),
+				0
+			);
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::can_deposit(X_TOKEN_ID, &ALICE, 10, Provenance::Extant),
+				DepositConsequence::Blocked
+			);
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::can_withdraw(X_TOKEN_ID, &ALICE, 10),
+				WithdrawConsequence::Frozen
+			);
+
+			assert_noop!(
+				<Currencies as fungibles::Unbalanced<_>>::write_balance(X_TOKEN_ID, &ALICE, 10),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_noop!(
+				<Currencies as fungibles::Unbalanced<_>>::increase_balance(X_TOKEN_ID, &ALICE, 10, Precision::Exact),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_noop!(
+				<Currencies as fungibles::Unbalanced<_>>::decrease_balance(
+					X_TOKEN_ID,
+					&ALICE,
+					10,
+					Precision::Exact,
+					Preservation::Expendable,
+					Fortitude::Polite
+				),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert!(<Currencies as fungibles::Balanced<_>>::deposit(X_TOKEN_ID, &ALICE, 10, Precision::Exact).is_err());
+			assert!(<Currencies as fungibles::Balanced<_>>::withdraw(
+				X_TOKEN_ID,
+				&ALICE,
+				10,
+				Precision::Exact,
+				Preservation::Expendable,
+				Fortitude::Polite
+			)
+			.is_err());
+			assert_noop!(
+				<Currencies as fungibles::UnbalancedHold<_>>::set_balance_on_hold(X_TOKEN_ID, &(), &ALICE, 10),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_noop!(
+				<Currencies as fungibles::MutateHold<_>>::burn_held(
+					X_TOKEN_ID,
+					&(),
+					&ALICE,
+					10,
+					Precision::Exact,
+					Fortitude::Polite
+				),
+				Error::<Runtime>::CurrencyPaused
+			);
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &ALICE), 80);
+			assert_eq!(Tokens::reserved_balance(X_TOKEN_ID, &ALICE), 20);
+
+			// the whitelisted treasury can still change its balance
+			assert!(<Currencies as fungibles::Balanced<_>>::deposit(
+				X_TOKEN_ID,
+				&TREASURY_ACCOUNT,
+				10,
+				Precision::Exact
+			)
+			.is_ok());
+			assert_eq!(Tokens::free_balance(X_TOKEN_ID, &TREASURY_ACCOUNT), 10);
+			assert_eq!(
+				<Currencies as fungibles::Inspect<_>>::can_withdraw(X_TOKEN_ID, &TREASURY_ACCOUNT, 5),
+				WithdrawConsequence::Success
+			);
+		});
+}
+
+#[test]
+fn swap_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Currencies::create_swap(Some(ALICE).into(), BOB, NATIVE_CURRENCY_ID, 50, X_TOKEN_ID, 20, 1),
+				Error::<Runtime>::SwapExpired
+			);
+			assert_ok!(Currencies::create_swap(
+				Some(ALICE).into(),
+				BOB,
+				NATIVE_CURRENCY_ID,
+				50,
+				X_TOKEN_ID,
+				20,
+				10
+			));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::SwapCreated {
+				swap_id: 0,
+				maker: ALICE,
+				taker: BOB,
+				give_currency_id: NATIVE_CURRENCY_ID,
+				give_amount: 50,
+				take_currency_id: X_TOKEN_ID,
+				take_amount: 20,
+				expires_at: 10,
+			}));
+			assert_eq!(
+				Swaps::<Runtime>::get(0).map(|swap| swap.deposit),
+				Some(SwapDeposit::get())
+			);
+			// the offered balance and the deposit
+			assert_eq!(
+				Currencies::reserved_balance_named(&SwapReserveId::get(), NATIVE_CURRENCY_ID, &ALICE),
+				55
+			);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &ALICE), 45);
+
+			assert_noop!(
+				Currencies::accept_swap(Some(EVA).into(), 0),
+				Error::<Runtime>::NotSwapParty
+			);
+			assert_noop!(
+				Currencies::accept_swap(Some(BOB).into(), 1),
+				Error::<Runtime>::SwapNotFound
+			);
+			assert_ok!(Currencies::accept_swap(Some(BOB).into(), 0));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::SwapAccepted { swap_id: 0 }));
+			assert_eq!(Swaps::<Runtime>::get(0), None);
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &ALICE), 50);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &BOB), 150);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 120);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 80);
+		});
+}
+
+#[test]
+fn create_swap_should_fail_if_invalid() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Currencies::create_swap(Some(ALICE).into(), ALICE, NATIVE_CURRENCY_ID, 50, X_TOKEN_ID, 20, 10),
+				Error::<Runtime>::SwapWithSelf
+			);
+			assert_noop!(
+				Currencies::create_swap(Some(ALICE).into(), BOB, NATIVE_CURRENCY_ID, 0, X_TOKEN_ID, 20, 10),
+				Error::<Runtime>::ZeroSwapAmount
+			);
+			assert_noop!(
+				Currencies::create_swap(Some(ALICE).into(), BOB, NATIVE_CURRENCY_ID, 50, X_TOKEN_ID, 0, 10),
+				Error::<Runtime>::ZeroSwapAmount
+			);
+			assert_noop!(
+				Currencies::create_swap(Some(ALICE).into(), BOB, X_TOKEN_ID, 50, X_TOKEN_ID, 20, 10),
+				Error::<Runtime>::SameSwapCurrency
+			);
+			assert_eq!(NextSwapId::<Runtime>::get(), 0);
+		});
+}
+
+#[test]
+fn swap_should_settle_atomically() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Currencies::create_swap(
+				Some(ALICE).into(),
+				BOB,
+				X_TOKEN_ID,
+				10,
+				NATIVE_CURRENCY_ID,
+				1_000,
+				10
+			));
+			assert!(Currencies::accept_swap(Some(BOB).into(), 0).is_err());
+			assert!(Swaps::<Runtime>::get(0).is_some());
+			assert_eq!(
+				Currencies::reserved_balance_named(&SwapReserveId::get(), X_TOKEN_ID, &ALICE),
+				10
+			);
+			assert_eq!(
+				Currencies::reserved_balance_named(&SwapReserveId::get(), NATIVE_CURRENCY_ID, &ALICE),
+				5
+			);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 90);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &BOB), 100);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &ALICE), 95);
+		});
+}
+
+#[test]
+fn cancel_swap_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Currencies::create_swap(
+				Some(ALICE).into(),
+				BOB,
+				X_TOKEN_ID,
+				10,
+				NATIVE_CURRENCY_ID,
+				10,
+				5
+			));
+			assert_ok!(Currencies::create_swap(
+				Some(ALICE).into(),
+				BOB,
+				X_TOKEN_ID,
+				20,
+				NATIVE_CURRENCY_ID,
+				20,
+				5
+			));
+			assert_eq!(Currencies::reserved_balance(X_TOKEN_ID, &ALICE), 30);
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &ALICE), 10);
+
+			// only the maker can cancel before the expiry
+			assert_noop!(
+				Currencies::cancel_swap(Some(BOB).into(), 0),
+				Error::<Runtime>::NotSwapParty
+			);
+			assert_ok!(Currencies::cancel_swap(Some(ALICE).into(), 0));
+			System::assert_last_event(RuntimeEvent::Currencies(crate::Event::SwapCancelled { swap_id: 0 }));
+			assert_eq!(Currencies::reserved_balance(X_TOKEN_ID, &ALICE), 20);
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &ALICE), 5);
+
+			// expired swaps can't be accepted, and can be cleaned up by anyone,
+			// which returns the deposit to the maker
+			System::set_block_number(5);
+			assert_noop!(
+				Currencies::accept_swap(Some(BOB).into(), 1),
+				Error::<Runtime>::SwapExpired
+			);
+			assert_ok!(Currencies::cancel_swap(Some(EVA).into(), 1));
+			assert_eq!(Swaps::<Runtime>::get(1), None);
+			assert_eq!(Currencies::reserved_balance(X_TOKEN_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 100);
+			assert_eq!(Currencies::reserved_balance(NATIVE_CURRENCY_ID, &ALICE), 0);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &ALICE), 100);
+			assert_eq!(Currencies::free_balance(NATIVE_CURRENCY_ID, &EVA), 0);
+		});
+}
+
+#[test]
+fn create_swap_should_require_deposit() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, X_TOKEN_ID, 100), (ALICE, NATIVE_CURRENCY_ID, 4)])
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				Currencies::create_swap(Some(ALICE).into(), BOB, X_TOKEN_ID, 10, NATIVE_CURRENCY_ID, 10, 5),
+				pallet_balances::Error::<Runtime>::InsufficientBalance
+			);
+			assert_eq!(NextSwapId::<Runtime>::get(), 0);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 100);
+		});
+}
@@ -1,6 +1,16 @@
-//! Autogenerated weights for module_currencies
+//! Weights for module_currencies
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
+//! The weights of `transfer_non_native_currency`, `transfer_native_currency`,
+//! `update_balance_non_native_currency`,
+//! `update_balance_native_currency_creating` and
+//! `update_balance_native_currency_killing` were generated with the benchmark
+//! CLI below. The other weights are hand-estimated from their storage accesses
+//! and are not backed by benchmarks yet, runtimes should benchmark them before
+//! relying on them. `create_swap`, `accept_swap` and `cancel_swap` are
+//! benchmarked in `benchmarking.rs`, and their weights are to be regenerated
+//! with the benchmark CLI.
+//!
+//! SUBSTRATE BENCHMARK CLI VERSION 3.0.0
 //! DATE: 2021-05-04, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
 
@@ -35,6 +45,11 @@ pub trait WeightInfo {
 	fn update_balance_non_native_currency() -> Weight;
 	fn update_balance_native_currency_creating() -> Weight;
 	fn update_balance_native_currency_killing() -> Weight;
+	fn pause_currency() -> Weight;
+	fn unpause_currency() -> Weight;
+	fn create_swap() -> Weight;
+	fn accept_swap() -> Weight;
+	fn cancel_swap() -> Weight;
 }
 
 /// Default weights.
@@ -64,4 +79,28 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
 			.saturating_add(RocksDbWeight::get().writes(2 as u64))
 	}
+	// The weights below are hand-estimated, not benchmarked.
+	fn pause_currency() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn unpause_currency() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn create_swap() -> Weight {
+		Weight::from_parts(62_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(7 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	fn accept_swap() -> Weight {
+		Weight::from_parts(115_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(9 as u64))
+	}
+	fn cancel_swap() -> Weight {
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
 }
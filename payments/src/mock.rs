@@ -97,6 +97,19 @@ impl orml_tokens::Config for Test {
 	type DustRemovalWhitelist = MockDustRemovalWhitelist;
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = ReserveIdentifier;
+	type RuntimeHoldReason = ();
+	type MaxHolds = ();
+	type RuntimeFreezeReason = ();
+	type MaxFreezes = ();
+	type Sufficiency = orml_tokens::AllSufficient;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransfersPerBatch = ();
+	type MaxIssuance = orml_tokens::NoIssuanceCap;
+	type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxSnapshots = ConstU32<10>;
+	type ApprovalDeposit = orml_tokens::NoApprovalDeposit;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkCurrencyId = ConstU32<CURRENCY_ID>;
 }
 
 pub struct MockDisputeResolver;
@@ -6,13 +6,83 @@
 // The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
 #![allow(clippy::unnecessary_mut_passed)]
 
-use parity_scale_codec::Codec;
+use frame_support::traits::{LockIdentifier, WithdrawReasons};
+use parity_scale_codec::{Codec, Decode, Encode};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::Vec;
+
+/// The balances of an account in a single currency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CurrencyBalance<CurrencyId, Balance> {
+	pub currency_id: CurrencyId,
+	pub free: Balance,
+	pub reserved: Balance,
+	pub frozen: Balance,
+}
+
+/// A lock on the balance of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LockInfo<Balance> {
+	pub id: LockIdentifier,
+	pub amount: Balance,
+	pub reasons: WithdrawReasons,
+}
+
+/// A named reserve on the balance of an account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReserveInfo<ReserveIdentifier, Balance> {
+	pub id: ReserveIdentifier,
+	pub amount: Balance,
+}
+
+/// The locks and named reserves of an account in a single currency.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct LocksAndReserves<ReserveIdentifier, Balance> {
+	pub locks: Vec<LockInfo<Balance>>,
+	pub reserves: Vec<ReserveInfo<ReserveIdentifier, Balance>>,
+}
 
 sp_api::decl_runtime_apis! {
-	pub trait TokensApi<CurrencyId, Balance> where
+	/// Version 2 added the portfolio queries, and the `AccountId` and
+	/// `ReserveIdentifier` parameters.
+	#[api_version(2)]
+	pub trait TokensApi<CurrencyId, Balance, AccountId, ReserveIdentifier> where
 		Balance: Codec,
-		CurrencyId: Codec
+		CurrencyId: Codec,
+		AccountId: Codec,
+		ReserveIdentifier: Codec,
 	{
 		fn query_existential_deposit(currency_id: CurrencyId) -> Balance;
+
+		/// Every currency in which `who` has a non-zero balance.
+		#[api_version(2)]
+		fn query_account_balances(who: AccountId) -> Vec<CurrencyBalance<CurrencyId, Balance>>;
+
+		/// The locks and named reserves of `who` in `currency_id`.
+		#[api_version(2)]
+		fn query_locks_and_reserves(
+			who: AccountId,
+			currency_id: CurrencyId,
+		) -> LocksAndReserves<ReserveIdentifier, Balance>;
+
+		/// The total issuance of each of `currency_ids`, in the same order.
+		#[api_version(2)]
+		fn query_total_issuance(currency_ids: Vec<CurrencyId>) -> Vec<(CurrencyId, Balance)>;
+
+		/// The balance of `who` in `currency_id` that can be withdrawn or
+		/// transferred, i.e. `fungibles::Inspect::reducible_balance` with
+		/// `Preservation::Preserve` if `keep_alive` else
+		/// `Preservation::Expendable`, and `Fortitude::Force` if `force` else
+		/// `Fortitude::Polite`.
+		///
+		/// NOTE: `Preservation` and `Fortitude` don't implement `Encode` and
+		/// `Decode`, so they can't be passed to a runtime API.
+		#[api_version(2)]
+		fn query_reducible_balance(
+			who: AccountId,
+			currency_id: CurrencyId,
+			keep_alive: bool,
+			force: bool,
+		) -> Balance;
 	}
 }
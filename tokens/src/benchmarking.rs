@@ -0,0 +1,152 @@
+//! Benchmarks for the tokens module.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use sp_runtime::traits::One;
+
+const SEED: u32 = 0;
+
+/// Returns an amount well above the existential deposit of `currency_id`.
+fn amount<T: Config>(currency_id: T::CurrencyId) -> T::Balance {
+	T::ExistentialDeposits::get(&currency_id)
+		.saturating_add(One::one())
+		.saturating_mul(100u32.into())
+}
+
+/// Endows `who` with `amount` of `currency_id`.
+fn endow<T: Config>(currency_id: T::CurrencyId, who: &T::AccountId, amount: T::Balance) {
+	assert_ok!(<Pallet<T> as MultiCurrency<T::AccountId>>::deposit(
+		currency_id,
+		who,
+		amount
+	));
+}
+
+fn assert_last_event<T: Config>(event: Event<T>) {
+	frame_system::Pallet::<T>::assert_last_event(<T as Config>::RuntimeEvent::from(event).into());
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn transfer_batch(n: Linear<1, { T::MaxTransfersPerBatch::get() }>) {
+		let currency_id = T::BenchmarkCurrencyId::get();
+		let amount = amount::<T>(currency_id);
+		let caller: T::AccountId = whitelisted_caller();
+		endow::<T>(currency_id, &caller, amount.saturating_mul((n + 1).into()));
+
+		// every transfer creates a new account, the worst case
+		let transfers: Vec<_> = (0..n)
+			.map(|i| {
+				let to: T::AccountId = account("recipient", i, SEED);
+				(T::Lookup::unlookup(to), currency_id, amount)
+			})
+			.collect();
+		let transfers: BoundedVec<_, T::MaxTransfersPerBatch> = transfers.try_into().expect("n is bounded; qed");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), transfers);
+
+		assert_last_event::<T>(Event::BatchTransferred { from: caller, count: n });
+	}
+
+	#[benchmark]
+	fn approve_transfer() {
+		let currency_id = T::BenchmarkCurrencyId::get();
+		let amount = amount::<T>(currency_id);
+		let owner: T::AccountId = whitelisted_caller();
+		endow::<T>(
+			currency_id,
+			&owner,
+			amount.saturating_add(T::ApprovalDeposit::get(&currency_id)),
+		);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+
+		// a new approval takes a deposit, the worst case
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(owner.clone()),
+			currency_id,
+			T::Lookup::unlookup(delegate.clone()),
+			amount,
+		);
+
+		assert_last_event::<T>(Event::TransferApproved {
+			currency_id,
+			owner,
+			delegate,
+			amount,
+		});
+	}
+
+	#[benchmark]
+	fn cancel_approval() {
+		let currency_id = T::BenchmarkCurrencyId::get();
+		let amount = amount::<T>(currency_id);
+		let owner: T::AccountId = whitelisted_caller();
+		endow::<T>(
+			currency_id,
+			&owner,
+			amount.saturating_add(T::ApprovalDeposit::get(&currency_id)),
+		);
+		let delegate: T::AccountId = account("delegate", 0, SEED);
+		assert_ok!(Pallet::<T>::do_approve_transfer(currency_id, &owner, &delegate, amount));
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(owner.clone()),
+			currency_id,
+			T::Lookup::unlookup(delegate.clone()),
+		);
+
+		assert_last_event::<T>(Event::ApprovalCancelled {
+			currency_id,
+			owner,
+			delegate,
+		});
+	}
+
+	#[benchmark]
+	fn transfer_approved() {
+		let currency_id = T::BenchmarkCurrencyId::get();
+		let amount = amount::<T>(currency_id);
+		let owner: T::AccountId = account("owner", 0, SEED);
+		endow::<T>(
+			currency_id,
+			&owner,
+			amount
+				.saturating_mul(2u32.into())
+				.saturating_add(T::ApprovalDeposit::get(&currency_id)),
+		);
+		let delegate: T::AccountId = whitelisted_caller();
+		assert_ok!(Pallet::<T>::do_approve_transfer(currency_id, &owner, &delegate, amount));
+		let destination: T::AccountId = account("destination", 0, SEED);
+
+		// the approval is used up and the destination account is created, the
+		// worst case
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(delegate.clone()),
+			currency_id,
+			T::Lookup::unlookup(owner.clone()),
+			T::Lookup::unlookup(destination.clone()),
+			amount,
+		);
+
+		assert_last_event::<T>(Event::TransferredApproved {
+			currency_id,
+			owner,
+			delegate,
+			destination,
+			amount,
+		});
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::ExtBuilder::default().build(), crate::mock::Runtime);
+}
@@ -41,7 +41,7 @@
 pub use crate::imbalances::{NegativeImbalance, PositiveImbalance};
 
 use frame_support::{
-	ensure,
+	defensive, ensure,
 	pallet_prelude::*,
 	traits::{
 		tokens::{
@@ -65,17 +65,22 @@ use sp_runtime::{
 	},
 	ArithmeticError, DispatchError, DispatchResult, FixedPointOperand, RuntimeDebug, TokenError,
 };
-use sp_std::{cmp, convert::Infallible, marker, prelude::*, vec::Vec};
+use sp_std::{cmp, marker, prelude::*, vec::Vec};
 
 use orml_traits::{
-	arithmetic::{self, Signed},
-	currency::{MutationHooks, OnDeposit, OnDust, OnSlash, OnTransfer, TransferAll},
+	arithmetic::{self, Signed, SignedBalance},
+	currency::{
+		MutationHooks, OnDeposit, OnDust, OnReserve, OnSlash, OnTransfer, OnUnreserve, TransferAll, TransferFee,
+	},
 	BalanceStatus, GetByKey, Happened, LockIdentifier, MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency,
 	MultiReservableCurrency, NamedMultiReservableCurrency,
 };
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 mod imbalances;
 mod impls;
+pub mod migrations;
 mod mock;
 mod tests;
 mod tests_currency_adapter;
@@ -112,7 +117,44 @@ impl<T: Config> OnDust<T::AccountId, T::CurrencyId, T::Balance> for BurnDust<T>
 	fn on_dust(who: &T::AccountId, currency_id: T::CurrencyId, amount: T::Balance) {
 		// burn the dust, ignore the result,
 		// if failed will leave some dust which still could be recycled.
-		let _ = Pallet::<T>::do_withdraw(currency_id, who, amount, ExistenceRequirement::AllowDeath, true);
+		let _ = Pallet::<T>::do_withdraw(
+			currency_id,
+			who,
+			amount,
+			WithdrawReasons::all(),
+			ExistenceRequirement::AllowDeath,
+			true,
+		);
+	}
+}
+
+/// The withdraw reasons a lock is in effect for, stored as the bits of
+/// `WithdrawReasons`.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct LockReasons(u8);
+
+impl LockReasons {
+	/// Whether the lock applies to a withdrawal for any of the `reasons`.
+	pub fn intersects(&self, reasons: WithdrawReasons) -> bool {
+		WithdrawReasons::from(*self).intersects(reasons)
+	}
+}
+
+impl Default for LockReasons {
+	fn default() -> Self {
+		WithdrawReasons::all().into()
+	}
+}
+
+impl From<WithdrawReasons> for LockReasons {
+	fn from(reasons: WithdrawReasons) -> Self {
+		LockReasons(reasons.bits())
+	}
+}
+
+impl From<LockReasons> for WithdrawReasons {
+	fn from(reasons: LockReasons) -> Self {
+		WithdrawReasons::from_bits_truncate(reasons.0)
 	}
 }
 
@@ -126,6 +168,8 @@ pub struct BalanceLock<Balance> {
 	/// The amount which the free balance may not drop below when this lock
 	/// is in effect.
 	pub amount: Balance,
+	/// The withdraw reasons for which this lock is in effect.
+	pub reasons: LockReasons,
 }
 
 /// Store named reserved balance.
@@ -137,6 +181,80 @@ pub struct ReserveData<ReserveIdentifier, Balance> {
 	pub amount: Balance,
 }
 
+/// A single hold on a balance under a given reason. The held amount is
+/// accounted for in the `reserved` balance of the account, but can't be
+/// unreserved, slashed or repatriated through `MultiReservableCurrency`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct HoldData<HoldReason, Balance> {
+	/// The reason for the hold.
+	pub id: HoldReason,
+	/// The amount on hold.
+	pub amount: Balance,
+}
+
+/// A single freeze on a balance under a given reason. Like locks, freezes
+/// "overlap", so the same balance is frozen by multiple freezes.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct FreezeData<FreezeReason, Balance> {
+	/// The reason for the freeze.
+	pub id: FreezeReason,
+	/// The amount which the free balance may not drop below when this
+	/// freeze is in effect.
+	pub amount: Balance,
+}
+
+/// How holding a currency affects the references of an account.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum SufficiencyKind {
+	/// A balance of the currency is enough to keep the account alive, a
+	/// provider reference is added for each new token account.
+	#[default]
+	Sufficient,
+	/// A balance of the currency can only be held by an account which already
+	/// exists, a consumer reference is added for each new token account.
+	Insufficient,
+	/// No new token accounts can be created for the currency.
+	Blocked,
+}
+
+/// Treats every currency as `SufficiencyKind::Sufficient`, which was the
+/// behaviour before the sufficiency of a currency became configurable.
+pub struct AllSufficient;
+impl<CurrencyId> GetByKey<CurrencyId, SufficiencyKind> for AllSufficient {
+	fn get(_: &CurrencyId) -> SufficiencyKind {
+		SufficiencyKind::Sufficient
+	}
+}
+
+/// The id of a balance snapshot of a currency.
+pub type SnapshotId = u32;
+
+/// No cap on the total issuance of any currency.
+pub struct NoIssuanceCap;
+impl<CurrencyId, Balance> GetByKey<CurrencyId, Option<Balance>> for NoIssuanceCap {
+	fn get(_: &CurrencyId) -> Option<Balance> {
+		None
+	}
+}
+
+/// Charge no deposit for approvals.
+pub struct NoApprovalDeposit;
+impl<CurrencyId, Balance: Zero> GetByKey<CurrencyId, Balance> for NoApprovalDeposit {
+	fn get(_: &CurrencyId) -> Balance {
+		Zero::zero()
+	}
+}
+
+/// An approval of a delegate to transfer some balance of an owner.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ApprovalData<Balance> {
+	/// The amount the delegate may still transfer.
+	pub amount: Balance,
+	/// The deposit reserved from the owner, released when the approval is
+	/// cancelled or used up.
+	pub deposit: Balance,
+}
+
 /// balance information for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, MaxEncodedLen, RuntimeDebug, TypeInfo)]
 pub struct AccountData<Balance> {
@@ -219,6 +337,13 @@ pub mod module {
 		/// System::AccountInfo, zero ED may cause some problems.
 		type ExistentialDeposits: GetByKey<Self::CurrencyId, Self::Balance>;
 
+		/// Whether a balance of a currency is sufficient to keep an account
+		/// alive, or if it requires the account to exist already.
+		type Sufficiency: GetByKey<Self::CurrencyId, SufficiencyKind>;
+
+		/// The maximum total issuance of a currency, `None` means no cap.
+		type MaxIssuance: GetByKey<Self::CurrencyId, Option<Self::Balance>>;
+
 		/// Hooks are actions that are executed on certain events.
 		/// For example: OnDust, OnNewTokenAccount
 		type CurrencyHooks: MutationHooks<Self::AccountId, Self::CurrencyId, Self::Balance>;
@@ -233,9 +358,48 @@ pub mod module {
 		/// The id type for named reserves.
 		type ReserveIdentifier: Parameter + Member + MaxEncodedLen + Ord + Copy;
 
+		/// The overarching hold reason.
+		type RuntimeHoldReason: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// The maximum number of holds that can exist on an account.
+		#[pallet::constant]
+		type MaxHolds: Get<u32>;
+
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: Parameter + Member + MaxEncodedLen + Ord + Copy;
+
+		/// The maximum number of freezes that can exist on an account.
+		#[pallet::constant]
+		type MaxFreezes: Get<u32>;
+
 		// The whitelist of accounts that will not be reaped even if its total
 		// is zero or below ED.
 		type DustRemovalWhitelist: Contains<Self::AccountId>;
+
+		/// The origin which may freeze and thaw currencies and token accounts.
+		type FreezeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of transfers in a `transfer_batch` call.
+		#[pallet::constant]
+		type MaxTransfersPerBatch: Get<u32>;
+
+		/// The origin which may take and prune balance snapshots of a
+		/// currency.
+		type SnapshotOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of balance snapshots of a currency kept at
+		/// once, older ones are deleted with `prune_snapshot`.
+		#[pallet::constant]
+		type MaxSnapshots: Get<SnapshotId>;
+
+		/// The deposit reserved from the owner, in the approved currency, when
+		/// a new approval is created. Use `NoApprovalDeposit` to charge none.
+		type ApprovalDeposit: GetByKey<Self::CurrencyId, Self::Balance>;
+
+		/// The currency the benchmarks transfer. Accounts must be able to hold
+		/// it without restriction.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkCurrencyId: Get<Self::CurrencyId>;
 	}
 
 	#[pallet::error]
@@ -256,6 +420,24 @@ pub mod module {
 		DeadAccount,
 		// Number of named reserves exceed `T::MaxReserves`
 		TooManyReserves,
+		/// Number of holds exceed `T::MaxHolds`
+		TooManyHolds,
+		/// Number of freezes exceed `T::MaxFreezes`
+		TooManyFreezes,
+		/// The currency is frozen
+		CurrencyFrozen,
+		/// The token account is frozen
+		AccountFrozen,
+		/// The total issuance would exceed `T::MaxIssuance`
+		IssuanceCapExceeded,
+		/// No approval exists that would allow the transfer
+		Unapproved,
+		/// The account holds more currencies than declared
+		TooManyCurrencies,
+		/// Number of snapshots of the currency exceed `T::MaxSnapshots`
+		TooManySnapshots,
+		/// The currency has no snapshot to prune besides the latest one
+		NoSnapshotToPrune,
 	}
 
 	#[pallet::event]
@@ -274,12 +456,14 @@ pub mod module {
 			who: T::AccountId,
 			amount: T::Balance,
 		},
-		/// Transfer succeeded.
+		/// Transfer succeeded. `fee` was deducted from `amount` and routed to
+		/// the fee receiver.
 		Transfer {
 			currency_id: T::CurrencyId,
 			from: T::AccountId,
 			to: T::AccountId,
 			amount: T::Balance,
+			fee: T::Balance,
 		},
 		/// Some balance was reserved (moved from free to reserved).
 		Reserved {
@@ -366,13 +550,134 @@ pub mod module {
 			currency_id: T::CurrencyId,
 			amount: T::Balance,
 		},
+		/// Some funds are frozen
+		FreezeSet {
+			reason: T::RuntimeFreezeReason,
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+			amount: T::Balance,
+		},
+		/// Some frozen funds were thawed
+		FreezeRemoved {
+			reason: T::RuntimeFreezeReason,
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// A currency was frozen.
+		CurrencyFrozen { currency_id: T::CurrencyId },
+		/// A currency was thawed.
+		CurrencyThawed { currency_id: T::CurrencyId },
+		/// A token account was frozen.
+		AccountFrozen {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// A token account was thawed.
+		AccountThawed {
+			currency_id: T::CurrencyId,
+			who: T::AccountId,
+		},
+		/// A batch of transfers was executed.
+		BatchTransferred { from: T::AccountId, count: u32 },
+		/// A snapshot of the balances of a currency was taken.
+		SnapshotTaken {
+			currency_id: T::CurrencyId,
+			snapshot_id: SnapshotId,
+		},
+		/// An owner approved a delegate to transfer some of its balance.
+		TransferApproved {
+			currency_id: T::CurrencyId,
+			owner: T::AccountId,
+			delegate: T::AccountId,
+			amount: T::Balance,
+		},
+		/// An approval was cancelled.
+		ApprovalCancelled {
+			currency_id: T::CurrencyId,
+			owner: T::AccountId,
+			delegate: T::AccountId,
+		},
+		/// A delegate transferred some approved balance of an owner.
+		TransferredApproved {
+			currency_id: T::CurrencyId,
+			owner: T::AccountId,
+			delegate: T::AccountId,
+			destination: T::AccountId,
+			amount: T::Balance,
+		},
+		/// All the balances of an account in a currency, including reserves,
+		/// holds, locks and freezes, were moved to another account.
+		AccountMigrated {
+			currency_id: T::CurrencyId,
+			from: T::AccountId,
+			to: T::AccountId,
+			free: T::Balance,
+			reserved: T::Balance,
+		},
+		/// The oldest snapshot of a currency and its balances were deleted.
+		SnapshotPruned {
+			currency_id: T::CurrencyId,
+			snapshot_id: SnapshotId,
+		},
 	}
 
+	/// The latest snapshot of a token type, zero if no snapshot was taken.
+	#[pallet::storage]
+	pub type CurrentSnapshotId<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, SnapshotId, ValueQuery>;
+
+	/// The latest deleted snapshot of a token type, the snapshots after it
+	/// until `CurrentSnapshotId` are kept.
+	#[pallet::storage]
+	pub type PrunedSnapshotId<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, SnapshotId, ValueQuery>;
+
+	/// The block number at which a snapshot of a token type was taken.
+	#[pallet::storage]
+	pub type Snapshots<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::CurrencyId, Twox64Concat, SnapshotId, BlockNumberFor<T>, OptionQuery>;
+
+	/// The total balance of an account at a snapshot. It is recorded lazily,
+	/// before the first change of the account after the snapshot.
+	#[pallet::storage]
+	pub type SnapshotBalances<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, T::CurrencyId>,
+			NMapKey<Twox64Concat, SnapshotId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		T::Balance,
+		OptionQuery,
+	>;
+
+	/// The amount of the balance of an owner that a delegate is approved to
+	/// transfer, and the deposit taken for the approval.
+	#[pallet::storage]
+	pub type Approvals<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, T::CurrencyId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		ApprovalData<T::Balance>,
+		OptionQuery,
+	>;
+
 	/// The total issuance of a token type.
 	#[pallet::storage]
 	#[pallet::getter(fn total_issuance)]
 	pub type TotalIssuance<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, T::Balance, ValueQuery>;
 
+	/// The total dust removed from the token accounts of a token type.
+	#[pallet::storage]
+	#[pallet::getter(fn total_dust)]
+	pub type TotalDust<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, T::Balance, ValueQuery>;
+
+	/// The last token account visited by the dust sweeper, it resumes from
+	/// the next token account in `on_idle`.
+	#[pallet::storage]
+	pub type DustSweepCursor<T: Config> = StorageValue<_, (T::AccountId, T::CurrencyId), OptionQuery>;
+
 	/// Any liquidity locks of a token type under an account.
 	/// NOTE: Should only be accessed when setting, changing and freeing a lock.
 	#[pallet::storage]
@@ -418,6 +723,49 @@ pub mod module {
 		ValueQuery,
 	>;
 
+	/// Holds on account balances, keyed by hold reason.
+	#[pallet::storage]
+	#[pallet::getter(fn holds)]
+	pub type Holds<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<HoldData<T::RuntimeHoldReason, T::Balance>, T::MaxHolds>,
+		ValueQuery,
+	>;
+
+	/// Freezes on account balances, keyed by freeze reason.
+	#[pallet::storage]
+	#[pallet::getter(fn freezes)]
+	pub type Freezes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::CurrencyId,
+		BoundedVec<FreezeData<T::RuntimeFreezeReason, T::Balance>, T::MaxFreezes>,
+		ValueQuery,
+	>;
+
+	/// The token accounts of insufficient currencies, which hold a consumer
+	/// reference instead of a provider reference on the account.
+	#[pallet::storage]
+	pub type InsufficientAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
+	/// The currencies frozen by `FreezeOrigin`, no balance of a frozen
+	/// currency can be moved.
+	#[pallet::storage]
+	pub type FrozenCurrencies<T: Config> = StorageMap<_, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
+	/// The token accounts frozen by `FreezeOrigin`, no balance can be moved
+	/// in or out of a frozen token account.
+	#[pallet::storage]
+	pub type FrozenAccounts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, T::CurrencyId, (), OptionQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub balances: Vec<(T::AccountId, T::CurrencyId, T::Balance)>,
@@ -450,9 +798,11 @@ pub mod module {
 						*initial_balance >= T::ExistentialDeposits::get(currency_id),
 						"the balance of any account should always be more than existential deposit.",
 					);
-					Pallet::<T>::mutate_account(account_id, *currency_id, |account_data, _| {
-						account_data.free = *initial_balance
-					});
+					Pallet::<T>::try_mutate_account(account_id, *currency_id, |account_data, _| -> DispatchResult {
+						account_data.free = *initial_balance;
+						Ok(())
+					})
+					.expect("the reference of any account should be added in genesis.");
 					TotalIssuance::<T>::mutate(*currency_id, |total_issuance| {
 						*total_issuance = total_issuance
 							.checked_add(initial_balance)
@@ -462,11 +812,23 @@ pub mod module {
 		}
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::sweep_dust(remaining_weight)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -484,7 +846,7 @@ pub mod module {
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::transfer())]
+		#[pallet::weight(T::WeightInfo::transfer().saturating_add(Pallet::<T>::snapshot_weight(3)))]
 		pub fn transfer(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -516,7 +878,7 @@ pub mod module {
 		///   except at least the existential deposit, which will guarantee to
 		///   keep the sender account alive (true).
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::transfer_all())]
+		#[pallet::weight(T::WeightInfo::transfer_all().saturating_add(Pallet::<T>::snapshot_weight(3)))]
 		pub fn transfer_all(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -552,7 +914,7 @@ pub mod module {
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::transfer_keep_alive())]
+		#[pallet::weight(T::WeightInfo::transfer_keep_alive().saturating_add(Pallet::<T>::snapshot_weight(3)))]
 		pub fn transfer_keep_alive(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
@@ -575,7 +937,7 @@ pub mod module {
 		/// - `currency_id`: currency type.
 		/// - `amount`: free balance amount to tranfer.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::force_transfer())]
+		#[pallet::weight(T::WeightInfo::force_transfer().saturating_add(Pallet::<T>::snapshot_weight(3)))]
 		pub fn force_transfer(
 			origin: OriginFor<T>,
 			source: <T::Lookup as StaticLookup>::Source,
@@ -598,7 +960,7 @@ pub mod module {
 		///
 		/// The dispatch origin for this call is `root`.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::set_balance())]
+		#[pallet::weight(T::WeightInfo::set_balance().saturating_add(Pallet::<T>::snapshot_weight(1)))]
 		pub fn set_balance(
 			origin: OriginFor<T>,
 			who: <T::Lookup as StaticLookup>::Source,
@@ -609,7 +971,7 @@ pub mod module {
 			ensure_root(origin)?;
 			let who = T::Lookup::lookup(who)?;
 
-			Self::try_mutate_account(&who, currency_id, |account, _| -> DispatchResult {
+			Self::try_mutate_account(&who, currency_id, |account, existed| -> DispatchResult {
 				let mut new_total = new_free.checked_add(&new_reserved).ok_or(ArithmeticError::Overflow)?;
 				let (new_free, new_reserved) = if new_total < T::ExistentialDeposits::get(&currency_id) {
 					new_total = Zero::zero();
@@ -617,12 +979,16 @@ pub mod module {
 				} else {
 					(new_free, new_reserved)
 				};
+				if !existed && !new_total.is_zero() {
+					Self::ensure_can_create_account(currency_id, &who)?;
+				}
 				let old_total = account.total();
 
 				account.free = new_free;
 				account.reserved = new_reserved;
 
 				if new_total > old_total {
+					Self::ensure_within_issuance_cap(currency_id, new_total.defensive_saturating_sub(old_total))?;
 					TotalIssuance::<T>::try_mutate(currency_id, |t| -> DispatchResult {
 						*t = t
 							.checked_add(&(new_total.defensive_saturating_sub(old_total)))
@@ -649,12 +1015,260 @@ pub mod module {
 
 			Ok(())
 		}
+
+		/// Freeze a currency, no balance of it can be transferred, deposited
+		/// or withdrawn until it is thawed.
+		///
+		/// The dispatch origin for this call must be `FreezeOrigin`.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::freeze_currency())]
+		pub fn freeze_currency(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+
+			FrozenCurrencies::<T>::insert(currency_id, ());
+			Self::deposit_event(Event::CurrencyFrozen { currency_id });
+			Ok(())
+		}
+
+		/// Thaw a frozen currency.
+		///
+		/// The dispatch origin for this call must be `FreezeOrigin`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::thaw_currency())]
+		pub fn thaw_currency(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+
+			FrozenCurrencies::<T>::remove(currency_id);
+			Self::deposit_event(Event::CurrencyThawed { currency_id });
+			Ok(())
+		}
+
+		/// Freeze the token account of `who`, no balance of `currency_id` can
+		/// be transferred, deposited or withdrawn by it until it is thawed.
+		///
+		/// The dispatch origin for this call must be `FreezeOrigin`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::freeze_account())]
+		pub fn freeze_account(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+		) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			FrozenAccounts::<T>::insert(&who, currency_id, ());
+			Self::deposit_event(Event::AccountFrozen { currency_id, who });
+			Ok(())
+		}
+
+		/// Thaw a frozen token account.
+		///
+		/// The dispatch origin for this call must be `FreezeOrigin`.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::thaw_account())]
+		pub fn thaw_account(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: T::CurrencyId,
+		) -> DispatchResult {
+			T::FreezeOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+
+			FrozenAccounts::<T>::remove(&who, currency_id);
+			Self::deposit_event(Event::AccountThawed { currency_id, who });
+			Ok(())
+		}
+
+		/// Transfer some liquid free balance of several currencies to several
+		/// accounts.
+		///
+		/// The transfers are atomic, if any of them fails none of them is
+		/// applied.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `transfers`: The recipient, currency type and amount of each
+		///   transfer.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::transfer_batch(transfers.len() as u32)
+			.saturating_add(Pallet::<T>::snapshot_weight((transfers.len() as u32).saturating_mul(3))))]
+		pub fn transfer_batch(
+			origin: OriginFor<T>,
+			transfers: BoundedVec<
+				(<T::Lookup as StaticLookup>::Source, T::CurrencyId, T::Balance),
+				T::MaxTransfersPerBatch,
+			>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let count = transfers.len() as u32;
+			for (dest, currency_id, amount) in transfers {
+				let to = T::Lookup::lookup(dest)?;
+				Self::do_transfer(currency_id, &from, &to, amount, ExistenceRequirement::AllowDeath)?;
+			}
+
+			Self::deposit_event(Event::BatchTransferred { from, count });
+			Ok(())
+		}
+
+		/// Take a snapshot of the balances of a currency, the balance of an
+		/// account at the snapshot can be queried with `balance_at`.
+		///
+		/// The dispatch origin for this call must be `SnapshotOrigin`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::take_snapshot())]
+		pub fn take_snapshot(origin: OriginFor<T>, currency_id: T::CurrencyId) -> DispatchResult {
+			T::SnapshotOrigin::ensure_origin(origin)?;
+
+			let pruned_snapshot_id = PrunedSnapshotId::<T>::get(currency_id);
+			let snapshot_id =
+				CurrentSnapshotId::<T>::try_mutate(currency_id, |id| -> Result<SnapshotId, DispatchError> {
+					ensure!(
+						id.saturating_sub(pruned_snapshot_id) < T::MaxSnapshots::get(),
+						Error::<T>::TooManySnapshots
+					);
+					*id = id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+					Ok(*id)
+				})?;
+			Snapshots::<T>::insert(currency_id, snapshot_id, frame_system::Pallet::<T>::block_number());
+
+			Self::deposit_event(Event::SnapshotTaken {
+				currency_id,
+				snapshot_id,
+			});
+			Ok(())
+		}
+
+		/// Approve a delegate to transfer some of the free balance of the
+		/// origin. The amount is added to any existing approval, otherwise
+		/// `ApprovalDeposit` is reserved from the origin until the approval is
+		/// cancelled or used up.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		///
+		/// - `currency_id`: currency type.
+		/// - `delegate`: The account to approve.
+		/// - `amount`: The additional amount the delegate may transfer.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::approve_transfer())]
+		pub fn approve_transfer(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			Self::do_approve_transfer(currency_id, &owner, &delegate, amount)
+		}
+
+		/// Cancel the approval of a delegate, releasing its deposit.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		///
+		/// - `currency_id`: currency type.
+		/// - `delegate`: The account whose approval is cancelled.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::cancel_approval())]
+		pub fn cancel_approval(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			Self::do_cancel_approval(currency_id, &owner, &delegate)
+		}
+
+		/// Transfer some approved balance of an owner to a destination.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// approved delegate.
+		///
+		/// - `currency_id`: currency type.
+		/// - `owner`: The account which approved the transfer.
+		/// - `destination`: The recipient of the transfer.
+		/// - `amount`: free balance amount to transfer.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::transfer_approved().saturating_add(Pallet::<T>::snapshot_weight(3)))]
+		pub fn transfer_approved(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			owner: <T::Lookup as StaticLookup>::Source,
+			destination: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] amount: T::Balance,
+		) -> DispatchResult {
+			let delegate = ensure_signed(origin)?;
+			let owner = T::Lookup::lookup(owner)?;
+			let destination = T::Lookup::lookup(destination)?;
+			Self::do_transfer_approved(currency_id, &owner, &delegate, &destination, amount)
+		}
+
+		/// Move all the balances of the origin to `dest`, including the
+		/// reserved balance, named reserves, holds, locks and freezes.
+		///
+		/// The migration is atomic, if any currency can't be migrated none of
+		/// them is.
+		///
+		/// The dispatch origin for this call must be `Signed` by the
+		/// transactor.
+		///
+		/// - `dest`: The recipient of the balances.
+		/// - `max_currencies`: An upper bound on the number of currencies held
+		///   by the origin, used to weigh the call.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::migrate_account(*max_currencies)
+			.saturating_add(Pallet::<T>::snapshot_weight(max_currencies.saturating_mul(2))))]
+		pub fn migrate_account(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] max_currencies: u32,
+		) -> DispatchResultWithPostInfo {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let migrated = Self::do_migrate_account(&from, &to, max_currencies)?;
+			Ok(Some(T::WeightInfo::migrate_account(migrated)).into())
+		}
+
+		/// Delete the oldest snapshot of a currency and up to `max_accounts`
+		/// of its balances. It is no longer queryable after the first call,
+		/// and is pruned once all its balances are deleted, which may take
+		/// several calls. The latest snapshot is never pruned.
+		///
+		/// The dispatch origin for this call must be `SnapshotOrigin`.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::prune_snapshot(*max_accounts))]
+		pub fn prune_snapshot(
+			origin: OriginFor<T>,
+			currency_id: T::CurrencyId,
+			#[pallet::compact] max_accounts: u32,
+		) -> DispatchResultWithPostInfo {
+			T::SnapshotOrigin::ensure_origin(origin)?;
+
+			let snapshot_id = PrunedSnapshotId::<T>::get(currency_id).saturating_add(1);
+			ensure!(
+				snapshot_id < CurrentSnapshotId::<T>::get(currency_id),
+				Error::<T>::NoSnapshotToPrune
+			);
+
+			Snapshots::<T>::remove(currency_id, snapshot_id);
+			let result = SnapshotBalances::<T>::clear_prefix((currency_id, snapshot_id), max_accounts, None);
+			if result.maybe_cursor.is_none() {
+				PrunedSnapshotId::<T>::insert(currency_id, snapshot_id);
+				Self::deposit_event(Event::SnapshotPruned {
+					currency_id,
+					snapshot_id,
+				});
+			}
+			Ok(Some(T::WeightInfo::prune_snapshot(result.unique)).into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
 	pub(crate) fn deposit_consequence(
-		_who: &T::AccountId,
+		who: &T::AccountId,
 		currency_id: T::CurrencyId,
 		amount: T::Balance,
 		account: &AccountData<T::Balance>,
@@ -663,10 +1277,18 @@ impl<T: Config> Pallet<T> {
 			return DepositConsequence::Success;
 		}
 
+		if Self::ensure_not_frozen(currency_id, who).is_err() {
+			return DepositConsequence::Blocked;
+		}
+
 		if TotalIssuance::<T>::get(currency_id).checked_add(&amount).is_none() {
 			return DepositConsequence::Overflow;
 		}
 
+		if Self::ensure_within_issuance_cap(currency_id, amount).is_err() {
+			return DepositConsequence::Overflow;
+		}
+
 		let new_total_balance = match account.total().checked_add(&amount) {
 			Some(x) => x,
 			None => return DepositConsequence::Overflow,
@@ -676,22 +1298,103 @@ impl<T: Config> Pallet<T> {
 			return DepositConsequence::BelowMinimum;
 		}
 
-		// NOTE: We assume that we are a provider, so don't need to do any checks in the
-		// case of account creation.
+		if !Accounts::<T>::contains_key(who, currency_id) {
+			return Self::creation_consequence(currency_id, who);
+		}
 
 		DepositConsequence::Success
 	}
 
-	pub(crate) fn withdraw_consequence(
-		who: &T::AccountId,
-		currency_id: T::CurrencyId,
-		amount: T::Balance,
-		account: &AccountData<T::Balance>,
-	) -> WithdrawConsequence<T::Balance> {
-		if amount.is_zero() {
+	/// Returns whether a new token account of `currency_id` can be created
+	/// for `who`, according to the sufficiency of the currency.
+	pub(crate) fn creation_consequence(currency_id: T::CurrencyId, who: &T::AccountId) -> DepositConsequence {
+		match T::Sufficiency::get(&currency_id) {
+			SufficiencyKind::Sufficient => DepositConsequence::Success,
+			SufficiencyKind::Insufficient => {
+				if frame_system::Pallet::<T>::can_inc_consumer(who) {
+					DepositConsequence::Success
+				} else {
+					DepositConsequence::CannotCreate
+				}
+			}
+			SufficiencyKind::Blocked => DepositConsequence::Blocked,
+		}
+	}
+
+	/// Ensure a new token account of `currency_id` can be created for `who`.
+	pub(crate) fn ensure_can_create_account(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		Self::creation_consequence(currency_id, who).into_result()
+	}
+
+	/// Ensure the total issuance of `currency_id` can grow by `amount` without
+	/// exceeding `MaxIssuance`.
+	pub(crate) fn ensure_within_issuance_cap(currency_id: T::CurrencyId, amount: T::Balance) -> DispatchResult {
+		if let Some(cap) = T::MaxIssuance::get(&currency_id) {
+			let new_total_issuance = Self::total_issuance(currency_id)
+				.checked_add(&amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(new_total_issuance <= cap, Error::<T>::IssuanceCapExceeded);
+		}
+		Ok(())
+	}
+
+	/// Ensure neither `currency_id` nor the token account of `who` is frozen
+	/// by `FreezeOrigin`.
+	pub(crate) fn ensure_not_frozen(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		ensure!(
+			!FrozenCurrencies::<T>::contains_key(currency_id),
+			Error::<T>::CurrencyFrozen
+		);
+		ensure!(
+			!FrozenAccounts::<T>::contains_key(who, currency_id),
+			Error::<T>::AccountFrozen
+		);
+		Ok(())
+	}
+
+	/// Returns whether `receiver` can be credited with a transfer `fee`: its
+	/// token account isn't frozen, can be created if it doesn't exist, and
+	/// ends up above the existential deposit.
+	pub(crate) fn can_receive_fee(currency_id: T::CurrencyId, receiver: &T::AccountId, fee: T::Balance) -> bool {
+		if Self::ensure_not_frozen(currency_id, receiver).is_err() {
+			return false;
+		}
+		if !Accounts::<T>::contains_key(receiver, currency_id)
+			&& Self::ensure_can_create_account(currency_id, receiver).is_err()
+		{
+			return false;
+		}
+		Self::accounts(receiver, currency_id)
+			.total()
+			.checked_add(&fee)
+			.map_or(false, |total| {
+				total >= T::ExistentialDeposits::get(&currency_id) || T::DustRemovalWhitelist::contains(receiver)
+			})
+	}
+
+	/// Returns whether the token account of `who` can be reaped without
+	/// breaking the references of the system account.
+	///
+	/// Token accounts of insufficient currencies only hold a consumer
+	/// reference, which can always be dropped.
+	pub(crate) fn can_dec_reference(currency_id: T::CurrencyId, who: &T::AccountId) -> bool {
+		InsufficientAccounts::<T>::contains_key(who, currency_id) || frame_system::Pallet::<T>::can_dec_provider(who)
+	}
+
+	pub(crate) fn withdraw_consequence(
+		who: &T::AccountId,
+		currency_id: T::CurrencyId,
+		amount: T::Balance,
+		account: &AccountData<T::Balance>,
+	) -> WithdrawConsequence<T::Balance> {
+		if amount.is_zero() {
 			return WithdrawConsequence::Success;
 		}
 
+		if Self::ensure_not_frozen(currency_id, who).is_err() {
+			return WithdrawConsequence::Frozen;
+		}
+
 		if TotalIssuance::<T>::get(currency_id).checked_sub(&amount).is_none() {
 			return WithdrawConsequence::Underflow;
 		}
@@ -703,11 +1406,11 @@ impl<T: Config> Pallet<T> {
 
 		// Provider restriction - total account balance cannot be reduced to zero if it
 		// cannot sustain the loss of a provider reference.
-		// NOTE: This assumes that the pallet is a provider (which is true). Is this
-		// ever changes, then this will need to adapt accordingly.
+		// NOTE: Token accounts of insufficient currencies hold a consumer reference
+		// instead, which can always be dropped.
 		let ed = T::ExistentialDeposits::get(&currency_id);
 		let success = if new_total_balance < ed {
-			if frame_system::Pallet::<T>::can_dec_provider(who) {
+			if Self::can_dec_reference(currency_id, who) {
 				WithdrawConsequence::ReducedToZero(new_total_balance)
 			} else {
 				return WithdrawConsequence::WouldDie;
@@ -730,6 +1433,30 @@ impl<T: Config> Pallet<T> {
 		success
 	}
 
+	/// The amount that the free balance of `who` may not drop below when
+	/// withdrawing for any of the `reasons`.
+	///
+	/// Only the locks whose reasons intersect with `reasons` are applied,
+	/// freezes are always applied.
+	pub(crate) fn frozen_for_reasons(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		account: &AccountData<T::Balance>,
+		reasons: WithdrawReasons,
+	) -> T::Balance {
+		if account.frozen().is_zero() {
+			return Zero::zero();
+		}
+
+		let max_lock = Self::locks(who, currency_id)
+			.iter()
+			.filter(|lock| lock.reasons.intersects(reasons))
+			.fold(Zero::zero(), |frozen: T::Balance, lock| frozen.max(lock.amount));
+		Self::freezes(who, currency_id)
+			.iter()
+			.fold(max_lock, |frozen, freeze| frozen.max(freeze.amount))
+	}
+
 	// Ensure that an account can withdraw from their free balance given any
 	// existing withdrawal restrictions like locks and vesting balance.
 	// Is a no-op if amount to be withdrawn is zero.
@@ -737,22 +1464,39 @@ impl<T: Config> Pallet<T> {
 		currency_id: T::CurrencyId,
 		who: &T::AccountId,
 		amount: T::Balance,
+	) -> DispatchResult {
+		Self::ensure_can_withdraw_with_reasons(currency_id, who, amount, WithdrawReasons::all())
+	}
+
+	// Ensure that an account can withdraw from their free balance for the
+	// given `reasons`, only the locks in effect for any of the `reasons` are
+	// applied.
+	// Is a no-op if amount to be withdrawn is zero.
+	pub(crate) fn ensure_can_withdraw_with_reasons(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
 	) -> DispatchResult {
 		if amount.is_zero() {
 			return Ok(());
 		}
 
-		let new_balance = Self::free_balance(currency_id, who)
-			.checked_sub(&amount)
-			.ok_or(Error::<T>::BalanceTooLow)?;
+		let account = Self::accounts(who, currency_id);
+		let new_balance = account.free.checked_sub(&amount).ok_or(Error::<T>::BalanceTooLow)?;
 		ensure!(
-			new_balance >= Self::accounts(who, currency_id).frozen(),
+			new_balance >= Self::frozen_for_reasons(currency_id, who, &account, reasons),
 			Error::<T>::LiquidityRestrictions
 		);
 		Ok(())
 	}
 
-	pub(crate) fn try_mutate_account<R, E>(
+	/// Mutate the token account of `who` under `currency_id`.
+	///
+	/// A new token account adds a reference to `who` according to the
+	/// sufficiency of the currency, the mutation fails if the reference can't
+	/// be added.
+	pub(crate) fn try_mutate_account<R, E: From<DispatchError>>(
 		who: &T::AccountId,
 		currency_id: T::CurrencyId,
 		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> sp_std::result::Result<R, E>,
@@ -760,38 +1504,49 @@ impl<T: Config> Pallet<T> {
 		Accounts::<T>::try_mutate_exists(who, currency_id, |maybe_account| {
 			let existed = maybe_account.is_some();
 			let mut account = maybe_account.take().unwrap_or_default();
-			f(&mut account, existed).map(move |result| {
-				let maybe_endowed = if !existed { Some(account.free) } else { None };
-				let mut maybe_dust: Option<T::Balance> = None;
-				let total = account.total();
-				*maybe_account = if total < T::ExistentialDeposits::get(&currency_id) {
-					// if ED is not zero, but account total is zero, account will be reaped
-					if total.is_zero() {
-						None
-					} else {
-						if !T::DustRemovalWhitelist::contains(who) {
-							maybe_dust = Some(total);
-						}
-						Some(account)
-					}
+			let previous_total = account.total();
+			let result = f(&mut account, existed)?;
+			let maybe_endowed = if !existed { Some(account.free) } else { None };
+			let mut maybe_dust: Option<T::Balance> = None;
+			let total = account.total();
+			*maybe_account = if total < T::ExistentialDeposits::get(&currency_id) {
+				// if ED is not zero, but account total is zero, account will be reaped
+				if total.is_zero() {
+					None
 				} else {
-					// Note: if ED is zero, account will never be reaped
+					if !T::DustRemovalWhitelist::contains(who) {
+						maybe_dust = Some(total);
+					}
 					Some(account)
-				};
+				}
+			} else {
+				// Note: if ED is zero, account will never be reaped
+				Some(account)
+			};
 
-				(maybe_endowed, existed, maybe_account.is_some(), maybe_dust, result)
-			})
+			let exists = maybe_account.is_some();
+			if !existed && exists {
+				// the last fallible step, nothing is written if it fails
+				Self::inc_account_reference(currency_id, who)?;
+			}
+
+			Ok((maybe_endowed, existed, exists, maybe_dust, previous_total, result))
 		})
-		.map(|(maybe_endowed, existed, exists, maybe_dust, result)| {
+		.map(|(maybe_endowed, existed, exists, maybe_dust, previous_total, result)| {
+			Self::record_snapshot_balance(currency_id, who, previous_total);
+
 			if existed && !exists {
-				// If existed before, decrease account provider.
-				// Ignore the result, because if it failed then there are remaining consumers,
-				// and the account storage in frame_system shouldn't be reaped.
-				let _ = frame_system::Pallet::<T>::dec_providers(who);
+				if InsufficientAccounts::<T>::take(who, currency_id).is_some() {
+					// If existed before with a consumer reference, decrease account consumer.
+					frame_system::Pallet::<T>::dec_consumers(who);
+				} else {
+					// If existed before, decrease account provider.
+					// Ignore the result, because if it failed then there are remaining consumers,
+					// and the account storage in frame_system shouldn't be reaped.
+					let _ = frame_system::Pallet::<T>::dec_providers(who);
+				}
 				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnKilledTokenAccount::happened(&(who.clone(), currency_id));
 			} else if !existed && exists {
-				// if new, increase account provider
-				frame_system::Pallet::<T>::inc_providers(who);
 				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnNewTokenAccount::happened(&(who.clone(), currency_id));
 			}
 
@@ -808,6 +1563,9 @@ impl<T: Config> Pallet<T> {
 				// to avoid some unexpected errors.
 				<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnDust::on_dust(who, currency_id, dust_amount);
 
+				// only account the dust which was actually removed by `OnDust`
+				let removed = dust_amount.saturating_sub(Self::accounts(who, currency_id).total());
+				TotalDust::<T>::mutate(currency_id, |total| *total = total.saturating_add(removed));
 				Self::deposit_event(Event::DustLost {
 					currency_id,
 					who: who.clone(),
@@ -819,15 +1577,160 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// The weight of recording the snapshot balances of `accounts` token
+	/// accounts, which is added by every mutation of a token account.
+	pub(crate) fn snapshot_weight(accounts: u32) -> Weight {
+		T::WeightInfo::record_snapshot_balance().saturating_mul(accounts.into())
+	}
+
+	/// Record the total balance of `who` for the latest snapshot of
+	/// `currency_id`, if it wasn't changed since the snapshot was taken.
+	fn record_snapshot_balance(currency_id: T::CurrencyId, who: &T::AccountId, total: T::Balance) {
+		let snapshot_id = CurrentSnapshotId::<T>::get(currency_id);
+		if !snapshot_id.is_zero() && !SnapshotBalances::<T>::contains_key((currency_id, snapshot_id, who)) {
+			SnapshotBalances::<T>::insert((currency_id, snapshot_id, who), total);
+		}
+	}
+
+	/// The total balance of `who` when the snapshot `snapshot_id` of
+	/// `currency_id` was taken, `None` if there is no such snapshot.
+	///
+	/// NOTE: Reads the snapshots taken after `snapshot_id` until one recorded
+	/// the balance of `who`. Only the snapshots which aren't pruned are
+	/// queryable, so it reads at most `T::MaxSnapshots` of them.
+	pub fn balance_at(snapshot_id: SnapshotId, currency_id: T::CurrencyId, who: &T::AccountId) -> Option<T::Balance> {
+		if !Snapshots::<T>::contains_key(currency_id, snapshot_id) {
+			return None;
+		}
+
+		// the balance is recorded before the first change after a snapshot, if it
+		// wasn't recorded for `snapshot_id` it didn't change until the next
+		// recorded snapshot
+		let current_snapshot_id = CurrentSnapshotId::<T>::get(currency_id);
+		(snapshot_id..=current_snapshot_id)
+			.find_map(|id| SnapshotBalances::<T>::get((currency_id, id, who)))
+			.or_else(|| Some(Self::accounts(who, currency_id).total()))
+	}
+
+	/// Every currency in which `who` has a non-zero balance, with its
+	/// balance.
+	///
+	/// NOTE: Iterates all the token accounts of `who`, meant for off-chain
+	/// queries.
+	pub fn account_balances(who: &T::AccountId) -> Vec<(T::CurrencyId, AccountData<T::Balance>)> {
+		Accounts::<T>::iter_prefix(who)
+			.filter(|(_, account)| !account.total().is_zero())
+			.collect()
+	}
+
+	/// The total issuance of each of `currency_ids`, in the same order.
+	pub fn total_issuances(currency_ids: Vec<T::CurrencyId>) -> Vec<(T::CurrencyId, T::Balance)> {
+		currency_ids
+			.into_iter()
+			.map(|currency_id| (currency_id, Self::total_issuance(currency_id)))
+			.collect()
+	}
+
+	/// The balance of `who` in `currency_id` that can be withdrawn or
+	/// transferred, keeping the token account alive if `keep_alive`.
+	pub fn reducible_balance_of(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		keep_alive: bool,
+		force: bool,
+	) -> T::Balance {
+		let preservation = if keep_alive {
+			Preservation::Preserve
+		} else {
+			Preservation::Expendable
+		};
+		let force = if force { Fortitude::Force } else { Fortitude::Polite };
+		<Self as fungibles::Inspect<_>>::reducible_balance(currency_id, who, preservation, force)
+	}
+
+	/// Add the reference of a new token account of `currency_id` to `who`, a
+	/// provider for sufficient currencies, or a consumer otherwise.
+	///
+	/// NOTE: Blocked currencies are not checked here, new token accounts of
+	/// them are rejected by `ensure_can_create_account` beforehand.
+	fn inc_account_reference(currency_id: T::CurrencyId, who: &T::AccountId) -> DispatchResult {
+		if T::Sufficiency::get(&currency_id) == SufficiencyKind::Sufficient {
+			frame_system::Pallet::<T>::inc_providers(who);
+		} else {
+			frame_system::Pallet::<T>::inc_consumers(who).map_err(|_| TokenError::CannotCreate)?;
+			InsufficientAccounts::<T>::insert(who, currency_id, ());
+		}
+		Ok(())
+	}
+
+	/// Mutate the token account of `who` under `currency_id`, which must not
+	/// create a new token account unless it's ensured by
+	/// `ensure_can_create_account`.
 	pub(crate) fn mutate_account<R>(
 		who: &T::AccountId,
 		currency_id: T::CurrencyId,
 		f: impl FnOnce(&mut AccountData<T::Balance>, bool) -> R,
 	) -> (R, Option<T::Balance>) {
-		Self::try_mutate_account(who, currency_id, |account, existed| -> Result<R, Infallible> {
-			Ok(f(account, existed))
-		})
-		.expect("Error is infallible; qed")
+		let mut result = None;
+		let maybe_dust = match Self::try_mutate_account(who, currency_id, |account, existed| -> DispatchResult {
+			result = Some(f(account, existed));
+			Ok(())
+		}) {
+			Ok((_, maybe_dust)) => maybe_dust,
+			Err(e) => {
+				defensive!("the token account can't be created", e);
+				None
+			}
+		};
+		(result.expect("`f` is always called; qed"), maybe_dust)
+	}
+
+	/// Walk `Accounts` from `DustSweepCursor` and remove the dust of the
+	/// token accounts whose total is below the existential deposit, for
+	/// example after the existential deposit of a currency was raised.
+	///
+	/// Stops when the next token account could exceed `limit`, and returns
+	/// the weight used.
+	pub(crate) fn sweep_dust(limit: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		// read and write the cursor
+		let mut used = db_weight.reads_writes(1, 1);
+		let max_per_account = db_weight
+			.reads(1)
+			.saturating_add(T::WeightInfo::sweep_dust_account())
+			.saturating_add(Self::snapshot_weight(2));
+		if used.saturating_add(max_per_account).any_gt(limit) {
+			return Weight::zero();
+		}
+
+		let mut accounts = match DustSweepCursor::<T>::get() {
+			Some((who, currency_id)) => Accounts::<T>::iter_from(Accounts::<T>::hashed_key_for(who, currency_id)),
+			None => Accounts::<T>::iter(),
+		};
+		let mut cursor = None;
+		while !used.saturating_add(max_per_account).any_gt(limit) {
+			let (who, currency_id, account) = match accounts.next() {
+				Some(next) => next,
+				None => {
+					// reached the end, start over next time
+					cursor = None;
+					break;
+				}
+			};
+			used.saturating_accrue(db_weight.reads(1));
+
+			if account.total() < T::ExistentialDeposits::get(&currency_id) && !T::DustRemovalWhitelist::contains(&who) {
+				// mutating the account triggers the dust removal
+				Self::mutate_account(&who, currency_id, |_, _| {});
+				used.saturating_accrue(T::WeightInfo::sweep_dust_account());
+				// the dust may be transferred to another account
+				used.saturating_accrue(Self::snapshot_weight(2));
+			}
+			cursor = Some((who, currency_id));
+		}
+
+		DustSweepCursor::<T>::set(cursor);
+		used
 	}
 
 	/// Set free balance of `who` to a new value.
@@ -868,14 +1771,15 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
-	/// Update the account entry for `who` under `currency_id`, given the
-	/// locks.
-	pub(crate) fn update_locks(
+	/// Update the frozen balance of `who` under `currency_id`, given the
+	/// locks and freezes.
+	fn update_frozen(
 		currency_id: T::CurrencyId,
 		who: &T::AccountId,
 		locks: &[BalanceLock<T::Balance>],
-	) -> DispatchResult {
-		// track lock delta
+		freezes: &[FreezeData<T::RuntimeFreezeReason, T::Balance>],
+	) {
+		// track frozen delta
 		let mut total_frozen_prev = Zero::zero();
 		let mut total_frozen_after = Zero::zero();
 
@@ -886,9 +1790,39 @@ impl<T: Config> Pallet<T> {
 			for lock in locks.iter() {
 				account.frozen = account.frozen.max(lock.amount);
 			}
+			for freeze in freezes.iter() {
+				account.frozen = account.frozen.max(freeze.amount);
+			}
 			total_frozen_after = account.frozen;
 		});
 
+		if total_frozen_prev < total_frozen_after {
+			let amount = total_frozen_after.saturating_sub(total_frozen_prev);
+			Self::deposit_event(Event::Locked {
+				currency_id,
+				who: who.clone(),
+				amount,
+			});
+		} else if total_frozen_prev > total_frozen_after {
+			let amount = total_frozen_prev.saturating_sub(total_frozen_after);
+			Self::deposit_event(Event::Unlocked {
+				currency_id,
+				who: who.clone(),
+				amount,
+			});
+		}
+	}
+
+	/// Update the account entry for `who` under `currency_id`, given the
+	/// locks.
+	pub(crate) fn update_locks(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		locks: &[BalanceLock<T::Balance>],
+	) -> DispatchResult {
+		// update account data
+		Self::update_frozen(currency_id, who, locks, &Self::freezes(who, currency_id));
+
 		// update locks
 		let existed = Locks::<T>::contains_key(who, currency_id);
 		if locks.is_empty() {
@@ -915,23 +1849,272 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		if total_frozen_prev < total_frozen_after {
-			let amount = total_frozen_after.saturating_sub(total_frozen_prev);
-			Self::deposit_event(Event::Locked {
+		Ok(())
+	}
+
+	/// Update the account entry for `who` under `currency_id`, given the
+	/// freezes.
+	pub(crate) fn update_freezes(
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		freezes: &[FreezeData<T::RuntimeFreezeReason, T::Balance>],
+	) -> DispatchResult {
+		let bounded_freezes: BoundedVec<FreezeData<T::RuntimeFreezeReason, T::Balance>, T::MaxFreezes> =
+			freezes.to_vec().try_into().map_err(|_| Error::<T>::TooManyFreezes)?;
+
+		// update account data
+		Self::update_frozen(currency_id, who, &Self::locks(who, currency_id), freezes);
+
+		// update freezes
+		let existed = Freezes::<T>::contains_key(who, currency_id);
+		if freezes.is_empty() {
+			Freezes::<T>::remove(who, currency_id);
+			if existed {
+				// decrease account ref count when destruct freeze
+				frame_system::Pallet::<T>::dec_consumers(who);
+			}
+		} else {
+			Freezes::<T>::insert(who, currency_id, bounded_freezes);
+			if !existed {
+				// increase account ref count when initialize freeze
+				if frame_system::Pallet::<T>::inc_consumers(who).is_err() {
+					// No providers for the freezes. This is impossible under normal circumstances
+					// since the funds that are frozen will themselves be stored in the account and
+					// therefore will need a reference.
+					log::warn!(
+						"Warning: Attempt to introduce freeze consumer reference, yet no providers. \
+						This is unexpected but should be safe."
+					);
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Set a lock on the balance of `who` under `currency_id`, in effect for
+	/// the given `reasons`.
+	///
+	/// Is a no-op if lock amount is zero.
+	pub(crate) fn do_set_lock(
+		lock_id: LockIdentifier,
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		let mut new_lock = Some(BalanceLock {
+			id: lock_id,
+			amount,
+			reasons: reasons.into(),
+		});
+		let mut locks = Self::locks(who, currency_id)
+			.into_iter()
+			.filter_map(|lock| {
+				if lock.id == lock_id {
+					new_lock.take()
+				} else {
+					Some(lock)
+				}
+			})
+			.collect::<Vec<_>>();
+		if let Some(lock) = new_lock {
+			locks.push(lock)
+		}
+		Self::update_locks(currency_id, who, &locks[..])?;
+
+		Self::deposit_event(Event::LockSet {
+			lock_id,
+			currency_id,
+			who: who.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Extend a lock on the balance of `who` under `currency_id`. An existing
+	/// lock keeps the greater amount and the union of the withdraw reasons.
+	///
+	/// Is a no-op if lock amount is zero.
+	pub(crate) fn do_extend_lock(
+		lock_id: LockIdentifier,
+		currency_id: T::CurrencyId,
+		who: &T::AccountId,
+		amount: T::Balance,
+		reasons: WithdrawReasons,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		let mut new_lock = Some(BalanceLock {
+			id: lock_id,
+			amount,
+			reasons: reasons.into(),
+		});
+		let mut locks = Self::locks(who, currency_id)
+			.into_iter()
+			.filter_map(|lock| {
+				if lock.id == lock_id {
+					new_lock.take().map(|nl| {
+						let new_amount = lock.amount.max(nl.amount);
+						Self::deposit_event(Event::LockSet {
+							lock_id,
+							currency_id,
+							who: who.clone(),
+							amount: new_amount,
+						});
+						BalanceLock {
+							id: lock.id,
+							amount: new_amount,
+							reasons: (WithdrawReasons::from(lock.reasons) | reasons).into(),
+						}
+					})
+				} else {
+					Some(lock)
+				}
+			})
+			.collect::<Vec<_>>();
+		if let Some(lock) = new_lock {
+			Self::deposit_event(Event::LockSet {
+				lock_id,
 				currency_id,
 				who: who.clone(),
-				amount,
+				amount: lock.amount,
 			});
-		} else if total_frozen_prev > total_frozen_after {
-			let amount = total_frozen_prev.saturating_sub(total_frozen_after);
-			Self::deposit_event(Event::Unlocked {
+			locks.push(lock)
+		}
+		Self::update_locks(currency_id, who, &locks[..])
+	}
+
+	/// Set the amount on hold for `reason` of `who` under `currency_id`.
+	///
+	/// Note: this only maintains the `Holds` bookkeeping, the caller is
+	/// expected to move the funds into or out of the reserved balance.
+	pub(crate) fn set_hold(
+		currency_id: T::CurrencyId,
+		reason: &T::RuntimeHoldReason,
+		who: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Holds::<T>::try_mutate_exists(who, currency_id, |maybe_holds| -> DispatchResult {
+			let mut holds = maybe_holds.take().unwrap_or_default();
+			match holds.binary_search_by_key(reason, |data| data.id) {
+				Ok(index) => {
+					if amount.is_zero() {
+						holds.remove(index);
+					} else {
+						holds[index].amount = amount;
+					}
+				}
+				Err(index) => {
+					if !amount.is_zero() {
+						holds
+							.try_insert(index, HoldData { id: *reason, amount })
+							.map_err(|_| Error::<T>::TooManyHolds)?;
+					}
+				}
+			}
+
+			if !holds.is_empty() {
+				*maybe_holds = Some(holds);
+			}
+			Ok(())
+		})
+	}
+
+	/// Move at most `movable` of the reserved balance of `slashed` into the
+	/// balance of `beneficiary`, according to `status`. Returns the amount
+	/// that was unable to be moved.
+	///
+	/// `slashed` must not be the same as `beneficiary`.
+	#[transactional]
+	pub(crate) fn do_repatriate_reserved(
+		currency_id: T::CurrencyId,
+		slashed: &T::AccountId,
+		beneficiary: &T::AccountId,
+		value: T::Balance,
+		status: BalanceStatus,
+		movable: T::Balance,
+	) -> sp_std::result::Result<T::Balance, DispatchError> {
+		let from_account = Self::accounts(slashed, currency_id);
+		let to_account = Self::accounts(beneficiary, currency_id);
+		let actual = movable.min(value);
+		if !actual.is_zero() && !Accounts::<T>::contains_key(beneficiary, currency_id) {
+			Self::ensure_can_create_account(currency_id, beneficiary)?;
+		}
+		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnUnreserve::on_unreserve(
+			currency_id,
+			slashed,
+			actual,
+		)?;
+		if status == BalanceStatus::Reserved {
+			<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PreReserve::on_reserve(
 				currency_id,
-				who: who.clone(),
-				amount,
-			});
+				beneficiary,
+				actual,
+			)?;
+		}
+		match status {
+			BalanceStatus::Free => {
+				Self::set_free_balance(
+					currency_id,
+					beneficiary,
+					to_account.free.defensive_saturating_add(actual),
+				);
+			}
+			BalanceStatus::Reserved => {
+				Self::set_reserved_balance(
+					currency_id,
+					beneficiary,
+					to_account.reserved.defensive_saturating_add(actual),
+				);
+			}
 		}
+		Self::set_reserved_balance(
+			currency_id,
+			slashed,
+			from_account.reserved.defensive_saturating_sub(actual),
+		);
 
-		Ok(())
+		if status == BalanceStatus::Reserved {
+			<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PostReserve::on_reserve(
+				currency_id,
+				beneficiary,
+				actual,
+			)?;
+		}
+
+		Self::deposit_event(Event::<T>::ReserveRepatriated {
+			currency_id,
+			from: slashed.clone(),
+			to: beneficiary.clone(),
+			amount: actual,
+			status,
+		});
+		Ok(value.defensive_saturating_sub(actual))
+	}
+
+	/// The balance of `who` under `currency_id` on hold for any reason. It is
+	/// part of the reserved balance.
+	pub(crate) fn total_held(currency_id: T::CurrencyId, who: &T::AccountId) -> T::Balance {
+		Self::holds(who, currency_id)
+			.iter()
+			.fold(Zero::zero(), |total: T::Balance, hold| {
+				total.saturating_add(hold.amount)
+			})
+	}
+
+	/// The reserved balance of `who` under `currency_id` which is not on hold
+	/// for any reason. Only this part could be unreserved, slashed or
+	/// repatriated without releasing a hold, so `Holds` never exceed the
+	/// reserved balance.
+	pub(crate) fn reserved_not_held(currency_id: T::CurrencyId, who: &T::AccountId) -> T::Balance {
+		Self::accounts(who, currency_id)
+			.reserved
+			.saturating_sub(Self::total_held(currency_id, who))
 	}
 
 	/// Transfer some free balance from `from` to `to`. Ensure from_account
@@ -940,6 +2123,12 @@ impl<T: Config> Pallet<T> {
 	///
 	/// Is a no-op if value to be transferred is zero or the `from` is the same
 	/// as `to`.
+	///
+	/// The fee returned by the `TransferFee` hook is deducted from `amount`
+	/// and routed to its receiver, so `from` is always debited `amount`. The
+	/// fee is waived if its receiver is frozen, can't be created or would end
+	/// up below the existential deposit.
+	#[transactional]
 	pub(crate) fn do_transfer(
 		currency_id: T::CurrencyId,
 		from: &T::AccountId,
@@ -951,19 +2140,51 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
+		Self::ensure_not_frozen(currency_id, from)?;
+		Self::ensure_not_frozen(currency_id, to)?;
+
 		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PreTransfer::on_transfer(
 			currency_id,
 			from,
 			to,
 			amount,
 		)?;
-		Self::try_mutate_account(to, currency_id, |to_account, _existed| -> DispatchResult {
+		let (fee, fee_receiver) =
+			match <T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::TransferFee::fee(
+				currency_id,
+				from,
+				to,
+				amount,
+			) {
+				// the fee can never exceed the transferred amount, and paying it to the
+				// sender or the recipient is the same as not charging it at all
+				Some((fee, receiver)) if !fee.is_zero() && receiver != *from && receiver != *to => {
+					let fee = fee.min(amount);
+					// waive the fee if the receiver can't take it, so it never fails a
+					// transfer
+					if Self::can_receive_fee(currency_id, &receiver, fee) {
+						(fee, Some(receiver))
+					} else {
+						(Zero::zero(), None)
+					}
+				}
+				_ => (Zero::zero(), None),
+			};
+
+		Self::try_mutate_account(to, currency_id, |to_account, to_existed| -> DispatchResult {
+			if !to_existed {
+				Self::ensure_can_create_account(currency_id, to)?;
+			}
+
 			Self::try_mutate_account(from, currency_id, |from_account, _existed| -> DispatchResult {
 				from_account.free = from_account
 					.free
 					.checked_sub(&amount)
 					.ok_or(Error::<T>::BalanceTooLow)?;
-				to_account.free = to_account.free.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+				to_account.free = to_account
+					.free
+					.checked_add(&amount.saturating_sub(fee))
+					.ok_or(ArithmeticError::Overflow)?;
 
 				let ed = T::ExistentialDeposits::get(&currency_id);
 				// if the total of `to_account` is below existential deposit, would return an
@@ -974,10 +2195,10 @@ impl<T: Config> Pallet<T> {
 					Error::<T>::ExistentialDeposit
 				);
 
-				Self::ensure_can_withdraw(currency_id, from, amount)?;
+				Self::ensure_can_withdraw_with_reasons(currency_id, from, amount, WithdrawReasons::TRANSFER)?;
 
 				let allow_death = existence_requirement == ExistenceRequirement::AllowDeath;
-				let allow_death = allow_death && frame_system::Pallet::<T>::can_dec_provider(from);
+				let allow_death = allow_death && Self::can_dec_reference(currency_id, from);
 				let would_be_dead = if from_account.total() < ed {
 					if from_account.total().is_zero() {
 						true
@@ -996,23 +2217,244 @@ impl<T: Config> Pallet<T> {
 			Ok(())
 		})?;
 
+		if let Some(fee_receiver) = fee_receiver {
+			Self::try_mutate_account(&fee_receiver, currency_id, |account, _existed| -> DispatchResult {
+				account.free = account.free.checked_add(&fee).ok_or(ArithmeticError::Overflow)?;
+				Ok(())
+			})?;
+		}
+
+		// the post hook is given the amount actually received by `to`
 		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PostTransfer::on_transfer(
 			currency_id,
 			from,
 			to,
-			amount,
+			amount.saturating_sub(fee),
 		)?;
 		Self::deposit_event(Event::Transfer {
 			currency_id,
 			from: from.clone(),
 			to: to.clone(),
 			amount,
+			fee,
 		});
 		Ok(())
 	}
 
+	/// Approve `delegate` to transfer `amount` more of the balance of `owner`.
+	pub(crate) fn do_approve_transfer(
+		currency_id: T::CurrencyId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Approvals::<T>::try_mutate((currency_id, owner, delegate), |maybe_approval| -> DispatchResult {
+			match maybe_approval {
+				Some(approval) => {
+					approval.amount = approval.amount.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+				}
+				None => {
+					let deposit = T::ApprovalDeposit::get(&currency_id);
+					<Self as MultiReservableCurrency<_>>::reserve(currency_id, owner, deposit)?;
+					*maybe_approval = Some(ApprovalData { amount, deposit });
+				}
+			}
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::TransferApproved {
+			currency_id,
+			owner: owner.clone(),
+			delegate: delegate.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Cancel the approval of `delegate` to transfer the balance of `owner`,
+	/// and release its deposit.
+	pub(crate) fn do_cancel_approval(
+		currency_id: T::CurrencyId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+	) -> DispatchResult {
+		let approval = Approvals::<T>::take((currency_id, owner, delegate)).ok_or(Error::<T>::Unapproved)?;
+		<Self as MultiReservableCurrency<_>>::unreserve(currency_id, owner, approval.deposit);
+
+		Self::deposit_event(Event::ApprovalCancelled {
+			currency_id,
+			owner: owner.clone(),
+			delegate: delegate.clone(),
+		});
+		Ok(())
+	}
+
+	/// Transfer `amount` of the balance of `owner` to `destination`, using
+	/// the approval of `delegate`.
+	pub(crate) fn do_transfer_approved(
+		currency_id: T::CurrencyId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		destination: &T::AccountId,
+		amount: T::Balance,
+	) -> DispatchResult {
+		Approvals::<T>::try_mutate_exists((currency_id, owner, delegate), |maybe_approval| -> DispatchResult {
+			let approval = maybe_approval.as_mut().ok_or(Error::<T>::Unapproved)?;
+			approval.amount = approval.amount.checked_sub(&amount).ok_or(Error::<T>::Unapproved)?;
+
+			Self::do_transfer(
+				currency_id,
+				owner,
+				destination,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			if approval.amount.is_zero() {
+				<Self as MultiReservableCurrency<_>>::unreserve(currency_id, owner, approval.deposit);
+				*maybe_approval = None;
+			}
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::TransferredApproved {
+			currency_id,
+			owner: owner.clone(),
+			delegate: delegate.clone(),
+			destination: destination.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	/// Move all the balances of `source` in every currency to `dest`,
+	/// returning the number of migrated currencies.
+	///
+	/// Locks, freezes, named reserves and holds with the same identifier are
+	/// merged by adding up their amounts.
+	///
+	/// Is a no-op if `source` is the same as `dest`.
+	#[transactional]
+	pub(crate) fn do_migrate_account(
+		source: &T::AccountId,
+		dest: &T::AccountId,
+		max_currencies: u32,
+	) -> Result<u32, DispatchError> {
+		// locks, freezes and holds could outlive the balances of a currency, so
+		// their currencies are migrated too. Stop reading as soon as there are more
+		// currencies than declared, the call is only charged for `max_currencies`.
+		let mut currency_ids: Vec<T::CurrencyId> = Vec::new();
+		for currency_id in Accounts::<T>::iter_key_prefix(source)
+			.chain(Locks::<T>::iter_key_prefix(source))
+			.chain(Freezes::<T>::iter_key_prefix(source))
+			.chain(Holds::<T>::iter_key_prefix(source))
+		{
+			if !currency_ids.contains(&currency_id) {
+				ensure!(
+					(currency_ids.len() as u32) < max_currencies,
+					Error::<T>::TooManyCurrencies
+				);
+				currency_ids.push(currency_id);
+			}
+		}
+		if source == dest {
+			return Ok(0);
+		}
+
+		for currency_id in currency_ids.iter().copied() {
+			Self::ensure_not_frozen(currency_id, source)?;
+			Self::ensure_not_frozen(currency_id, dest)?;
+
+			let mut locks = Self::locks(dest, currency_id).into_inner();
+			for lock in Self::locks(source, currency_id) {
+				match locks.iter_mut().find(|existing| existing.id == lock.id) {
+					Some(existing) => {
+						existing.amount = existing.amount.saturating_add(lock.amount);
+						existing.reasons =
+							(WithdrawReasons::from(existing.reasons) | WithdrawReasons::from(lock.reasons)).into();
+					}
+					None => locks.push(lock),
+				}
+			}
+
+			let mut freezes = Self::freezes(dest, currency_id);
+			for freeze in Self::freezes(source, currency_id) {
+				match freezes.binary_search_by_key(&freeze.id, |data| data.id) {
+					Ok(index) => freezes[index].amount = freezes[index].amount.saturating_add(freeze.amount),
+					Err(index) => freezes
+						.try_insert(index, freeze)
+						.map_err(|_| Error::<T>::TooManyFreezes)?,
+				}
+			}
+
+			let mut reserves = Self::reserves(dest, currency_id);
+			for reserve in Self::reserves(source, currency_id) {
+				match reserves.binary_search_by_key(&reserve.id, |data| data.id) {
+					Ok(index) => reserves[index].amount = reserves[index].amount.saturating_add(reserve.amount),
+					Err(index) => reserves
+						.try_insert(index, reserve)
+						.map_err(|_| Error::<T>::TooManyReserves)?,
+				}
+			}
+
+			let mut holds = Self::holds(dest, currency_id);
+			for hold in Self::holds(source, currency_id) {
+				match holds.binary_search_by_key(&hold.id, |data| data.id) {
+					Ok(index) => holds[index].amount = holds[index].amount.saturating_add(hold.amount),
+					Err(index) => holds.try_insert(index, hold).map_err(|_| Error::<T>::TooManyHolds)?,
+				}
+			}
+
+			let account = Self::accounts(source, currency_id);
+			Self::try_mutate_account(dest, currency_id, |dest_account, existed| -> DispatchResult {
+				if !existed {
+					Self::ensure_can_create_account(currency_id, dest)?;
+				}
+				dest_account.free = dest_account
+					.free
+					.checked_add(&account.free)
+					.ok_or(ArithmeticError::Overflow)?;
+				dest_account.reserved = dest_account
+					.reserved
+					.checked_add(&account.reserved)
+					.ok_or(ArithmeticError::Overflow)?;
+				Ok(())
+			})?;
+
+			// the reference counts of `dest` are only added once its account exists,
+			// and those of `source` removed before its account is killed
+			Self::update_locks(currency_id, dest, &locks)?;
+			Self::update_freezes(currency_id, dest, &freezes)?;
+			Self::update_locks(currency_id, source, &[])?;
+			Self::update_freezes(currency_id, source, &[])?;
+			if !reserves.is_empty() {
+				Reserves::<T>::insert(dest, currency_id, reserves);
+			}
+			Reserves::<T>::remove(source, currency_id);
+			if !holds.is_empty() {
+				Holds::<T>::insert(dest, currency_id, holds);
+			}
+			Holds::<T>::remove(source, currency_id);
+
+			Self::mutate_account(source, currency_id, |source_account, _| {
+				source_account.free = Zero::zero();
+				source_account.reserved = Zero::zero();
+			});
+
+			Self::deposit_event(Event::AccountMigrated {
+				currency_id,
+				from: source.clone(),
+				to: dest.clone(),
+				free: account.free,
+				reserved: account.reserved,
+			});
+		}
+
+		Ok(currency_ids.len() as u32)
+	}
+
 	/// Withdraw some free balance from an account, respecting existence
-	/// requirements.
+	/// requirements and the locks in effect for `reasons`.
 	///
 	/// `change_total_issuance`:
 	/// - true, decrease the total issuance by burned amount.
@@ -1023,6 +2465,7 @@ impl<T: Config> Pallet<T> {
 		currency_id: T::CurrencyId,
 		who: &T::AccountId,
 		amount: T::Balance,
+		reasons: WithdrawReasons,
 		existence_requirement: ExistenceRequirement,
 		change_total_issuance: bool,
 	) -> DispatchResult {
@@ -1030,8 +2473,10 @@ impl<T: Config> Pallet<T> {
 			return Ok(());
 		}
 
+		Self::ensure_not_frozen(currency_id, who)?;
+
 		Self::try_mutate_account(who, currency_id, |account, _existed| -> DispatchResult {
-			Self::ensure_can_withdraw(currency_id, who, amount)?;
+			Self::ensure_can_withdraw_with_reasons(currency_id, who, amount, reasons)?;
 			let previous_total = account.total();
 			account.free = account.free.defensive_saturating_sub(amount);
 
@@ -1090,6 +2535,8 @@ impl<T: Config> Pallet<T> {
 			return Ok(amount);
 		}
 
+		Self::ensure_not_frozen(currency_id, who)?;
+
 		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PreDeposit::on_deposit(
 			currency_id,
 			who,
@@ -1106,12 +2553,16 @@ impl<T: Config> Pallet<T> {
 					amount >= ed || existed || T::DustRemovalWhitelist::contains(who),
 					Error::<T>::ExistentialDeposit
 				);
+				if !existed {
+					Self::ensure_can_create_account(currency_id, who)?;
+				}
 			}
 
 			let new_total_issuance = Self::total_issuance(currency_id)
 				.checked_add(&amount)
 				.ok_or(ArithmeticError::Overflow)?;
 			if change_total_issuance {
+				Self::ensure_within_issuance_cap(currency_id, amount)?;
 				TotalIssuance::<T>::mutate(currency_id, |v| *v = new_total_issuance);
 			}
 			account.free = account.free.defensive_saturating_add(amount);
@@ -1131,6 +2582,66 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+#[cfg(any(feature = "std", feature = "try-runtime"))]
+impl<T: Config> Pallet<T> {
+	/// Check the accounting invariants of all the token accounts:
+	/// - `TotalIssuance` of a token type equals the sum of the free and
+	///   reserved balances of its accounts.
+	/// - `frozen` equals the max of the locks and freezes of the account.
+	/// - `reserved` covers the sum of the named reserves and holds of the
+	///   account. Reserves without an identifier are not itemized, so it may
+	///   be greater.
+	/// - No account is below the existential deposit, unless it is in
+	///   `DustRemovalWhitelist`.
+	pub fn do_try_state() -> Result<(), sp_runtime::TryRuntimeError> {
+		let mut total_issuance = sp_std::collections::btree_map::BTreeMap::<T::CurrencyId, T::Balance>::new();
+
+		for (who, currency_id, account) in Accounts::<T>::iter() {
+			let total = total_issuance.entry(currency_id).or_default();
+			*total = total.checked_add(&account.total()).ok_or(ArithmeticError::Overflow)?;
+
+			let frozen = Self::locks(&who, currency_id)
+				.iter()
+				.map(|lock| lock.amount)
+				.chain(Self::freezes(&who, currency_id).iter().map(|freeze| freeze.amount))
+				.max()
+				.unwrap_or_default();
+			ensure!(
+				account.frozen == frozen,
+				"Frozen balance is not the max of the locks and freezes"
+			);
+
+			let itemized = Self::reserves(&who, currency_id)
+				.iter()
+				.map(|reserve| reserve.amount)
+				.chain(Self::holds(&who, currency_id).iter().map(|hold| hold.amount))
+				.fold(Zero::zero(), |acc: T::Balance, amount| acc.saturating_add(amount));
+			ensure!(
+				account.reserved >= itemized,
+				"Reserved balance doesn't cover the named reserves and holds"
+			);
+
+			ensure!(
+				account.total() >= T::ExistentialDeposits::get(&currency_id) || T::DustRemovalWhitelist::contains(&who),
+				"Account is below the existential deposit"
+			);
+		}
+
+		for (currency_id, issuance) in TotalIssuance::<T>::iter() {
+			ensure!(
+				total_issuance.remove(&currency_id).unwrap_or_default() == issuance,
+				"Total issuance is not the sum of the account balances"
+			);
+		}
+		ensure!(
+			total_issuance.values().all(|total| total.is_zero()),
+			"Total issuance is not the sum of the account balances"
+		);
+
+		Ok(())
+	}
+}
+
 impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 	type CurrencyId = T::CurrencyId;
 	type Balance = T::Balance;
@@ -1173,7 +2684,14 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 
 	fn withdraw(currency_id: Self::CurrencyId, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
 		// allow death
-		Self::do_withdraw(currency_id, who, amount, ExistenceRequirement::AllowDeath, true)
+		Self::do_withdraw(
+			currency_id,
+			who,
+			amount,
+			WithdrawReasons::all(),
+			ExistenceRequirement::AllowDeath,
+			true,
+		)
 	}
 
 	// Check if `value` amount of free balance can be slashed from `who`.
@@ -1218,8 +2736,8 @@ impl<T: Config> MultiCurrency<T::AccountId> for Pallet<T> {
 			);
 		}
 
-		// slash reserved balance
-		let reserved_slashed_amount = account.reserved.min(remaining_slash);
+		// slash reserved balance, except the balance on hold
+		let reserved_slashed_amount = Self::reserved_not_held(currency_id, who).min(remaining_slash);
 
 		if !reserved_slashed_amount.is_zero() {
 			// Cannot underflow due to above line but just to be defensive here.
@@ -1255,20 +2773,9 @@ impl<T: Config> MultiCurrencyExtended<T::AccountId> for Pallet<T> {
 			return Ok(());
 		}
 
-		// Ensure this doesn't overflow. There isn't any traits that exposes
-		// `saturating_abs` so we need to do it manually.
-		let by_amount_abs = if by_amount == Self::Amount::min_value() {
-			Self::Amount::max_value()
-		} else {
-			by_amount.abs()
-		};
-
-		let by_balance =
-			TryInto::<Self::Balance>::try_into(by_amount_abs).map_err(|_| Error::<T>::AmountIntoBalanceFailed)?;
-		if by_amount.is_positive() {
-			Self::deposit(currency_id, who, by_balance)
-		} else {
-			Self::withdraw(currency_id, who, by_balance).map(|_| ())
+		match SignedBalance::try_from_amount(by_amount).ok_or(Error::<T>::AmountIntoBalanceFailed)? {
+			SignedBalance::Positive(by_balance) => Self::deposit(currency_id, who, by_balance),
+			SignedBalance::Negative(by_balance) => Self::withdraw(currency_id, who, by_balance),
 		}
 	}
 }
@@ -1276,43 +2783,20 @@ impl<T: Config> MultiCurrencyExtended<T::AccountId> for Pallet<T> {
 impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 	type Moment = BlockNumberFor<T>;
 
-	// Set a lock on the balance of `who` under `currency_id`.
-	// Is a no-op if lock amount is zero.
-	fn set_lock(
-		lock_id: LockIdentifier,
-		currency_id: Self::CurrencyId,
-		who: &T::AccountId,
-		amount: Self::Balance,
-	) -> DispatchResult {
-		if amount.is_zero() {
-			return Ok(());
-		}
-		let mut new_lock = Some(BalanceLock { id: lock_id, amount });
-		let mut locks = Self::locks(who, currency_id)
-			.into_iter()
-			.filter_map(|lock| {
-				if lock.id == lock_id {
-					new_lock.take()
-				} else {
-					Some(lock)
-				}
-			})
-			.collect::<Vec<_>>();
-		if let Some(lock) = new_lock {
-			locks.push(lock)
-		}
-		Self::update_locks(currency_id, who, &locks[..])?;
-
-		Self::deposit_event(Event::LockSet {
-			lock_id,
-			currency_id,
-			who: who.clone(),
-			amount,
-		});
-		Ok(())
+	// Set a lock on the balance of `who` under `currency_id`, in effect for
+	// all withdraw reasons.
+	// Is a no-op if lock amount is zero.
+	fn set_lock(
+		lock_id: LockIdentifier,
+		currency_id: Self::CurrencyId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::do_set_lock(lock_id, currency_id, who, amount, WithdrawReasons::all())
 	}
 
-	// Extend a lock on the balance of `who` under `currency_id`.
+	// Extend a lock on the balance of `who` under `currency_id`, in effect for
+	// all withdraw reasons.
 	// Is a no-op if lock amount is zero
 	fn extend_lock(
 		lock_id: LockIdentifier,
@@ -1320,42 +2804,7 @@ impl<T: Config> MultiLockableCurrency<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		if amount.is_zero() {
-			return Ok(());
-		}
-		let mut new_lock = Some(BalanceLock { id: lock_id, amount });
-		let mut locks = Self::locks(who, currency_id)
-			.into_iter()
-			.filter_map(|lock| {
-				if lock.id == lock_id {
-					new_lock.take().map(|nl| {
-						let new_amount = lock.amount.max(nl.amount);
-						Self::deposit_event(Event::LockSet {
-							lock_id,
-							currency_id,
-							who: who.clone(),
-							amount: new_amount,
-						});
-						BalanceLock {
-							id: lock.id,
-							amount: new_amount,
-						}
-					})
-				} else {
-					Some(lock)
-				}
-			})
-			.collect::<Vec<_>>();
-		if let Some(lock) = new_lock {
-			Self::deposit_event(Event::LockSet {
-				lock_id,
-				currency_id,
-				who: who.clone(),
-				amount: lock.amount,
-			});
-			locks.push(lock)
-		}
-		Self::update_locks(currency_id, who, &locks[..])
+		Self::do_extend_lock(lock_id, currency_id, who, amount, WithdrawReasons::all())
 	}
 
 	fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) -> DispatchResult {
@@ -1381,13 +2830,14 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 		if value.is_zero() {
 			return true;
 		}
-		Self::ensure_can_withdraw(currency_id, who, value).is_ok()
+		Self::ensure_can_withdraw_with_reasons(currency_id, who, value, WithdrawReasons::RESERVE).is_ok()
 	}
 
 	/// Slash from reserved balance, returning any amount that was unable to
 	/// be slashed.
 	///
-	/// Is a no-op if the value to be slashed is zero.
+	/// Is a no-op if the value to be slashed is zero. The balance on hold is
+	/// never slashed, see `reserved_not_held`.
 	fn slash_reserved(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		if value.is_zero() {
 			return value;
@@ -1398,11 +2848,10 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 			who,
 			value,
 		);
-		let reserved_balance = Self::reserved_balance(currency_id, who);
-		let actual = reserved_balance.min(value);
+		let actual = Self::reserved_not_held(currency_id, who).min(value);
 		Self::mutate_account(who, currency_id, |account, _| {
-			// ensured reserved_balance >= actual but just to be defensive here.
-			account.reserved = reserved_balance.defensive_saturating_sub(actual);
+			// ensured account.reserved >= actual but just to be defensive here.
+			account.reserved = account.reserved.defensive_saturating_sub(actual);
 		});
 		TotalIssuance::<T>::mutate(currency_id, |v| *v = v.defensive_saturating_sub(actual));
 
@@ -1423,12 +2872,18 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	/// balance.
 	///
 	/// Is a no-op if value to be reserved is zero.
+	#[transactional]
 	fn reserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> DispatchResult {
 		if value.is_zero() {
 			return Ok(());
 		}
-		Self::ensure_can_withdraw(currency_id, who, value)?;
+		Self::ensure_can_withdraw_with_reasons(currency_id, who, value, WithdrawReasons::RESERVE)?;
 
+		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PreReserve::on_reserve(
+			currency_id,
+			who,
+			value,
+		)?;
 		Self::mutate_account(who, currency_id, |account, _| {
 			account.free = account.free.defensive_saturating_sub(value);
 			account.reserved = account.reserved.defensive_saturating_add(value);
@@ -1439,6 +2894,11 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 				amount: value,
 			});
 		});
+		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::PostReserve::on_reserve(
+			currency_id,
+			who,
+			value,
+		)?;
 
 		Ok(())
 	}
@@ -1446,14 +2906,30 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 	/// Unreserve some funds, returning any amount that was unable to be
 	/// unreserved.
 	///
-	/// Is a no-op if the value to be unreserved is zero.
+	/// Is a no-op if the value to be unreserved is zero. The balance on hold
+	/// is never unreserved, see `reserved_not_held`. The `OnUnreserve`
+	/// hook is called with the amount which would actually be unreserved, if
+	/// it vetoes then nothing is unreserved and the whole `value` is returned.
 	fn unreserve(currency_id: Self::CurrencyId, who: &T::AccountId, value: Self::Balance) -> Self::Balance {
 		if value.is_zero() {
 			return value;
 		}
+		let actual = Self::reserved_not_held(currency_id, who).min(value);
+		if actual.is_zero() {
+			return value;
+		}
+		if <T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnUnreserve::on_unreserve(
+			currency_id,
+			who,
+			actual,
+		)
+		.is_err()
+		{
+			return value;
+		}
 
-		let (remaining, _) = Self::mutate_account(who, currency_id, |account, _| {
-			let actual = account.reserved.min(value);
+		Self::mutate_account(who, currency_id, |account, _| {
+			// ensured account.reserved >= actual but just to be defensive here.
 			account.reserved = account.reserved.defensive_saturating_sub(actual);
 			account.free = account.free.defensive_saturating_add(actual);
 
@@ -1462,10 +2938,9 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 				who: who.clone(),
 				amount: actual,
 			});
-			value.defensive_saturating_sub(actual)
 		});
 
-		remaining
+		value.defensive_saturating_sub(actual)
 	}
 
 	/// Move the reserved balance of one account into the balance of
@@ -1493,39 +2968,14 @@ impl<T: Config> MultiReservableCurrency<T::AccountId> for Pallet<T> {
 			};
 		}
 
-		let from_account = Self::accounts(slashed, currency_id);
-		let to_account = Self::accounts(beneficiary, currency_id);
-		let actual = from_account.reserved.min(value);
-		match status {
-			BalanceStatus::Free => {
-				Self::set_free_balance(
-					currency_id,
-					beneficiary,
-					to_account.free.defensive_saturating_add(actual),
-				);
-			}
-			BalanceStatus::Reserved => {
-				Self::set_reserved_balance(
-					currency_id,
-					beneficiary,
-					to_account.reserved.defensive_saturating_add(actual),
-				);
-			}
-		}
-		Self::set_reserved_balance(
+		Self::do_repatriate_reserved(
 			currency_id,
 			slashed,
-			from_account.reserved.defensive_saturating_sub(actual),
-		);
-
-		Self::deposit_event(Event::<T>::ReserveRepatriated {
-			currency_id,
-			from: slashed.clone(),
-			to: beneficiary.clone(),
-			amount: actual,
+			beneficiary,
+			value,
 			status,
-		});
-		Ok(value.defensive_saturating_sub(actual))
+			Self::reserved_not_held(currency_id, slashed),
+		)
 	}
 }
 
@@ -1804,9 +3254,12 @@ impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
 		_force: Fortitude,
 	) -> Self::Balance {
 		let a = Self::accounts(who, asset_id);
-		// Liquid balance is what is neither reserved nor locked/frozen.
-		let liquid = a.free.saturating_sub(a.frozen);
-		if frame_system::Pallet::<T>::can_dec_provider(who) && !matches!(preservation, Preservation::Protect) {
+		// Liquid balance is what is neither reserved nor locked/frozen. Only the locks
+		// in effect for transfers are applied.
+		let liquid = a
+			.free
+			.saturating_sub(Self::frozen_for_reasons(asset_id, who, &a, WithdrawReasons::TRANSFER));
+		if Self::can_dec_reference(asset_id, who) && !matches!(preservation, Preservation::Protect) {
 			liquid
 		} else {
 			// `must_remain_to_exist` is the part of liquid balance which must remain to
@@ -1845,6 +3298,8 @@ impl<T: Config> fungibles::Mutate<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_frozen(asset_id, who)?;
+		Self::ensure_within_issuance_cap(asset_id, amount)?;
 		Self::deposit_consequence(who, asset_id, amount, &Self::accounts(who, asset_id)).into_result()?;
 		// do not require existing
 		Self::do_deposit(asset_id, who, amount, false, true)
@@ -1859,11 +3314,20 @@ impl<T: Config> fungibles::Mutate<T::AccountId> for Pallet<T> {
 		// TODO: Respect fortitude
 		_fortitude: Fortitude,
 	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_not_frozen(asset_id, who)?;
 		let extra =
 			Self::withdraw_consequence(who, asset_id, amount, &Self::accounts(who, asset_id)).into_result(false)?;
 		let actual = amount.defensive_saturating_add(extra);
 		// allow death
-		Self::do_withdraw(asset_id, who, actual, ExistenceRequirement::AllowDeath, true).map(|_| actual)
+		Self::do_withdraw(
+			asset_id,
+			who,
+			actual,
+			WithdrawReasons::all(),
+			ExistenceRequirement::AllowDeath,
+			true,
+		)
+		.map(|_| actual)
 	}
 
 	fn transfer(
@@ -1891,6 +3355,8 @@ impl<T: Config> fungibles::Unbalanced<T::AccountId> for Pallet<T> {
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> Result<Option<Self::Balance>, DispatchError> {
+		Self::ensure_not_frozen(asset_id, who)?;
+
 		let max_reduction = <Self as fungibles::Inspect<_>>::reducible_balance(
 			asset_id,
 			who,
@@ -1898,6 +3364,10 @@ impl<T: Config> fungibles::Unbalanced<T::AccountId> for Pallet<T> {
 			Fortitude::Force,
 		);
 
+		if !amount.is_zero() && !Accounts::<T>::contains_key(who, asset_id) {
+			Self::ensure_can_create_account(asset_id, who)?;
+		}
+
 		// Balance is the same type and will not overflow
 		let (_, dust_amount) = Self::try_mutate_account(who, asset_id, |account, _| -> Result<(), DispatchError> {
 			// Make sure the reduction (if there is one) is no more than the maximum
@@ -1948,6 +3418,65 @@ impl<T: Config> fungibles::Unbalanced<T::AccountId> for Pallet<T> {
 		// here just return decrease amount, shouldn't count the dust_amount
 		Ok(old_balance.saturating_sub(new_balance))
 	}
+
+	fn increase_balance(
+		asset: Self::AssetId,
+		who: &T::AccountId,
+		amount: Self::Balance,
+		precision: Precision,
+	) -> Result<Self::Balance, DispatchError> {
+		Self::ensure_within_issuance_cap(asset, amount)?;
+
+		let old_balance = <Pallet<T> as fungibles::Inspect<T::AccountId>>::balance(asset, who);
+		let new_balance = if let Precision::BestEffort = precision {
+			old_balance.saturating_add(amount)
+		} else {
+			old_balance.checked_add(&amount).ok_or(ArithmeticError::Overflow)?
+		};
+		if new_balance < T::ExistentialDeposits::get(&asset) {
+			// attempt to increase from 0 to below minimum, stays at zero
+			return if let Precision::BestEffort = precision {
+				Ok(Zero::zero())
+			} else {
+				Err(TokenError::BelowMinimum.into())
+			};
+		}
+		if new_balance == old_balance {
+			return Ok(Zero::zero());
+		}
+		let _dust_amount = Self::write_balance(asset, who, new_balance)?.unwrap_or_default();
+
+		Ok(new_balance.saturating_sub(old_balance))
+	}
+}
+
+impl<T: Config> fungibles::approvals::Inspect<T::AccountId> for Pallet<T> {
+	fn allowance(asset: Self::AssetId, owner: &T::AccountId, delegate: &T::AccountId) -> Self::Balance {
+		Approvals::<T>::get((asset, owner, delegate))
+			.map(|approval| approval.amount)
+			.unwrap_or_default()
+	}
+}
+
+impl<T: Config> fungibles::approvals::Mutate<T::AccountId> for Pallet<T> {
+	fn approve(
+		asset: Self::AssetId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::do_approve_transfer(asset, owner, delegate, amount)
+	}
+
+	fn transfer_from(
+		asset: Self::AssetId,
+		owner: &T::AccountId,
+		delegate: &T::AccountId,
+		dest: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		Self::do_transfer_approved(asset, owner, delegate, dest, amount)
+	}
 }
 
 impl<T: Config> fungibles::Balanced<T::AccountId> for Pallet<T> {
@@ -1978,25 +3507,35 @@ impl<T: Config> fungibles::Balanced<T::AccountId> for Pallet<T> {
 
 type ReasonOf<P, T> = <P as fungibles::InspectHold<<T as frame_system::Config>::AccountId>>::Reason;
 impl<T: Config> fungibles::InspectHold<T::AccountId> for Pallet<T> {
-	type Reason = ();
+	type Reason = T::RuntimeHoldReason;
 
-	fn balance_on_hold(asset_id: Self::AssetId, _reason: &Self::Reason, who: &T::AccountId) -> T::Balance {
-		Self::accounts(who, asset_id).reserved
+	fn balance_on_hold(asset_id: Self::AssetId, reason: &Self::Reason, who: &T::AccountId) -> T::Balance {
+		let holds = Self::holds(who, asset_id);
+		holds
+			.binary_search_by_key(reason, |data| data.id)
+			.map(|index| holds[index].amount)
+			.unwrap_or_default()
 	}
 
+	/// The balance reserved through `MultiReservableCurrency` is not on hold
+	/// and is left out.
 	fn total_balance_on_hold(asset: Self::AssetId, who: &T::AccountId) -> Self::Balance {
-		Self::accounts(who, asset).reserved
+		Self::total_held(asset, who)
 	}
 
 	fn reducible_total_balance_on_hold(_asset: Self::AssetId, _who: &T::AccountId, _force: Fortitude) -> Self::Balance {
 		0u32.into()
 	}
 
-	fn hold_available(_asset: Self::AssetId, _reason: &Self::Reason, _who: &T::AccountId) -> bool {
-		true
+	fn hold_available(asset_id: Self::AssetId, reason: &Self::Reason, who: &T::AccountId) -> bool {
+		let holds = Self::holds(who, asset_id);
+		holds.len() < T::MaxHolds::get() as usize || holds.binary_search_by_key(reason, |data| data.id).is_ok()
 	}
 
-	fn can_hold(asset_id: Self::AssetId, _reason: &Self::Reason, who: &T::AccountId, amount: T::Balance) -> bool {
+	fn can_hold(asset_id: Self::AssetId, reason: &Self::Reason, who: &T::AccountId, amount: T::Balance) -> bool {
+		if !<Self as fungibles::InspectHold<_>>::hold_available(asset_id, reason, who) {
+			return false;
+		}
 		let a = Self::accounts(who, asset_id);
 		let min_balance = T::ExistentialDeposits::get(&asset_id).max(a.frozen);
 		if a.reserved.checked_add(&amount).is_none() {
@@ -2016,16 +3555,27 @@ impl<T: Config> fungibles::InspectHold<T::AccountId> for Pallet<T> {
 impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T> {
 	fn hold(
 		asset_id: Self::AssetId,
-		_reason: &ReasonOf<Self, T>,
+		reason: &ReasonOf<Self, T>,
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
-		<Pallet<T> as MultiReservableCurrency<_>>::reserve(asset_id, who, amount)
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		ensure!(
+			<Self as fungibles::InspectHold<_>>::hold_available(asset_id, reason, who),
+			Error::<T>::TooManyHolds
+		);
+		let on_hold = <Self as fungibles::InspectHold<_>>::balance_on_hold(asset_id, reason, who);
+		let new_on_hold = on_hold.checked_add(&amount).ok_or(ArithmeticError::Overflow)?;
+		<Pallet<T> as MultiReservableCurrency<_>>::reserve(asset_id, who, amount)?;
+		Self::set_hold(asset_id, reason, who, new_on_hold)
 	}
 
 	fn release(
 		asset_id: Self::AssetId,
-		_reason: &ReasonOf<Self, T>,
+		reason: &ReasonOf<Self, T>,
 		who: &T::AccountId,
 		amount: Self::Balance,
 		precision: Precision,
@@ -2034,26 +3584,30 @@ impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T> {
 			return Ok(amount);
 		}
 
+		let on_hold = <Self as fungibles::InspectHold<_>>::balance_on_hold(asset_id, reason, who);
 		// Done on a best-effort basis.
+		// Guaranteed to be <= amount, <= on_hold and <= reserved
+		let actual = amount.min(on_hold).min(Self::reserved_balance(asset_id, who));
+		ensure!(
+			matches!(precision, Precision::BestEffort) || actual == amount,
+			Error::<T>::BalanceTooLow
+		);
+		<T::CurrencyHooks as MutationHooks<T::AccountId, T::CurrencyId, T::Balance>>::OnUnreserve::on_unreserve(
+			asset_id, who, actual,
+		)?;
 		let (released, _) =
 			Self::try_mutate_account(who, asset_id, |a, _existed| -> Result<T::Balance, DispatchError> {
-				let new_free = a.free.saturating_add(amount.min(a.reserved));
-				let actual = new_free.defensive_saturating_sub(a.free);
-				// Guaranteed to be <= amount and <= a.reserved
-				ensure!(
-					matches!(precision, Precision::BestEffort) || actual == amount,
-					Error::<T>::BalanceTooLow
-				);
-				a.free = new_free;
-				a.reserved = a.reserved.saturating_sub(actual);
+				a.free = a.free.checked_add(&actual).ok_or(ArithmeticError::Overflow)?;
+				a.reserved = a.reserved.defensive_saturating_sub(actual);
 
 				Self::deposit_event(Event::Unreserved {
 					currency_id: asset_id,
 					who: who.clone(),
-					amount,
+					amount: actual,
 				});
 				Ok(actual)
 			})?;
+		Self::set_hold(asset_id, reason, who, on_hold.defensive_saturating_sub(released))?;
 
 		Ok(released)
 	}
@@ -2073,35 +3627,78 @@ impl<T: Config> fungibles::MutateHold<T::AccountId> for Pallet<T> {
 		} else {
 			Status::Free
 		};
+		let source_on_hold = <Self as fungibles::InspectHold<T::AccountId>>::balance_on_hold(asset_id, reason, source);
 		ensure!(
-			amount <= <Self as fungibles::InspectHold<T::AccountId>>::balance_on_hold(asset_id, reason, source)
-				|| precision == Precision::BestEffort,
+			amount <= source_on_hold || precision == Precision::BestEffort,
 			Error::<T>::BalanceTooLow
 		);
-		let gap = Self::repatriate_reserved(asset_id, source, dest, amount, status)?;
+		if source == dest {
+			return match status {
+				Status::Free => {
+					<Self as fungibles::MutateHold<_>>::release(asset_id, reason, source, amount, precision)
+				}
+				Status::Reserved => Ok(amount.min(source_on_hold)),
+			};
+		}
+		if status == Status::Reserved {
+			ensure!(
+				<Self as fungibles::InspectHold<_>>::hold_available(asset_id, reason, dest),
+				Error::<T>::TooManyHolds
+			);
+		}
+
+		let to_transfer = amount.min(source_on_hold);
+		let gap = Self::do_repatriate_reserved(
+			asset_id,
+			source,
+			dest,
+			to_transfer,
+			status,
+			Self::reserved_balance(asset_id, source),
+		)?;
+		let actual = to_transfer.saturating_sub(gap);
+
+		Self::set_hold(
+			asset_id,
+			reason,
+			source,
+			source_on_hold.defensive_saturating_sub(actual),
+		)?;
+		if status == Status::Reserved {
+			let dest_on_hold = <Self as fungibles::InspectHold<T::AccountId>>::balance_on_hold(asset_id, reason, dest);
+			Self::set_hold(asset_id, reason, dest, dest_on_hold.defensive_saturating_add(actual))?;
+		}
+
 		// return actual transferred amount
-		Ok(amount.saturating_sub(gap))
+		Ok(actual)
 	}
 }
 
 impl<T: Config> fungibles::UnbalancedHold<T::AccountId> for Pallet<T> {
 	fn set_balance_on_hold(
 		asset: Self::AssetId,
-		_reason: &Self::Reason,
+		reason: &Self::Reason,
 		who: &T::AccountId,
 		amount: Self::Balance,
 	) -> DispatchResult {
+		let old_on_hold = <Self as fungibles::InspectHold<T::AccountId>>::balance_on_hold(asset, reason, who);
 		// Balance is the same type and will not overflow
 		Self::try_mutate_account(who, asset, |account, _| -> Result<(), DispatchError> {
-			let old_reserved = account.reserved;
-			account.reserved = amount;
+			// reserved = reserved + new - old
+			account.reserved = account
+				.reserved
+				.checked_add(&amount)
+				.ok_or(ArithmeticError::Overflow)?
+				.checked_sub(&old_on_hold)
+				.ok_or(ArithmeticError::Underflow)?;
 			// free = free + old - new
 			account.free = account
 				.free
-				.checked_add(&old_reserved)
+				.checked_add(&old_on_hold)
 				.ok_or(ArithmeticError::Overflow)?
-				.checked_sub(&account.reserved)
+				.checked_sub(&amount)
 				.ok_or(TokenError::BelowMinimum)?;
+			Self::set_hold(asset, reason, who, amount)?;
 
 			Self::deposit_event(Event::BalanceSet {
 				currency_id: asset,
@@ -2116,6 +3713,106 @@ impl<T: Config> fungibles::UnbalancedHold<T::AccountId> for Pallet<T> {
 	}
 }
 
+impl<T: Config> fungibles::InspectFreeze<T::AccountId> for Pallet<T> {
+	type Id = T::RuntimeFreezeReason;
+
+	fn balance_frozen(asset_id: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> Self::Balance {
+		let freezes = Self::freezes(who, asset_id);
+		freezes
+			.binary_search_by_key(id, |data| data.id)
+			.map(|index| freezes[index].amount)
+			.unwrap_or_default()
+	}
+
+	fn can_freeze(asset_id: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> bool {
+		let freezes = Self::freezes(who, asset_id);
+		freezes.len() < T::MaxFreezes::get() as usize || freezes.binary_search_by_key(id, |data| data.id).is_ok()
+	}
+}
+
+impl<T: Config> fungibles::MutateFreeze<T::AccountId> for Pallet<T> {
+	// Set a freeze on the balance of `who` under `asset_id`.
+	// Is equivalent to `thaw` if freeze amount is zero.
+	fn set_freeze(asset_id: Self::AssetId, id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return <Self as fungibles::MutateFreeze<_>>::thaw(asset_id, id, who);
+		}
+		let mut freezes = Self::freezes(who, asset_id);
+		match freezes.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => {
+				freezes[index].amount = amount;
+			}
+			Err(index) => {
+				freezes
+					.try_insert(index, FreezeData { id: *id, amount })
+					.map_err(|_| Error::<T>::TooManyFreezes)?;
+			}
+		}
+		Self::update_freezes(asset_id, who, &freezes[..])?;
+
+		Self::deposit_event(Event::FreezeSet {
+			reason: *id,
+			currency_id: asset_id,
+			who: who.clone(),
+			amount,
+		});
+		Ok(())
+	}
+
+	// Extend a freeze on the balance of `who` under `asset_id`.
+	// Is a no-op if freeze amount is zero.
+	fn extend_freeze(
+		asset_id: Self::AssetId,
+		id: &Self::Id,
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+		let mut freezes = Self::freezes(who, asset_id);
+		let new_amount = match freezes.binary_search_by_key(id, |data| data.id) {
+			Ok(index) => {
+				freezes[index].amount = freezes[index].amount.max(amount);
+				freezes[index].amount
+			}
+			Err(index) => {
+				freezes
+					.try_insert(index, FreezeData { id: *id, amount })
+					.map_err(|_| Error::<T>::TooManyFreezes)?;
+				amount
+			}
+		};
+		Self::update_freezes(asset_id, who, &freezes[..])?;
+
+		Self::deposit_event(Event::FreezeSet {
+			reason: *id,
+			currency_id: asset_id,
+			who: who.clone(),
+			amount: new_amount,
+		});
+		Ok(())
+	}
+
+	// Is a no-op if there is no freeze of `id`.
+	fn thaw(asset_id: Self::AssetId, id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		let mut freezes = Self::freezes(who, asset_id);
+		let len = freezes.len();
+		freezes.retain(|freeze| &freeze.id != id);
+		if freezes.len() == len {
+			return Ok(());
+		}
+		Self::update_freezes(asset_id, who, &freezes[..])?;
+
+		Self::deposit_event(Event::FreezeRemoved {
+			reason: *id,
+			currency_id: asset_id,
+			who: who.clone(),
+		});
+		Ok(())
+	}
+}
+
 pub struct CurrencyAdapter<T, GetCurrencyId>(marker::PhantomData<(T, GetCurrencyId)>);
 
 impl<T, GetCurrencyId> PalletCurrency<T::AccountId> for CurrencyAdapter<T, GetCurrencyId>
@@ -2166,6 +3863,10 @@ where
 		if amount.is_zero() {
 			return NegativeImbalance::zero();
 		}
+		// nothing is issued if the total issuance would exceed the cap
+		if Pallet::<T>::ensure_within_issuance_cap(GetCurrencyId::get(), amount).is_err() {
+			return NegativeImbalance::zero();
+		}
 		TotalIssuance::<T>::mutate(GetCurrencyId::get(), |issued| {
 			*issued = issued.checked_add(&amount).unwrap_or_else(|| {
 				amount = Self::Balance::max_value().defensive_saturating_sub(*issued);
@@ -2187,10 +3888,10 @@ where
 	fn ensure_can_withdraw(
 		who: &T::AccountId,
 		amount: Self::Balance,
-		_reasons: WithdrawReasons,
+		reasons: WithdrawReasons,
 		_new_balance: Self::Balance,
 	) -> DispatchResult {
-		<Pallet<T> as MultiCurrency<_>>::ensure_can_withdraw(GetCurrencyId::get(), who, amount)
+		Pallet::<T>::ensure_can_withdraw_with_reasons(GetCurrencyId::get(), who, amount, reasons)
 	}
 
 	fn transfer(
@@ -2221,9 +3922,9 @@ where
 			);
 		}
 
-		// slash reserved balance
+		// slash reserved balance, except the balance on hold
 		if !remaining_slash.is_zero() {
-			let reserved_slashed_amount = account.reserved.min(remaining_slash);
+			let reserved_slashed_amount = Pallet::<T>::reserved_not_held(currency_id, who).min(remaining_slash);
 			remaining_slash = remaining_slash.defensive_saturating_sub(reserved_slashed_amount);
 			Pallet::<T>::set_reserved_balance(
 				currency_id,
@@ -2258,6 +3959,9 @@ where
 		who: &T::AccountId,
 		value: Self::Balance,
 	) -> sp_std::result::Result<Self::PositiveImbalance, DispatchError> {
+		// the total issuance grows when the imbalance is dropped, so the cap is
+		// checked here
+		Pallet::<T>::ensure_within_issuance_cap(GetCurrencyId::get(), value)?;
 		// do not change total issuance
 		Pallet::<T>::do_deposit(GetCurrencyId::get(), who, value, true, false).map(|_| PositiveImbalance::new(value))
 	}
@@ -2265,19 +3969,22 @@ where
 	/// Deposit some `value` into the free balance of `who`, possibly creating a
 	/// new account.
 	fn deposit_creating(who: &T::AccountId, value: Self::Balance) -> Self::PositiveImbalance {
-		// do not change total issuance
-		Pallet::<T>::do_deposit(GetCurrencyId::get(), who, value, false, false)
+		// the total issuance grows when the imbalance is dropped, so the cap is
+		// checked here
+		Pallet::<T>::ensure_within_issuance_cap(GetCurrencyId::get(), value)
+			// do not change total issuance
+			.and_then(|_| Pallet::<T>::do_deposit(GetCurrencyId::get(), who, value, false, false))
 			.map_or_else(|_| Self::PositiveImbalance::zero(), |_| PositiveImbalance::new(value))
 	}
 
 	fn withdraw(
 		who: &T::AccountId,
 		value: Self::Balance,
-		_reasons: WithdrawReasons,
+		reasons: WithdrawReasons,
 		liveness: ExistenceRequirement,
 	) -> sp_std::result::Result<Self::NegativeImbalance, DispatchError> {
 		// do not change total issuance
-		Pallet::<T>::do_withdraw(GetCurrencyId::get(), who, value, liveness, false)
+		Pallet::<T>::do_withdraw(GetCurrencyId::get(), who, value, reasons, liveness, false)
 			.map(|_| Self::NegativeImbalance::new(value))
 	}
 
@@ -2289,7 +3996,7 @@ where
 		Pallet::<T>::try_mutate_account(
 			who,
 			currency_id,
-			|account, existed| -> Result<SignedImbalance<Self::Balance, Self::PositiveImbalance>, ()> {
+			|account, existed| -> Result<SignedImbalance<Self::Balance, Self::PositiveImbalance>, DispatchError> {
 				// If we're attempting to set an existing account to less than ED, then
 				// bypass the entire operation. It's a no-op if you follow it through, but
 				// since this is an instance where we might account for a negative imbalance
@@ -2298,7 +4005,13 @@ where
 				// instance that there's no other accounts on the system at all, we might
 				// underflow the issuance and our arithmetic will be off.
 				let ed = T::ExistentialDeposits::get(&currency_id);
-				ensure!(value.saturating_add(account.reserved) >= ed || existed, ());
+				ensure!(
+					value.saturating_add(account.reserved) >= ed || existed,
+					TokenError::BelowMinimum
+				);
+				if !existed {
+					Pallet::<T>::ensure_can_create_account(currency_id, who)?;
+				}
 
 				let imbalance = if account.free <= value {
 					SignedImbalance::Positive(PositiveImbalance::new(value.saturating_sub(account.free)))
@@ -2418,12 +4131,12 @@ where
 	type Moment = BlockNumberFor<T>;
 	type MaxLocks = ();
 
-	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, _reasons: WithdrawReasons) {
-		let _ = <Pallet<T> as MultiLockableCurrency<_>>::set_lock(id, GetCurrencyId::get(), who, amount);
+	fn set_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		let _ = Pallet::<T>::do_set_lock(id, GetCurrencyId::get(), who, amount, reasons);
 	}
 
-	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, _reasons: WithdrawReasons) {
-		let _ = <Pallet<T> as MultiLockableCurrency<_>>::extend_lock(id, GetCurrencyId::get(), who, amount);
+	fn extend_lock(id: LockIdentifier, who: &T::AccountId, amount: Self::Balance, reasons: WithdrawReasons) {
+		let _ = Pallet::<T>::do_extend_lock(id, GetCurrencyId::get(), who, amount, reasons);
 	}
 
 	fn remove_lock(id: LockIdentifier, who: &T::AccountId) {
@@ -2593,3 +4306,37 @@ where
 		<Pallet<T> as fungibles::UnbalancedHold<_>>::set_balance_on_hold(GetCurrencyId::get(), reason, who, amount)
 	}
 }
+
+impl<T, GetCurrencyId> fungible::InspectFreeze<T::AccountId> for CurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<T::CurrencyId>,
+{
+	type Id = <Pallet<T> as fungibles::InspectFreeze<T::AccountId>>::Id;
+
+	fn balance_frozen(id: &Self::Id, who: &T::AccountId) -> Self::Balance {
+		<Pallet<T> as fungibles::InspectFreeze<_>>::balance_frozen(GetCurrencyId::get(), id, who)
+	}
+	fn balance_freezable(who: &T::AccountId) -> Self::Balance {
+		<Pallet<T> as fungibles::InspectFreeze<_>>::balance_freezable(GetCurrencyId::get(), who)
+	}
+	fn can_freeze(id: &Self::Id, who: &T::AccountId) -> bool {
+		<Pallet<T> as fungibles::InspectFreeze<_>>::can_freeze(GetCurrencyId::get(), id, who)
+	}
+}
+
+impl<T, GetCurrencyId> fungible::MutateFreeze<T::AccountId> for CurrencyAdapter<T, GetCurrencyId>
+where
+	T: Config,
+	GetCurrencyId: Get<T::CurrencyId>,
+{
+	fn set_freeze(id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		<Pallet<T> as fungibles::MutateFreeze<_>>::set_freeze(GetCurrencyId::get(), id, who, amount)
+	}
+	fn extend_freeze(id: &Self::Id, who: &T::AccountId, amount: Self::Balance) -> DispatchResult {
+		<Pallet<T> as fungibles::MutateFreeze<_>>::extend_freeze(GetCurrencyId::get(), id, who, amount)
+	}
+	fn thaw(id: &Self::Id, who: &T::AccountId) -> DispatchResult {
+		<Pallet<T> as fungibles::MutateFreeze<_>>::thaw(GetCurrencyId::get(), id, who)
+	}
+}
@@ -0,0 +1,59 @@
+use crate::{BalanceLock, Config, LockReasons, Locks, Pallet};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{OnRuntimeUpgrade, WithdrawReasons},
+	weights::Weight,
+};
+use orml_traits::LockIdentifier;
+
+use sp_std::vec::Vec;
+
+/// Migrate the `Locks` storage to store the withdraw reasons of each lock.
+///
+/// All existing locks were enforced for every withdraw reason, so they are
+/// migrated to be in effect for `WithdrawReasons::all()`.
+pub struct Migration<T>(PhantomData<T>);
+impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		let mut weight: Weight = Weight::zero();
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version < 1 {
+			let inner_weight = v1::migrate::<T>();
+			weight.saturating_accrue(inner_weight);
+		}
+		weight
+	}
+}
+
+pub(crate) mod v1 {
+	use super::*;
+
+	/// The lock layout before withdraw reasons were stored.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+	pub(crate) struct OldBalanceLock<Balance> {
+		pub id: LockIdentifier,
+		pub amount: Balance,
+	}
+
+	pub(crate) fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		Locks::<T>::translate::<Vec<OldBalanceLock<T::Balance>>, _>(|_, _, old_locks| {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			let locks = old_locks
+				.into_iter()
+				.map(|lock| BalanceLock {
+					id: lock.id,
+					amount: lock.amount,
+					reasons: LockReasons::from(WithdrawReasons::all()),
+				})
+				.collect::<Vec<_>>();
+			// the number of locks is unchanged so the bound is always satisfied
+			Some(BoundedVec::truncate_from(locks))
+		});
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight.saturating_accrue(T::DbWeight::get().writes(1));
+		weight
+	}
+}
@@ -11,7 +11,7 @@ use frame_support::{
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{
-	traits::{AccountIdConversion, IdentityLookup},
+	traits::{AccountIdConversion, IdentityLookup, SaturatedConversion},
 	AccountId32, BuildStorage, Permill,
 };
 use sp_std::cell::RefCell;
@@ -24,6 +24,8 @@ pub type ReserveIdentifier = [u8; 8];
 pub const DOT: CurrencyId = 1;
 pub const BTC: CurrencyId = 2;
 pub const ETH: CurrencyId = 3;
+pub const KSM: CurrencyId = 4;
+pub const ACA: CurrencyId = 5;
 pub const ALICE: AccountId = AccountId32::new([0u8; 32]);
 pub const BOB: AccountId = AccountId32::new([1u8; 32]);
 pub const CHARLIE: AccountId = AccountId32::new([2u8; 32]);
@@ -35,6 +37,13 @@ pub const ID_3: LockIdentifier = *b"3       ";
 pub const RID_1: ReserveIdentifier = [1u8; 8];
 pub const RID_2: ReserveIdentifier = [2u8; 8];
 
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub enum TestId {
+	Foo,
+	Bar,
+	Baz,
+}
+
 use crate as tokens;
 
 impl frame_system::Config for Runtime {
@@ -214,11 +223,48 @@ parameter_type_with_key! {
 		match currency_id {
 			&BTC => 1,
 			&DOT => 2,
+			&KSM => 1,
+			&ACA => 1,
 			_ => 0,
 		}
 	};
 }
 
+parameter_types! {
+	pub static DotIssuanceCap: Option<Balance> = None;
+}
+
+parameter_type_with_key! {
+	pub MaxIssuance: |currency_id: CurrencyId| -> Option<Balance> {
+		#[allow(clippy::match_ref_pats)] // false positive
+		match currency_id {
+			&DOT => DotIssuanceCap::get(),
+			_ => None,
+		}
+	};
+}
+
+parameter_type_with_key! {
+	pub ApprovalDeposit: |currency_id: CurrencyId| -> Balance {
+		#[allow(clippy::match_ref_pats)] // false positive
+		match currency_id {
+			&DOT => 5,
+			_ => 0,
+		}
+	};
+}
+
+parameter_type_with_key! {
+	pub Sufficiency: |currency_id: CurrencyId| -> SufficiencyKind {
+		#[allow(clippy::match_ref_pats)] // false positive
+		match currency_id {
+			&KSM => SufficiencyKind::Insufficient,
+			&ACA => SufficiencyKind::Blocked,
+			_ => SufficiencyKind::Sufficient,
+		}
+	};
+}
+
 thread_local! {
 	pub static CREATED: RefCell<Vec<(AccountId, CurrencyId)>> = RefCell::new(vec![]);
 	pub static KILLED: RefCell<Vec<(AccountId, CurrencyId)>> = RefCell::new(vec![]);
@@ -294,6 +340,10 @@ thread_local! {
 	pub static ON_DEPOSIT_POSTHOOK_CALLS: RefCell<u32> = RefCell::new(0);
 	pub static ON_TRANSFER_PREHOOK_CALLS: RefCell<u32> = RefCell::new(0);
 	pub static ON_TRANSFER_POSTHOOK_CALLS: RefCell<u32> = RefCell::new(0);
+	pub static ON_RESERVE_PREHOOK_CALLS: RefCell<u32> = RefCell::new(0);
+	pub static ON_RESERVE_POSTHOOK_CALLS: RefCell<u32> = RefCell::new(0);
+	pub static ON_UNRESERVE_CALLS: RefCell<u32> = RefCell::new(0);
+	pub static ON_UNRESERVE_AMOUNT: RefCell<u128> = RefCell::new(0);
 }
 
 pub struct OnSlashHook<T>(marker::PhantomData<T>);
@@ -382,10 +432,87 @@ impl<T: Config> PostTransfer<T> {
 	}
 }
 
+parameter_types! {
+	pub static ReserveHooksVeto: bool = false;
+	pub static PostReserveVeto: bool = false;
+}
+
+pub struct PreReserve<T>(marker::PhantomData<T>);
+impl<T: Config> OnReserve<T::AccountId, T::CurrencyId, T::Balance> for PreReserve<T> {
+	fn on_reserve(_currency_id: T::CurrencyId, _account_id: &T::AccountId, _amount: T::Balance) -> DispatchResult {
+		ON_RESERVE_PREHOOK_CALLS.with(|cell| *cell.borrow_mut() += 1);
+		ensure!(!ReserveHooksVeto::get(), DispatchError::Other("reserve vetoed"));
+		Ok(())
+	}
+}
+impl<T: Config> PreReserve<T> {
+	pub fn calls() -> u32 {
+		ON_RESERVE_PREHOOK_CALLS.with(|accounts| *accounts.borrow())
+	}
+}
+
+pub struct PostReserve<T>(marker::PhantomData<T>);
+impl<T: Config> OnReserve<T::AccountId, T::CurrencyId, T::Balance> for PostReserve<T> {
+	fn on_reserve(currency_id: T::CurrencyId, account_id: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		ON_RESERVE_POSTHOOK_CALLS.with(|cell| *cell.borrow_mut() += 1);
+		let account_balance: AccountData<T::Balance> =
+			tokens::Pallet::<T>::accounts::<T::AccountId, T::CurrencyId>(account_id.clone(), currency_id);
+		assert!(
+			account_balance.reserved.ge(&amount),
+			"Posthook must run after the account balance is updated."
+		);
+		ensure!(!PostReserveVeto::get(), DispatchError::Other("reserve vetoed after"));
+		Ok(())
+	}
+}
+impl<T: Config> PostReserve<T> {
+	pub fn calls() -> u32 {
+		ON_RESERVE_POSTHOOK_CALLS.with(|accounts| *accounts.borrow())
+	}
+}
+
+pub struct OnUnreserveHook<T>(marker::PhantomData<T>);
+impl<T: Config> OnUnreserve<T::AccountId, T::CurrencyId, T::Balance> for OnUnreserveHook<T> {
+	fn on_unreserve(_currency_id: T::CurrencyId, _account_id: &T::AccountId, amount: T::Balance) -> DispatchResult {
+		ON_UNRESERVE_CALLS.with(|cell| *cell.borrow_mut() += 1);
+		ON_UNRESERVE_AMOUNT.with(|cell| *cell.borrow_mut() = amount.saturated_into());
+		ensure!(!ReserveHooksVeto::get(), DispatchError::Other("unreserve vetoed"));
+		Ok(())
+	}
+}
+impl<T: Config> OnUnreserveHook<T> {
+	pub fn calls() -> u32 {
+		ON_UNRESERVE_CALLS.with(|accounts| *accounts.borrow())
+	}
+	pub fn last_amount() -> u128 {
+		ON_UNRESERVE_AMOUNT.with(|amount| *amount.borrow())
+	}
+}
+
 parameter_types! {
 	pub DustReceiver: AccountId = PalletId(*b"orml/dst").into_account_truncating();
 }
 
+parameter_types! {
+	pub static TransferFeeRate: Permill = Permill::zero();
+}
+
+pub struct MockTransferFee<T>(marker::PhantomData<T>);
+impl<T: Config> TransferFee<T::AccountId, T::CurrencyId, T::Balance> for MockTransferFee<T>
+where
+	T::AccountId: From<AccountId32>,
+{
+	fn fee(
+		_currency_id: T::CurrencyId,
+		_from: &T::AccountId,
+		_to: &T::AccountId,
+		amount: T::Balance,
+	) -> Option<(T::Balance, T::AccountId)> {
+		let fee = TransferFeeRate::get().mul_floor(amount);
+		(!fee.is_zero()).then(|| (fee, TREASURY_ACCOUNT.into()))
+	}
+}
+
 pub struct CurrencyHooks<T>(marker::PhantomData<T>);
 impl<T: Config> MutationHooks<T::AccountId, T::CurrencyId, T::Balance> for CurrencyHooks<T>
 where
@@ -398,6 +525,10 @@ where
 	type PostDeposit = PostDeposit<T>;
 	type PreTransfer = PreTransfer<T>;
 	type PostTransfer = PostTransfer<T>;
+	type PreReserve = PreReserve<T>;
+	type PostReserve = PostReserve<T>;
+	type OnUnreserve = OnUnreserveHook<T>;
+	type TransferFee = MockTransferFee<T>;
 	type OnNewTokenAccount = TrackCreatedAccounts<T>;
 	type OnKilledTokenAccount = TrackKilledAccounts<T>;
 }
@@ -409,11 +540,24 @@ impl Config for Runtime {
 	type CurrencyId = CurrencyId;
 	type WeightInfo = ();
 	type ExistentialDeposits = ExistentialDeposits;
+	type Sufficiency = Sufficiency;
+	type MaxIssuance = MaxIssuance;
 	type CurrencyHooks = CurrencyHooks<Runtime>;
 	type MaxLocks = ConstU32<2>;
 	type MaxReserves = ConstU32<2>;
 	type ReserveIdentifier = ReserveIdentifier;
+	type RuntimeHoldReason = TestId;
+	type MaxHolds = ConstU32<2>;
+	type RuntimeFreezeReason = TestId;
+	type MaxFreezes = ConstU32<2>;
 	type DustRemovalWhitelist = MockDustRemovalWhitelist;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransfersPerBatch = ConstU32<3>;
+	type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxSnapshots = ConstU32<2>;
+	type ApprovalDeposit = ApprovalDeposit;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkCurrencyId = ConstU32<DOT>;
 }
 pub type TreasuryCurrencyAdapter = <Runtime as pallet_treasury::Config>::Currency;
 
@@ -432,6 +576,27 @@ construct_runtime!(
 pub struct ExtBuilder {
 	balances: Vec<(AccountId, CurrencyId, Balance)>,
 	treasury_genesis: bool,
+	skip_try_state: bool,
+}
+
+/// Test externalities checking the invariants of the token accounts after
+/// each execution.
+pub struct TestExternalities {
+	ext: sp_io::TestExternalities,
+	try_state: bool,
+}
+
+impl TestExternalities {
+	pub fn execute_with<R>(&mut self, execute: impl FnOnce() -> R) -> R {
+		let try_state = self.try_state;
+		self.ext.execute_with(|| {
+			let result = execute();
+			if try_state {
+				Tokens::do_try_state().expect("token accounts invariants should hold");
+			}
+			result
+		})
+	}
 }
 
 impl ExtBuilder {
@@ -440,7 +605,14 @@ impl ExtBuilder {
 		self
 	}
 
-	pub fn build(self) -> sp_io::TestExternalities {
+	/// Don't check the invariants of the token accounts, for the tests
+	/// breaking them on purpose through the low-level setters.
+	pub fn skip_try_state(mut self) -> Self {
+		self.skip_try_state = true;
+		self
+	}
+
+	pub fn build(self) -> TestExternalities {
 		let mut t = frame_system::GenesisConfig::<Runtime>::default()
 			.build_storage()
 			.unwrap();
@@ -468,6 +640,9 @@ impl ExtBuilder {
 
 		let mut ext = sp_io::TestExternalities::new(t);
 		ext.execute_with(|| System::set_block_number(1));
-		ext
+		TestExternalities {
+			ext,
+			try_state: !self.skip_try_state,
+		}
 	}
 }
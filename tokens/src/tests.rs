@@ -3,7 +3,7 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnRuntimeUpgrade};
 use frame_system::RawOrigin;
 use mock::*;
 use sp_runtime::{traits::BadOrigin, TokenError};
@@ -41,6 +41,7 @@ fn transfer_should_work() {
 				from: ALICE,
 				to: BOB,
 				amount: 50,
+				fee: 0,
 			}));
 			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
 			assert_eq!(Tokens::free_balance(DOT, &BOB), 150);
@@ -88,6 +89,7 @@ fn transfer_keep_alive_should_work() {
 				from: ALICE,
 				to: BOB,
 				amount: 98,
+				fee: 0,
 			}));
 			assert_eq!(Tokens::free_balance(DOT, &ALICE), 2);
 			assert_eq!(Tokens::free_balance(DOT, &BOB), 198);
@@ -107,6 +109,7 @@ fn transfer_all_keep_alive_should_work() {
 				from: ALICE,
 				to: CHARLIE,
 				amount: 98,
+				fee: 0,
 			}));
 			assert_eq!(Tokens::free_balance(DOT, &ALICE), 2);
 
@@ -119,6 +122,7 @@ fn transfer_all_keep_alive_should_work() {
 				from: BOB,
 				to: CHARLIE,
 				amount: 50,
+				fee: 0,
 			}));
 		});
 }
@@ -138,6 +142,7 @@ fn transfer_all_allow_death_should_work() {
 				from: ALICE,
 				to: CHARLIE,
 				amount: 100,
+				fee: 0,
 			}));
 			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
 			assert_eq!(Tokens::free_balance(DOT, &ALICE), 0);
@@ -152,6 +157,7 @@ fn transfer_all_allow_death_should_work() {
 				from: BOB,
 				to: CHARLIE,
 				amount: 50,
+				fee: 0,
 			}));
 		});
 }
@@ -177,6 +183,7 @@ fn force_transfer_should_work() {
 				from: ALICE,
 				to: BOB,
 				amount: 100,
+				fee: 0,
 			}));
 			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
 			assert_eq!(TrackKilledAccounts::<Runtime>::accounts(), vec![(ALICE, DOT)]);
@@ -185,6 +192,460 @@ fn force_transfer_should_work() {
 		});
 }
 
+#[test]
+fn transfer_batch_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::transfer_batch(
+				Some(ALICE).into(),
+				vec![(BOB, DOT, 50), (CHARLIE, BTC, 30), (BOB, BTC, 20)]
+					.try_into()
+					.unwrap()
+			));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 50,
+				fee: 0,
+			}));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: BTC,
+				from: ALICE,
+				to: CHARLIE,
+				amount: 30,
+				fee: 0,
+			}));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::BatchTransferred {
+				from: ALICE,
+				count: 3,
+			}));
+
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(BTC, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 50);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 20);
+			assert_eq!(Tokens::free_balance(BTC, &CHARLIE), 30);
+		});
+}
+
+#[test]
+fn transfer_batch_should_be_atomic() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::transfer_batch(
+					Some(ALICE).into(),
+					vec![(BOB, DOT, 50), (CHARLIE, BTC, 101)].try_into().unwrap()
+				),
+				Error::<Runtime>::BalanceTooLow
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 0);
+		});
+}
+
+#[test]
+fn account_balances_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 50), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+
+			let mut balances = Tokens::account_balances(&ALICE);
+			balances.sort_by_key(|(currency_id, _)| *currency_id);
+			assert_eq!(
+				balances,
+				vec![
+					(
+						DOT,
+						AccountData {
+							free: 70,
+							reserved: 30,
+							frozen: 0,
+						}
+					),
+					(
+						BTC,
+						AccountData {
+							free: 50,
+							reserved: 0,
+							frozen: 0,
+						}
+					),
+				]
+			);
+			assert_eq!(Tokens::account_balances(&CHARLIE), vec![]);
+		});
+}
+
+#[test]
+fn portfolio_queries_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 50), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 20));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+
+			assert_eq!(
+				Tokens::total_issuances(vec![BTC, DOT, ETH]),
+				vec![(BTC, 50), (DOT, 200), (ETH, 0)]
+			);
+
+			// neither the reserved nor the locked balance can be withdrawn
+			assert_eq!(Tokens::reducible_balance_of(DOT, &ALICE, false, false), 50);
+			assert_eq!(Tokens::reducible_balance_of(BTC, &ALICE, false, false), 50);
+			assert_eq!(Tokens::reducible_balance_of(BTC, &CHARLIE, false, false), 0);
+
+			let locks = Tokens::locks(ALICE, DOT);
+			assert_eq!(locks.len(), 1);
+			assert_eq!((locks[0].id, locks[0].amount), (ID_1, 20));
+			let reserves = Tokens::reserves(ALICE, DOT);
+			assert_eq!(reserves.len(), 1);
+			assert_eq!((reserves[0].id, reserves[0].amount), (RID_1, 30));
+		});
+}
+
+#[test]
+fn migrate_account_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, BTC, 50), (BOB, DOT, 10)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 20));
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &BOB, 5));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 10));
+
+			assert_noop!(
+				Tokens::migrate_account(Some(ALICE).into(), BOB, 1),
+				Error::<Runtime>::TooManyCurrencies
+			);
+
+			assert_ok!(Tokens::migrate_account(Some(ALICE).into(), BOB, 2));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::AccountMigrated {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				free: 60,
+				reserved: 40,
+			}));
+			System::assert_has_event(RuntimeEvent::Tokens(crate::Event::AccountMigrated {
+				currency_id: BTC,
+				from: ALICE,
+				to: BOB,
+				free: 50,
+				reserved: 0,
+			}));
+
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 70);
+			assert_eq!(Tokens::reserved_balance(DOT, &BOB), 40);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &BOB), 30);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 50);
+			assert_eq!(Tokens::locks(BOB, DOT).len(), 1);
+			assert_eq!(Tokens::locks(BOB, DOT)[0].amount, 25);
+			assert_eq!(Tokens::accounts(BOB, DOT).frozen, 25);
+
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, BTC));
+			assert!(Tokens::locks(ALICE, DOT).is_empty());
+			assert!(Tokens::reserves(ALICE, DOT).is_empty());
+			assert_eq!(Tokens::total_issuance(DOT), 110);
+		});
+}
+
+#[test]
+fn migrate_account_should_migrate_locks_without_balance() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 10)])
+		.build()
+		.execute_with(|| {
+			// a lock could outlive the balance it locked
+			assert_ok!(Tokens::set_lock(ID_1, BTC, &ALICE, 20));
+			assert!(!Accounts::<Runtime>::contains_key(ALICE, BTC));
+
+			assert_noop!(
+				Tokens::migrate_account(Some(ALICE).into(), BOB, 1),
+				Error::<Runtime>::TooManyCurrencies
+			);
+
+			assert_ok!(Tokens::migrate_account(Some(ALICE).into(), BOB, 2));
+			assert!(Tokens::locks(ALICE, BTC).is_empty());
+			assert_eq!(Tokens::locks(BOB, BTC).len(), 1);
+			assert_eq!(Tokens::locks(BOB, BTC)[0].amount, 20);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 110);
+		});
+}
+
+#[test]
+fn migrate_account_should_respect_max_locks() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 20));
+			assert_ok!(Tokens::set_lock(ID_2, DOT, &BOB, 20));
+			assert_ok!(Tokens::set_lock(ID_3, DOT, &BOB, 20));
+
+			assert_noop!(
+				Tokens::migrate_account(Some(ALICE).into(), BOB, 1),
+				Error::<Runtime>::MaxLocksExceeded
+			);
+		});
+}
+
+#[test]
+fn transfer_fee_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			TransferFeeRate::set(Permill::from_percent(10));
+
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 50,
+				fee: 5,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 145);
+			assert_eq!(Tokens::free_balance(DOT, &TREASURY_ACCOUNT), 5);
+			assert_eq!(Tokens::total_issuance(DOT), 200);
+
+			assert_eq!(
+				<Tokens as fungibles::Mutate<_>>::transfer(DOT, &ALICE, &BOB, 20, Preservation::Expendable),
+				Ok(20)
+			);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 163);
+			assert_eq!(Tokens::free_balance(DOT, &TREASURY_ACCOUNT), 7);
+			assert_eq!(Tokens::total_issuance(DOT), 200);
+		});
+}
+
+#[test]
+fn transfer_fee_should_be_waived_if_receiver_cannot_take_it() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			TransferFeeRate::set(Permill::from_percent(10));
+
+			// the fee receiver has no account yet, and a fee of 1 is below the
+			// existential deposit
+			assert!(!Accounts::<Runtime>::contains_key(TREASURY_ACCOUNT, DOT));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 10));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 10,
+				fee: 0,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 90);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 110);
+			assert!(!Accounts::<Runtime>::contains_key(TREASURY_ACCOUNT, DOT));
+
+			// a fee above the existential deposit creates its account
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 20));
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 128);
+			assert_eq!(Tokens::free_balance(DOT, &TREASURY_ACCOUNT), 2);
+
+			// a frozen fee receiver takes no fee
+			assert_ok!(Tokens::freeze_account(RawOrigin::Root.into(), TREASURY_ACCOUNT, DOT));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 20));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::Transfer {
+				currency_id: DOT,
+				from: ALICE,
+				to: BOB,
+				amount: 20,
+				fee: 0,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 148);
+			assert_eq!(Tokens::free_balance(DOT, &TREASURY_ACCOUNT), 2);
+			assert_eq!(Tokens::total_issuance(DOT), 200);
+		});
+}
+
+#[test]
+fn balance_snapshots_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::take_snapshot(Some(ALICE).into(), DOT), BadOrigin);
+			assert_eq!(Tokens::balance_at(1, DOT, &ALICE), None);
+
+			assert_ok!(Tokens::take_snapshot(RawOrigin::Root.into(), DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::SnapshotTaken {
+				currency_id: DOT,
+				snapshot_id: 1,
+			}));
+			assert_eq!(Tokens::balance_at(1, DOT, &ALICE), Some(100));
+
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 30));
+			assert_ok!(Tokens::take_snapshot(RawOrigin::Root.into(), DOT));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 20));
+			assert_ok!(Tokens::reserve(DOT, &BOB, 50));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+
+			assert_eq!(Tokens::balance_at(1, DOT, &ALICE), Some(100));
+			assert_eq!(Tokens::balance_at(2, DOT, &ALICE), Some(70));
+			assert_eq!(Tokens::balance_at(1, DOT, &CHARLIE), Some(0));
+			assert_eq!(Tokens::balance_at(2, DOT, &CHARLIE), Some(30));
+			assert_eq!(Tokens::balance_at(1, DOT, &BOB), Some(100));
+			assert_eq!(Tokens::balance_at(2, DOT, &BOB), Some(100));
+			assert_eq!(Tokens::balance_at(3, DOT, &BOB), None);
+
+			// snapshots are taken per currency
+			assert_eq!(Tokens::balance_at(1, BTC, &ALICE), None);
+
+			// `MaxSnapshots` is 2
+			assert_noop!(
+				Tokens::take_snapshot(RawOrigin::Root.into(), DOT),
+				Error::<Runtime>::TooManySnapshots
+			);
+			assert_ok!(Tokens::take_snapshot(RawOrigin::Root.into(), BTC));
+		});
+}
+
+#[test]
+fn prune_snapshot_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::take_snapshot(RawOrigin::Root.into(), DOT));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 30));
+			assert_ok!(Tokens::take_snapshot(RawOrigin::Root.into(), DOT));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), CHARLIE, DOT, 20));
+
+			assert_noop!(Tokens::prune_snapshot(Some(ALICE).into(), DOT, 10), BadOrigin);
+			assert_noop!(
+				Tokens::prune_snapshot(RawOrigin::Root.into(), BTC, 10),
+				Error::<Runtime>::NoSnapshotToPrune
+			);
+
+			assert_noop!(
+				Tokens::take_snapshot(RawOrigin::Root.into(), DOT),
+				Error::<Runtime>::TooManySnapshots
+			);
+
+			// snapshot 1 recorded the balances of ALICE and CHARLIE
+			assert_eq!(SnapshotBalances::<Runtime>::iter_prefix((DOT, 1)).count(), 2);
+			assert_ok!(Tokens::prune_snapshot(RawOrigin::Root.into(), DOT, 10));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::SnapshotPruned {
+				currency_id: DOT,
+				snapshot_id: 1,
+			}));
+			assert_eq!(Tokens::balance_at(1, DOT, &ALICE), None);
+			assert_eq!(PrunedSnapshotId::<Runtime>::get(DOT), 1);
+			assert_eq!(SnapshotBalances::<Runtime>::iter_prefix((DOT, 1)).count(), 0);
+			assert_eq!(Tokens::balance_at(2, DOT, &ALICE), Some(70));
+
+			// the latest snapshot is kept
+			assert_noop!(
+				Tokens::prune_snapshot(RawOrigin::Root.into(), DOT, 10),
+				Error::<Runtime>::NoSnapshotToPrune
+			);
+
+			// a new snapshot could be taken once an old one is pruned
+			assert_ok!(Tokens::take_snapshot(RawOrigin::Root.into(), DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::SnapshotTaken {
+				currency_id: DOT,
+				snapshot_id: 3,
+			}));
+			assert_eq!(Tokens::balance_at(3, DOT, &ALICE), Some(50));
+		});
+}
+
+#[test]
+fn approvals_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 4)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Tokens::transfer_approved(Some(BOB).into(), DOT, ALICE, CHARLIE, 10),
+				Error::<Runtime>::Unapproved
+			);
+			assert_noop!(
+				Tokens::cancel_approval(Some(ALICE).into(), DOT, BOB),
+				Error::<Runtime>::Unapproved
+			);
+
+			// the owner must afford the deposit
+			assert_noop!(
+				Tokens::approve_transfer(Some(BOB).into(), DOT, ALICE, 1),
+				Error::<Runtime>::BalanceTooLow
+			);
+
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), DOT, BOB, 30));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::TransferApproved {
+				currency_id: DOT,
+				owner: ALICE,
+				delegate: BOB,
+				amount: 30,
+			}));
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 5);
+
+			// increasing an approval takes no further deposit
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), DOT, BOB, 20));
+			assert_eq!(
+				Approvals::<Runtime>::get((DOT, ALICE, BOB)),
+				Some(ApprovalData { amount: 50, deposit: 5 })
+			);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 5);
+
+			assert_noop!(
+				Tokens::transfer_approved(Some(BOB).into(), DOT, ALICE, CHARLIE, 51),
+				Error::<Runtime>::Unapproved
+			);
+			assert_ok!(Tokens::transfer_approved(Some(BOB).into(), DOT, ALICE, CHARLIE, 40));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::TransferredApproved {
+				currency_id: DOT,
+				owner: ALICE,
+				delegate: BOB,
+				destination: CHARLIE,
+				amount: 40,
+			}));
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 55);
+			assert_eq!(Tokens::free_balance(DOT, &CHARLIE), 40);
+			assert_eq!(
+				Approvals::<Runtime>::get((DOT, ALICE, BOB)),
+				Some(ApprovalData { amount: 10, deposit: 5 })
+			);
+
+			// the approval is removed and its deposit released once used up
+			assert_ok!(Tokens::transfer_approved(Some(BOB).into(), DOT, ALICE, CHARLIE, 10));
+			assert_eq!(Approvals::<Runtime>::get((DOT, ALICE, BOB)), None);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+
+			assert_ok!(Tokens::approve_transfer(Some(ALICE).into(), DOT, BOB, 10));
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 5);
+			assert_ok!(Tokens::cancel_approval(Some(ALICE).into(), DOT, BOB));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::ApprovalCancelled {
+				currency_id: DOT,
+				owner: ALICE,
+				delegate: BOB,
+			}));
+			assert_eq!(Approvals::<Runtime>::get((DOT, ALICE, BOB)), None);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 50);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+		});
+}
+
 #[test]
 fn set_balance_should_work() {
 	ExtBuilder::default()
@@ -500,7 +961,7 @@ fn ensure_can_withdraw_should_work() {
 
 #[test]
 fn set_free_balance_should_work() {
-	ExtBuilder::default().build().execute_with(|| {
+	ExtBuilder::default().skip_try_state().build().execute_with(|| {
 		/* Scenarios: ED is not zero, account is not in dust removal whitelist */
 		assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
 		assert_eq!(Tokens::free_balance(DOT, &ALICE), 0);
@@ -550,7 +1011,7 @@ fn set_free_balance_should_work() {
 
 #[test]
 fn set_reserved_balance_should_work() {
-	ExtBuilder::default().build().execute_with(|| {
+	ExtBuilder::default().skip_try_state().build().execute_with(|| {
 		/* Scenarios: ED is not zero, account is not in dust removal whitelist */
 		assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
 		assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
@@ -788,17 +1249,38 @@ fn do_withdraw_should_work() {
 		.execute_with(|| {
 			// always ok if amount is zero
 			assert!(!Accounts::<Runtime>::contains_key(BOB, DOT));
-			assert_ok!(Tokens::do_withdraw(DOT, &BOB, 0, ExistenceRequirement::KeepAlive, true));
+			assert_ok!(Tokens::do_withdraw(
+				DOT,
+				&BOB,
+				0,
+				WithdrawReasons::all(),
+				ExistenceRequirement::KeepAlive,
+				true
+			));
 			assert!(!Accounts::<Runtime>::contains_key(BOB, DOT));
 
 			assert_noop!(
-				Tokens::do_withdraw(DOT, &ALICE, 101, ExistenceRequirement::KeepAlive, true),
+				Tokens::do_withdraw(
+					DOT,
+					&ALICE,
+					101,
+					WithdrawReasons::all(),
+					ExistenceRequirement::KeepAlive,
+					true
+				),
 				Error::<Runtime>::BalanceTooLow
 			);
 
 			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 10));
 			assert_noop!(
-				Tokens::do_withdraw(DOT, &ALICE, 91, ExistenceRequirement::KeepAlive, true),
+				Tokens::do_withdraw(
+					DOT,
+					&ALICE,
+					91,
+					WithdrawReasons::all(),
+					ExistenceRequirement::KeepAlive,
+					true
+				),
 				Error::<Runtime>::LiquidityRestrictions
 			);
 
@@ -809,6 +1291,7 @@ fn do_withdraw_should_work() {
 				DOT,
 				&ALICE,
 				10,
+				WithdrawReasons::all(),
 				ExistenceRequirement::KeepAlive,
 				true
 			));
@@ -820,6 +1303,7 @@ fn do_withdraw_should_work() {
 				DOT,
 				&ALICE,
 				10,
+				WithdrawReasons::all(),
 				ExistenceRequirement::KeepAlive,
 				false
 			));
@@ -843,6 +1327,7 @@ fn do_withdraw_dust_removal_when_allow_death() {
 				DOT,
 				&ALICE,
 				99,
+				WithdrawReasons::all(),
 				ExistenceRequirement::AllowDeath,
 				true
 			));
@@ -860,7 +1345,14 @@ fn do_withdraw_report_keep_alive_error_when_ed_is_not_zero() {
 		.build()
 		.execute_with(|| {
 			assert_noop!(
-				Tokens::do_withdraw(DOT, &ALICE, 99, ExistenceRequirement::KeepAlive, true),
+				Tokens::do_withdraw(
+					DOT,
+					&ALICE,
+					99,
+					WithdrawReasons::all(),
+					ExistenceRequirement::KeepAlive,
+					true
+				),
 				Error::<Runtime>::KeepAlive
 			);
 
@@ -873,6 +1365,7 @@ fn do_withdraw_report_keep_alive_error_when_ed_is_not_zero() {
 				DOT,
 				&DAVE,
 				99,
+				WithdrawReasons::all(),
 				ExistenceRequirement::KeepAlive,
 				true
 			));
@@ -883,7 +1376,14 @@ fn do_withdraw_report_keep_alive_error_when_ed_is_not_zero() {
 			// even if dave is in dust removal whitelist, but if withdraw all total of it
 			// will still cause account reaped.
 			assert_noop!(
-				Tokens::do_withdraw(DOT, &DAVE, 1, ExistenceRequirement::KeepAlive, true),
+				Tokens::do_withdraw(
+					DOT,
+					&DAVE,
+					1,
+					WithdrawReasons::all(),
+					ExistenceRequirement::KeepAlive,
+					true
+				),
 				Error::<Runtime>::KeepAlive
 			);
 		});
@@ -902,6 +1402,7 @@ fn do_withdraw_will_not_report_keep_alive_error_when_ed_is_zero() {
 				ETH,
 				&ALICE,
 				100,
+				WithdrawReasons::all(),
 				ExistenceRequirement::KeepAlive,
 				true
 			));
@@ -915,6 +1416,7 @@ fn do_withdraw_will_not_report_keep_alive_error_when_ed_is_zero() {
 				ETH,
 				&DAVE,
 				100,
+				WithdrawReasons::all(),
 				ExistenceRequirement::KeepAlive,
 				true
 			));
@@ -994,9 +1496,51 @@ fn do_deposit_report_existential_deposit_error() {
 // tests for endowed account and remove account
 // *************************************************
 
+#[test]
+fn issuance_cap_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			DotIssuanceCap::set(Some(150));
+
+			assert_noop!(Tokens::deposit(DOT, &ALICE, 51), Error::<Runtime>::IssuanceCapExceeded);
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::mint_into(DOT, &ALICE, 51),
+				Error::<Runtime>::IssuanceCapExceeded
+			);
+			assert_noop!(
+				<Tokens as fungibles::Unbalanced<_>>::increase_balance(DOT, &ALICE, 51, Precision::Exact),
+				Error::<Runtime>::IssuanceCapExceeded
+			);
+			assert_noop!(
+				Tokens::set_balance(RawOrigin::Root.into(), BOB, DOT, 51, 0),
+				Error::<Runtime>::IssuanceCapExceeded
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(DOT, &ALICE, 51, Provenance::Minted),
+				DepositConsequence::Overflow
+			);
+
+			// other currencies are not capped
+			assert_ok!(Tokens::deposit(BTC, &ALICE, 1_000));
+
+			assert_ok!(Tokens::deposit(DOT, &ALICE, 30));
+			assert_ok!(<Tokens as fungibles::Mutate<_>>::mint_into(DOT, &ALICE, 20));
+			assert_eq!(Tokens::total_issuance(DOT), 150);
+			assert_noop!(Tokens::deposit(DOT, &ALICE, 1), Error::<Runtime>::IssuanceCapExceeded);
+
+			// moving balance is not affected by the cap
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50));
+			assert_ok!(Tokens::set_balance(RawOrigin::Root.into(), BOB, DOT, 40, 10));
+
+			DotIssuanceCap::set(None);
+		});
+}
+
 #[test]
 fn endowed_account_work() {
-	ExtBuilder::default().build().execute_with(|| {
+	ExtBuilder::default().skip_try_state().build().execute_with(|| {
 		assert_eq!(System::providers(&ALICE), 0);
 		assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
 		Tokens::set_free_balance(DOT, &ALICE, 100);
@@ -1014,6 +1558,7 @@ fn endowed_account_work() {
 fn remove_account_work() {
 	ExtBuilder::default()
 		.balances(vec![(ALICE, DOT, 100)])
+		.skip_try_state()
 		.build()
 		.execute_with(|| {
 			assert_eq!(System::providers(&ALICE), 1);
@@ -1077,6 +1622,7 @@ fn reap_account_will_dec_providers_work() {
 fn dust_removal_work() {
 	ExtBuilder::default()
 		.balances(vec![(ALICE, DOT, 100)])
+		.skip_try_state()
 		.build()
 		.execute_with(|| {
 			assert_eq!(System::providers(&ALICE), 1);
@@ -1093,6 +1639,7 @@ fn dust_removal_work() {
 			assert!(!Accounts::<Runtime>::contains_key(ALICE, DOT));
 			assert_eq!(Tokens::free_balance(DOT, &ALICE), 0);
 			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 1);
+			assert_eq!(Tokens::total_dust(DOT), 1);
 
 			// dave is in dust removal whitelist, will not remove its dust even if its total
 			// below ED
@@ -1111,9 +1658,55 @@ fn dust_removal_work() {
 		});
 }
 
+#[test]
+fn on_idle_should_sweep_dust() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.skip_try_state()
+		.build()
+		.execute_with(|| {
+			// token accounts below the existential deposit, as if it was raised after
+			// they were created
+			for who in [BOB, CHARLIE, DAVE] {
+				Accounts::<Runtime>::insert(
+					&who,
+					DOT,
+					AccountData {
+						free: 1,
+						reserved: 0,
+						frozen: 0,
+					},
+				);
+				let _ = System::inc_providers(&who);
+			}
+
+			assert_eq!(Tokens::on_idle(System::block_number(), Weight::zero()), Weight::zero());
+			assert_eq!(Tokens::total_dust(DOT), 0);
+
+			// only enough weight to sweep a single token account
+			let limit = <Runtime as Config>::WeightInfo::sweep_dust_account();
+			assert_eq!(Tokens::on_idle(System::block_number(), limit), limit);
+			assert!(DustSweepCursor::<Runtime>::get().is_some());
+			assert_eq!(Tokens::total_dust(DOT), 1);
+
+			Tokens::on_idle(System::block_number(), Weight::MAX);
+			assert_eq!(DustSweepCursor::<Runtime>::get(), None);
+			assert!(!Accounts::<Runtime>::contains_key(BOB, DOT));
+			assert!(!Accounts::<Runtime>::contains_key(CHARLIE, DOT));
+			assert_eq!(System::providers(&BOB), 0);
+			assert_eq!(System::providers(&CHARLIE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &DustReceiver::get()), 2);
+			assert_eq!(Tokens::total_dust(DOT), 2);
+
+			// dave is in dust removal whitelist, will not be swept
+			assert_eq!(Tokens::free_balance(DOT, &DAVE), 1);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 100);
+		});
+}
+
 #[test]
 fn account_survive_due_to_dust_transfer_failure() {
-	ExtBuilder::default().build().execute_with(|| {
+	ExtBuilder::default().skip_try_state().build().execute_with(|| {
 		let dust_account = DustReceiver::get();
 		Tokens::set_free_balance(DOT, &dust_account, 0);
 		assert_eq!(Tokens::free_balance(DOT, &dust_account), 0);
@@ -1134,6 +1727,157 @@ fn account_survive_due_to_dust_transfer_failure() {
 	});
 }
 
+#[test]
+fn insufficient_currency_should_use_consumer_reference() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, KSM, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(System::providers(&ALICE), 1);
+			assert_eq!(System::consumers(&ALICE), 1);
+			assert!(InsufficientAccounts::<Runtime>::contains_key(ALICE, KSM));
+
+			// BOB has no provider, the insufficient currency can't create its token account
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, KSM, 50),
+				TokenError::CannotCreate
+			);
+			assert_noop!(Tokens::deposit(KSM, &BOB, 50), TokenError::CannotCreate);
+
+			assert_ok!(Tokens::deposit(DOT, &BOB, 100));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, KSM, 50));
+			assert_eq!(System::providers(&BOB), 1);
+			assert_eq!(System::consumers(&BOB), 1);
+			assert!(InsufficientAccounts::<Runtime>::contains_key(BOB, KSM));
+
+			// the consumer reference doesn't prevent the token account from being reaped
+			assert_ok!(Tokens::transfer_all(Some(BOB).into(), ALICE, KSM, false));
+			assert_eq!(System::providers(&BOB), 1);
+			assert_eq!(System::consumers(&BOB), 0);
+			assert!(!InsufficientAccounts::<Runtime>::contains_key(BOB, KSM));
+			assert!(!Accounts::<Runtime>::contains_key(BOB, KSM));
+		});
+}
+
+#[test]
+fn insufficient_deposit_to_account_without_provider_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(System::providers(&BOB), 0);
+
+		// the token account is rejected instead of providing for BOB itself
+		assert_noop!(
+			Tokens::try_mutate_account(&BOB, KSM, |account, _| -> DispatchResult {
+				account.free = 50;
+				Ok(())
+			}),
+			TokenError::CannotCreate
+		);
+		assert_eq!(System::providers(&BOB), 0);
+		assert_eq!(System::consumers(&BOB), 0);
+		assert!(!Accounts::<Runtime>::contains_key(BOB, KSM));
+		assert!(!InsufficientAccounts::<Runtime>::contains_key(BOB, KSM));
+	});
+}
+
+#[test]
+fn blocked_currency_should_not_create_accounts() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (ALICE, ACA, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::transfer(Some(ALICE).into(), BOB, ACA, 50), TokenError::Blocked);
+			assert_noop!(Tokens::deposit(ACA, &BOB, 50), TokenError::Blocked);
+			assert_noop!(
+				Tokens::set_balance(RawOrigin::Root.into(), BOB, ACA, 50, 0),
+				TokenError::Blocked
+			);
+
+			// existing token accounts are not affected
+			assert_ok!(Tokens::deposit(ACA, &ALICE, 50));
+			assert_eq!(Tokens::free_balance(ACA, &ALICE), 150);
+		});
+}
+
+#[test]
+fn freeze_currency_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::freeze_currency(Some(ALICE).into(), DOT), BadOrigin);
+
+			assert_ok!(Tokens::freeze_currency(RawOrigin::Root.into(), DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::CurrencyFrozen { currency_id: DOT }));
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50),
+				Error::<Runtime>::CurrencyFrozen
+			);
+			assert_noop!(Tokens::deposit(DOT, &ALICE, 50), Error::<Runtime>::CurrencyFrozen);
+			assert_noop!(Tokens::withdraw(DOT, &ALICE, 50), Error::<Runtime>::CurrencyFrozen);
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::mint_into(DOT, &ALICE, 50),
+				Error::<Runtime>::CurrencyFrozen
+			);
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::burn_from(DOT, &ALICE, 50, Precision::Exact, Fortitude::Polite),
+				Error::<Runtime>::CurrencyFrozen
+			);
+
+			// other currencies are not affected
+			assert_ok!(Tokens::deposit(BTC, &ALICE, 50));
+
+			assert_ok!(Tokens::thaw_currency(RawOrigin::Root.into(), DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::CurrencyThawed { currency_id: DOT }));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50));
+		});
+}
+
+#[test]
+fn freeze_account_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100), (ALICE, BTC, 100)])
+		.build()
+		.execute_with(|| {
+			assert_noop!(Tokens::freeze_account(Some(ALICE).into(), ALICE, DOT), BadOrigin);
+
+			assert_ok!(Tokens::freeze_account(RawOrigin::Root.into(), ALICE, DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::AccountFrozen {
+				currency_id: DOT,
+				who: ALICE,
+			}));
+
+			assert_noop!(
+				Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(
+				Tokens::transfer(Some(BOB).into(), ALICE, DOT, 50),
+				Error::<Runtime>::AccountFrozen
+			);
+			assert_noop!(Tokens::withdraw(DOT, &ALICE, 50), Error::<Runtime>::AccountFrozen);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(DOT, &ALICE, 50, Provenance::Extant),
+				DepositConsequence::Blocked
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_withdraw(DOT, &ALICE, 50),
+				WithdrawConsequence::Frozen
+			);
+
+			// other accounts and currencies are not affected
+			assert_ok!(Tokens::transfer(Some(BOB).into(), CHARLIE, DOT, 50));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, BTC, 50));
+
+			assert_ok!(Tokens::thaw_account(RawOrigin::Root.into(), ALICE, DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::AccountThawed {
+				currency_id: DOT,
+				who: ALICE,
+			}));
+			assert_ok!(Tokens::transfer(Some(ALICE).into(), BOB, DOT, 50));
+		});
+}
+
 #[test]
 fn exceeding_max_reserves_should_fail() {
 	ExtBuilder::default()
@@ -1269,3 +2013,137 @@ fn post_transfer_can_use_new_balance() {
 			));
 		});
 }
+
+#[test]
+fn reserve_hooks_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			let initial_prehook_calls = PreReserve::<Runtime>::calls();
+			let initial_posthook_calls = PostReserve::<Runtime>::calls();
+			let initial_unreserve_calls = OnUnreserveHook::<Runtime>::calls();
+
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 0));
+			assert_eq!(PreReserve::<Runtime>::calls(), initial_prehook_calls);
+			assert_eq!(PostReserve::<Runtime>::calls(), initial_posthook_calls);
+
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+			assert_ok!(Tokens::reserve_named(&RID_1, DOT, &ALICE, 10));
+			assert_eq!(PreReserve::<Runtime>::calls(), initial_prehook_calls + 2);
+			assert_eq!(PostReserve::<Runtime>::calls(), initial_posthook_calls + 2);
+
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 5), 0);
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 5), 0);
+			assert_eq!(OnUnreserveHook::<Runtime>::calls(), initial_unreserve_calls + 2);
+
+			assert_eq!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 5, BalanceStatus::Reserved),
+				Ok(0)
+			);
+			assert_eq!(PreReserve::<Runtime>::calls(), initial_prehook_calls + 3);
+			assert_eq!(PostReserve::<Runtime>::calls(), initial_posthook_calls + 3);
+			assert_eq!(OnUnreserveHook::<Runtime>::calls(), initial_unreserve_calls + 3);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 25);
+			assert_eq!(Tokens::reserved_balance(DOT, &BOB), 5);
+
+			// the hooks can veto moves into and out of reserves
+			ReserveHooksVeto::set(true);
+			assert_noop!(Tokens::reserve(DOT, &ALICE, 10), DispatchError::Other("reserve vetoed"));
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 10), 10);
+			assert_eq!(Tokens::unreserve_named(&RID_1, DOT, &ALICE, 5), 5);
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 5, BalanceStatus::Free),
+				DispatchError::Other("unreserve vetoed")
+			);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 25);
+			assert_eq!(Tokens::reserved_balance_named(&RID_1, DOT, &ALICE), 5);
+
+			// a veto of the post hook reverts the balance changes
+			ReserveHooksVeto::set(false);
+			PostReserveVeto::set(true);
+			assert_noop!(
+				Tokens::reserve(DOT, &ALICE, 10),
+				DispatchError::Other("reserve vetoed after")
+			);
+			assert_noop!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 5, BalanceStatus::Reserved),
+				DispatchError::Other("reserve vetoed after")
+			);
+		});
+}
+
+#[test]
+fn on_unreserve_hook_should_get_actual_amount() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+			let initial_unreserve_calls = OnUnreserveHook::<Runtime>::calls();
+
+			// only the reserved balance is unreserved
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 50), 20);
+			assert_eq!(OnUnreserveHook::<Runtime>::last_amount(), 30);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 0);
+
+			// nothing to unreserve
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 10), 10);
+			assert_eq!(OnUnreserveHook::<Runtime>::calls(), initial_unreserve_calls + 1);
+
+			// a veto unreserves nothing, and the whole value is returned
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 30));
+			ReserveHooksVeto::set(true);
+			System::reset_events();
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 10), 10);
+			assert_eq!(OnUnreserveHook::<Runtime>::last_amount(), 10);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 70);
+			assert!(System::events().is_empty());
+
+			// slashes are excluded from the reserve hooks
+			assert_eq!(Tokens::slash_reserved(DOT, &ALICE, 10), 0);
+			assert_eq!(OnUnreserveHook::<Runtime>::calls(), initial_unreserve_calls + 2);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 20);
+		});
+}
+
+#[test]
+fn migrate_locks_to_v1_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			StorageVersion::new(0).put::<Tokens>();
+			let old_locks = vec![
+				migrations::v1::OldBalanceLock {
+					id: ID_1,
+					amount: 10u64,
+				},
+				migrations::v1::OldBalanceLock {
+					id: ID_2,
+					amount: 20u64,
+				},
+			];
+			frame_support::storage::unhashed::put(&Locks::<Runtime>::hashed_key_for(ALICE, DOT), &old_locks);
+
+			migrations::Migration::<Runtime>::on_runtime_upgrade();
+
+			assert_eq!(StorageVersion::get::<Tokens>(), 1);
+			assert_eq!(
+				Tokens::locks(ALICE, DOT).into_inner(),
+				vec![
+					BalanceLock {
+						id: ID_1,
+						amount: 10,
+						reasons: WithdrawReasons::all().into(),
+					},
+					BalanceLock {
+						id: ID_2,
+						amount: 20,
+						reasons: WithdrawReasons::all().into(),
+					}
+				]
+			);
+		});
+}
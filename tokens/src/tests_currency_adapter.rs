@@ -190,7 +190,7 @@ fn currency_adapter_deducting_balance_should_work() {
 
 #[test]
 fn currency_adapter_refunding_balance_should_work() {
-	ExtBuilder::default().build().execute_with(|| {
+	ExtBuilder::default().skip_try_state().build().execute_with(|| {
 		let _ = TreasuryCurrencyAdapter::deposit_creating(&TREASURY_ACCOUNT, 42);
 		Tokens::set_reserved_balance(DOT, &TREASURY_ACCOUNT, 69);
 		TreasuryCurrencyAdapter::unreserve(&TREASURY_ACCOUNT, 69);
@@ -312,13 +312,15 @@ fn currency_adapter_combination_locking_should_work() {
 		.balances(vec![(TREASURY_ACCOUNT, DOT, 100)])
 		.build()
 		.execute_with(|| {
-			// withdrawReasons not work
+			// a lock without any withdraw reasons is not in effect
 			TreasuryCurrencyAdapter::set_lock(ID_1, &TREASURY_ACCOUNT, u64::max_value(), WithdrawReasons::empty());
 			TreasuryCurrencyAdapter::set_lock(ID_2, &TREASURY_ACCOUNT, 0, WithdrawReasons::all());
-			assert_noop!(
-				TreasuryCurrencyAdapter::transfer(&TREASURY_ACCOUNT, &ALICE, 2, ExistenceRequirement::AllowDeath),
-				Error::<Runtime>::LiquidityRestrictions
-			);
+			assert_ok!(TreasuryCurrencyAdapter::transfer(
+				&TREASURY_ACCOUNT,
+				&ALICE,
+				2,
+				ExistenceRequirement::AllowDeath
+			));
 		});
 }
 
@@ -395,6 +397,56 @@ fn currency_adapter_lock_reasons_extension_should_work() {
 		});
 }
 
+#[test]
+fn currency_adapter_lock_should_only_apply_to_its_reasons() {
+	ExtBuilder::default()
+		.balances(vec![(TREASURY_ACCOUNT, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			TreasuryCurrencyAdapter::set_lock(ID_1, &TREASURY_ACCOUNT, 90, WithdrawReasons::TRANSFER);
+			assert_noop!(
+				TreasuryCurrencyAdapter::transfer(&TREASURY_ACCOUNT, &ALICE, 11, ExistenceRequirement::AllowDeath),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_noop!(
+				TreasuryCurrencyAdapter::ensure_can_withdraw(&TREASURY_ACCOUNT, 11, WithdrawReasons::TRANSFER, 89),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_eq!(
+				<MockCurrencyAdapter as fungible::Inspect<_>>::reducible_balance(
+					&TREASURY_ACCOUNT,
+					Preservation::Expendable,
+					Fortitude::Polite
+				),
+				10
+			);
+
+			// fees and reserves are not restricted by a transfer lock
+			assert_ok!(TreasuryCurrencyAdapter::ensure_can_withdraw(
+				&TREASURY_ACCOUNT,
+				50,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				50
+			));
+			assert_ok!(TreasuryCurrencyAdapter::withdraw(
+				&TREASURY_ACCOUNT,
+				20,
+				WithdrawReasons::TRANSACTION_PAYMENT,
+				ExistenceRequirement::KeepAlive
+			));
+			assert_eq!(TreasuryCurrencyAdapter::free_balance(&TREASURY_ACCOUNT), 80);
+			assert!(TreasuryCurrencyAdapter::can_reserve(&TREASURY_ACCOUNT, 30));
+			assert_ok!(TreasuryCurrencyAdapter::reserve(&TREASURY_ACCOUNT, 30));
+			assert_eq!(TreasuryCurrencyAdapter::free_balance(&TREASURY_ACCOUNT), 50);
+
+			// the multi currency api is restricted by every lock
+			assert_noop!(
+				Tokens::ensure_can_withdraw(DOT, &TREASURY_ACCOUNT, 1),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+		});
+}
+
 #[test]
 fn currency_adapter_deposit_creating_should_work() {
 	ExtBuilder::default()
@@ -414,6 +466,39 @@ fn currency_adapter_deposit_creating_should_work() {
 		});
 }
 
+#[test]
+fn currency_adapter_issuance_cap_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			DotIssuanceCap::set(Some(150));
+
+			let imbalance = TreasuryCurrencyAdapter::issue(51);
+			assert_eq!(imbalance.peek(), 0);
+			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 100);
+			let imbalance = TreasuryCurrencyAdapter::issue(50);
+			assert_eq!(imbalance.peek(), 50);
+			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 150);
+			drop(imbalance);
+			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 100);
+
+			let imbalance = TreasuryCurrencyAdapter::deposit_creating(&TREASURY_ACCOUNT, 51);
+			assert_eq!(imbalance.peek(), 0);
+			assert_eq!(TreasuryCurrencyAdapter::total_balance(&TREASURY_ACCOUNT), 0);
+			assert_noop!(
+				TreasuryCurrencyAdapter::deposit_into_existing(&ALICE, 51).map(drop),
+				Error::<Runtime>::IssuanceCapExceeded
+			);
+
+			let _ = TreasuryCurrencyAdapter::deposit_creating(&TREASURY_ACCOUNT, 50);
+			assert_eq!(TreasuryCurrencyAdapter::total_issuance(), 150);
+			assert_eq!(TreasuryCurrencyAdapter::total_balance(&TREASURY_ACCOUNT), 50);
+
+			DotIssuanceCap::set(None);
+		});
+}
+
 #[test]
 fn currency_adapter_deposit_into_existing_should_work() {
 	ExtBuilder::default()
@@ -581,6 +666,39 @@ fn exceeding_max_locks_should_fail() {
 		});
 }
 
+#[test]
+fn currency_adapter_freezing_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<MockCurrencyAdapter as fungible::MutateFreeze<_>>::set_freeze(
+				&TestId::Foo,
+				&ALICE,
+				91
+			));
+			assert_eq!(
+				<MockCurrencyAdapter as fungible::InspectFreeze<_>>::balance_frozen(&TestId::Foo, &ALICE),
+				91
+			);
+			assert_eq!(Tokens::freezes(ALICE, DOT).len(), 1);
+			assert_noop!(
+				MockCurrencyAdapter::transfer(&ALICE, &BOB, 10, ExistenceRequirement::AllowDeath),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+			assert_ok!(<MockCurrencyAdapter as fungible::MutateFreeze<_>>::thaw(
+				&TestId::Foo,
+				&ALICE
+			));
+			assert_ok!(MockCurrencyAdapter::transfer(
+				&ALICE,
+				&BOB,
+				10,
+				ExistenceRequirement::AllowDeath
+			));
+		});
+}
+
 #[test]
 fn currency_adapter_slashing_named_reserved_balance_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
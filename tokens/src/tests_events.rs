@@ -6,7 +6,7 @@ use super::*;
 use frame_support::assert_ok;
 use mock::*;
 
-const REASON: &() = &();
+const REASON: &TestId = &TestId::Foo;
 
 fn events() -> Vec<RuntimeEvent> {
 	let evt = System::events().into_iter().map(|evt| evt.event).collect::<Vec<_>>();
@@ -26,6 +26,7 @@ fn pallet_multicurrency_deposit_events() {
 				from: ALICE,
 				to: BOB,
 				amount: 10,
+				fee: 0,
 			}));
 
 			assert_ok!(<Tokens as MultiCurrency<AccountId>>::deposit(DOT, &ALICE, 10));
@@ -203,6 +204,7 @@ fn pallet_fungibles_transfer_deposit_events() {
 				from: ALICE,
 				to: BOB,
 				amount: 50,
+				fee: 0,
 			}));
 		});
 }
@@ -211,6 +213,7 @@ fn pallet_fungibles_transfer_deposit_events() {
 fn pallet_fungibles_unbalanced_deposit_events() {
 	ExtBuilder::default()
 		.balances(vec![(ALICE, DOT, 100)])
+		.skip_try_state()
 		.build()
 		.execute_with(|| {
 			assert_ok!(<Tokens as MultiReservableCurrency<AccountId>>::reserve(DOT, &ALICE, 50));
@@ -307,6 +310,7 @@ fn currency_adapter_pallet_currency_deposit_events() {
 				from: ALICE,
 				to: BOB,
 				amount: 50,
+				fee: 0,
 			}));
 
 			assert_ok!(<Tokens as MultiReservableCurrency<AccountId>>::reserve(DOT, &BOB, 50));
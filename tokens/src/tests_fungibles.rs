@@ -7,7 +7,7 @@ use frame_support::{assert_noop, assert_ok};
 use mock::*;
 use sp_runtime::{ArithmeticError, TokenError};
 
-const REASON: &() = &();
+const REASON: &TestId = &TestId::Foo;
 
 #[test]
 fn fungibles_inspect_trait_should_work() {
@@ -37,6 +37,74 @@ fn fungibles_inspect_trait_should_work() {
 		});
 }
 
+#[test]
+fn fungibles_can_deposit_should_respect_sufficiency() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(DOT, &BOB, 10, Provenance::Minted),
+				DepositConsequence::Success
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(KSM, &ALICE, 10, Provenance::Minted),
+				DepositConsequence::Success
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(KSM, &BOB, 10, Provenance::Minted),
+				DepositConsequence::CannotCreate
+			);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::can_deposit(ACA, &ALICE, 10, Provenance::Minted),
+				DepositConsequence::Blocked
+			);
+
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::mint_into(KSM, &BOB, 10),
+				TokenError::CannotCreate
+			);
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::mint_into(ACA, &ALICE, 10),
+				TokenError::Blocked
+			);
+			assert_ok!(<Tokens as fungibles::Mutate<_>>::mint_into(KSM, &ALICE, 10));
+		});
+}
+
+#[test]
+fn fungibles_approvals_trait_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				<Tokens as fungibles::approvals::Inspect<_>>::allowance(DOT, &ALICE, &BOB),
+				0
+			);
+			assert_ok!(<Tokens as fungibles::approvals::Mutate<_>>::approve(
+				DOT, &ALICE, &BOB, 10
+			));
+			assert_eq!(
+				<Tokens as fungibles::approvals::Inspect<_>>::allowance(DOT, &ALICE, &BOB),
+				10
+			);
+
+			assert_noop!(
+				<Tokens as fungibles::approvals::Mutate<_>>::transfer_from(DOT, &ALICE, &BOB, &CHARLIE, 11),
+				Error::<Runtime>::Unapproved
+			);
+			assert_ok!(<Tokens as fungibles::approvals::Mutate<_>>::transfer_from(
+				DOT, &ALICE, &BOB, &CHARLIE, 10
+			));
+			assert_eq!(
+				<Tokens as fungibles::approvals::Inspect<_>>::allowance(DOT, &ALICE, &BOB),
+				0
+			);
+			assert_eq!(<Tokens as fungibles::Inspect<_>>::balance(DOT, &CHARLIE), 10);
+		});
+}
+
 #[test]
 fn fungibles_mutate_trait_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -72,6 +140,7 @@ fn fungibles_transfer_trait_should_work() {
 fn fungibles_unbalanced_trait_should_work() {
 	ExtBuilder::default()
 		.balances(vec![(ALICE, DOT, 100)])
+		.skip_try_state()
 		.build()
 		.execute_with(|| {
 			// set_balance
@@ -552,6 +621,296 @@ fn fungibles_mutate_hold_trait_should_work() {
 		});
 }
 
+#[test]
+fn plain_reserve_operations_should_not_touch_holds() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::hold(DOT, REASON, &ALICE, 30));
+			assert_ok!(Tokens::reserve(DOT, &ALICE, 20));
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 50);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::total_balance_on_hold(DOT, &ALICE),
+				30
+			);
+
+			// only the reserved balance not on hold is unreserved
+			assert_eq!(Tokens::unreserve(DOT, &ALICE, 50), 30);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, REASON, &ALICE),
+				30
+			);
+
+			// nor slashed
+			assert_eq!(Tokens::slash_reserved(DOT, &ALICE, 10), 10);
+			assert_eq!(
+				Tokens::repatriate_reserved(DOT, &ALICE, &BOB, 10, BalanceStatus::Free),
+				Ok(10)
+			);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+
+			// slashing prefers free balance and keeps the balance on hold
+			assert_eq!(Tokens::slash(DOT, &ALICE, 80), 10);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 0);
+			assert_eq!(Tokens::reserved_balance(DOT, &ALICE), 30);
+
+			// the balance on hold is still moved by the hold operations
+			assert_eq!(
+				<Tokens as fungibles::MutateHold<_>>::transfer_on_hold(
+					DOT,
+					REASON,
+					&ALICE,
+					&BOB,
+					30,
+					Precision::Exact,
+					Restriction::Free,
+					Fortitude::Polite
+				),
+				Ok(30)
+			);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 130);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, REASON, &ALICE),
+				0
+			);
+		});
+}
+
+#[test]
+fn fungibles_holds_are_tracked_per_reason() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100), (BOB, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::hold(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				30
+			));
+			assert_ok!(<Tokens as fungibles::MutateHold<_>>::hold(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				20
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				30
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Bar, &ALICE),
+				20
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::total_balance_on_hold(DOT, &ALICE),
+				50
+			);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).reserved, 50);
+
+			// `MaxHolds` is 2, a third reason is not available
+			assert!(!<Tokens as fungibles::InspectHold<_>>::hold_available(
+				DOT,
+				&TestId::Baz,
+				&ALICE
+			));
+			assert!(<Tokens as fungibles::InspectHold<_>>::hold_available(
+				DOT,
+				&TestId::Foo,
+				&ALICE
+			));
+			assert!(!<Tokens as fungibles::InspectHold<_>>::can_hold(
+				DOT,
+				&TestId::Baz,
+				&ALICE,
+				10
+			));
+			assert_noop!(
+				<Tokens as fungibles::MutateHold<_>>::hold(DOT, &TestId::Baz, &ALICE, 10),
+				Error::<Runtime>::TooManyHolds
+			);
+
+			// release can not take funds held under another reason
+			assert_noop!(
+				<Tokens as fungibles::MutateHold<_>>::release(DOT, &TestId::Bar, &ALICE, 30, Precision::Exact),
+				Error::<Runtime>::BalanceTooLow
+			);
+			assert_eq!(
+				<Tokens as fungibles::MutateHold<_>>::release(DOT, &TestId::Bar, &ALICE, 30, Precision::BestEffort),
+				Ok(20)
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Bar, &ALICE),
+				0
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				30
+			);
+			assert_eq!(Tokens::holds(&ALICE, DOT).len(), 1);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).reserved, 30);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).free, 70);
+
+			// transfer on hold keeps the reason on the beneficiary
+			assert_eq!(
+				<Tokens as fungibles::MutateHold<_>>::transfer_on_hold(
+					DOT,
+					&TestId::Foo,
+					&ALICE,
+					&BOB,
+					10,
+					Precision::Exact,
+					Restriction::OnHold,
+					Fortitude::Polite
+				),
+				Ok(10)
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				20
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &BOB),
+				10
+			);
+			assert_noop!(
+				<Tokens as fungibles::MutateHold<_>>::transfer_on_hold(
+					DOT,
+					&TestId::Bar,
+					&ALICE,
+					&BOB,
+					10,
+					Precision::Exact,
+					Restriction::Free,
+					Fortitude::Polite
+				),
+				Error::<Runtime>::BalanceTooLow
+			);
+
+			// set balance on hold only adjusts the given reason
+			assert_ok!(<Tokens as fungibles::UnbalancedHold<_>>::set_balance_on_hold(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				15
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Bar, &ALICE),
+				15
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectHold<_>>::balance_on_hold(DOT, &TestId::Foo, &ALICE),
+				20
+			);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).reserved, 35);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).free, 55);
+
+			assert_ok!(<Tokens as fungibles::UnbalancedHold<_>>::set_balance_on_hold(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				0
+			));
+			assert_eq!(Tokens::holds(&ALICE, DOT).len(), 1);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).reserved, 20);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).free, 70);
+		});
+}
+
+#[test]
+fn fungibles_freeze_trait_should_work() {
+	ExtBuilder::default()
+		.balances(vec![(ALICE, DOT, 100)])
+		.build()
+		.execute_with(|| {
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				30
+			));
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::extend_freeze(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				50
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &TestId::Foo, &ALICE),
+				30
+			);
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &TestId::Bar, &ALICE),
+				50
+			);
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 50);
+			assert_eq!(
+				<Tokens as fungibles::Inspect<_>>::reducible_balance(
+					DOT,
+					&ALICE,
+					Preservation::Expendable,
+					Fortitude::Polite
+				),
+				50
+			);
+			assert_noop!(
+				<Tokens as fungibles::Mutate<_>>::transfer(DOT, &ALICE, &BOB, 51, Preservation::Expendable),
+				Error::<Runtime>::LiquidityRestrictions
+			);
+
+			// `MaxFreezes` is 2
+			assert!(!<Tokens as fungibles::InspectFreeze<_>>::can_freeze(
+				DOT,
+				&TestId::Baz,
+				&ALICE
+			));
+			assert_noop!(
+				<Tokens as fungibles::MutateFreeze<_>>::set_freeze(DOT, &TestId::Baz, &ALICE, 10),
+				Error::<Runtime>::TooManyFreezes
+			);
+
+			// extend never reduces a freeze
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::extend_freeze(
+				DOT,
+				&TestId::Bar,
+				&ALICE,
+				20
+			));
+			assert_eq!(
+				<Tokens as fungibles::InspectFreeze<_>>::balance_frozen(DOT, &TestId::Bar, &ALICE),
+				50
+			);
+
+			// freezes and locks overlap
+			assert_ok!(Tokens::set_lock(ID_1, DOT, &ALICE, 40));
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::thaw(DOT, &TestId::Bar, &ALICE));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 40);
+			assert_ok!(Tokens::remove_lock(ID_1, DOT, &ALICE));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 30);
+
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::set_freeze(
+				DOT,
+				&TestId::Foo,
+				&ALICE,
+				0
+			));
+			assert_eq!(Tokens::accounts(&ALICE, DOT).frozen, 0);
+			assert!(!Freezes::<Runtime>::contains_key(ALICE, DOT));
+			System::assert_last_event(RuntimeEvent::Tokens(crate::Event::FreezeRemoved {
+				reason: TestId::Foo,
+				currency_id: DOT,
+				who: ALICE,
+			}));
+
+			// no event if there is no freeze to remove
+			System::reset_events();
+			assert_ok!(<Tokens as fungibles::MutateFreeze<_>>::thaw(DOT, &TestId::Foo, &ALICE));
+			assert!(System::events().is_empty());
+		});
+}
+
 #[test]
 fn fungibles_inspect_convert_should_work() {
 	pub struct ConvertBalanceTest;
@@ -1,6 +1,14 @@
-//! Autogenerated weights for orml_tokens
+//! Weights for orml_tokens
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! The weights of `transfer`, `transfer_all`, `transfer_keep_alive`,
+//! `force_transfer` and `set_balance` were generated with the benchmark CLI
+//! below. The other weights are hand-estimated from their storage accesses,
+//! runtimes should benchmark them before relying on them. `transfer_batch`,
+//! `approve_transfer`, `cancel_approval` and `transfer_approved` are
+//! benchmarked in `benchmarking.rs`, and their weights are to be regenerated
+//! with the benchmark CLI.
+//!
+//! SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2021-09-14, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
 
@@ -34,6 +42,19 @@ pub trait WeightInfo {
 	fn transfer_keep_alive() -> Weight;
 	fn force_transfer() -> Weight;
 	fn set_balance() -> Weight;
+	fn freeze_currency() -> Weight;
+	fn thaw_currency() -> Weight;
+	fn freeze_account() -> Weight;
+	fn thaw_account() -> Weight;
+	fn sweep_dust_account() -> Weight;
+	fn transfer_batch(n: u32, ) -> Weight;
+	fn take_snapshot() -> Weight;
+	fn approve_transfer() -> Weight;
+	fn cancel_approval() -> Weight;
+	fn transfer_approved() -> Weight;
+	fn migrate_account(c: u32, ) -> Weight;
+	fn record_snapshot_balance() -> Weight;
+	fn prune_snapshot(a: u32, ) -> Weight;
 }
 
 /// Default weights.
@@ -63,4 +84,71 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	// The weights below are hand-estimated, not benchmarked.
+	fn freeze_currency() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn thaw_currency() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn freeze_account() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn thaw_account() -> Weight {
+		Weight::from_parts(13_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn sweep_dust_account() -> Weight {
+		Weight::from_parts(69_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn transfer_batch(n: u32, ) -> Weight {
+		Weight::from_parts(11_000_000, 0)
+			.saturating_add(Weight::from_parts(66_000_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((4 as u64).saturating_mul(n as u64)))
+	}
+	fn take_snapshot() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn approve_transfer() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn cancel_approval() -> Weight {
+		Weight::from_parts(34_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn transfer_approved() -> Weight {
+		Weight::from_parts(92_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	fn migrate_account(c: u32, ) -> Weight {
+		Weight::from_parts(14_000_000, 0)
+			.saturating_add(Weight::from_parts(118_000_000, 0).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().reads((14 as u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes((12 as u64).saturating_mul(c as u64)))
+	}
+	fn record_snapshot_balance() -> Weight {
+		Weight::from_parts(4_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn prune_snapshot(a: u32, ) -> Weight {
+		Weight::from_parts(16_000_000, 0)
+			.saturating_add(Weight::from_parts(2_000_000, 0).saturating_mul(a as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(a as u64)))
+	}
 }
@@ -1,9 +1,13 @@
 pub use num_traits::{
 	Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedShl, CheckedShr, CheckedSub, One, Signed, Zero,
 };
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{FixedPointNumber, FixedPointOperand, RuntimeDebug};
 use sp_std::{
 	self,
-	ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Shl, Shr, Sub, SubAssign},
+	cmp::Ordering,
+	ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Shl, Shr, Sub, SubAssign},
 };
 
 /// A meta trait for arithmetic.
@@ -88,3 +92,327 @@ impl<
 	> SimpleArithmetic for T
 {
 }
+
+/// A signed balance, the sign is held next to the unsigned `Balance`
+/// magnitude so that the whole range of `Balance` can be negated.
+///
+/// `Negative(0)` is equal to `Positive(0)`, the constructors always return
+/// the latter.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum SignedBalance<Balance> {
+	/// Zero or above.
+	Positive(Balance),
+	/// Below zero.
+	Negative(Balance),
+}
+
+impl<Balance: SimpleArithmetic + Copy> SignedBalance<Balance> {
+	/// A signed balance of `balance`.
+	pub fn positive(balance: Balance) -> Self {
+		Self::Positive(balance)
+	}
+
+	/// A signed balance of minus `balance`.
+	pub fn negative(balance: Balance) -> Self {
+		if balance.is_zero() {
+			Self::Positive(balance)
+		} else {
+			Self::Negative(balance)
+		}
+	}
+
+	/// The absolute value.
+	pub fn magnitude(&self) -> Balance {
+		match *self {
+			Self::Positive(balance) | Self::Negative(balance) => balance,
+		}
+	}
+
+	pub fn is_positive(&self) -> bool {
+		matches!(*self, Self::Positive(balance) if !balance.is_zero())
+	}
+
+	pub fn is_negative(&self) -> bool {
+		matches!(*self, Self::Negative(balance) if !balance.is_zero())
+	}
+
+	/// Convert a signed `Amount`, `None` if its magnitude doesn't fit in
+	/// `Balance`.
+	pub fn try_from_amount<Amount>(amount: Amount) -> Option<Self>
+	where
+		Amount: Signed + SimpleArithmetic + TryInto<Balance> + Copy,
+	{
+		if amount.is_negative() {
+			// `-(amount + 1)` can't overflow, even for `Amount::min_value()`
+			let magnitude: Balance = (-(amount + Amount::one())).try_into().ok()?;
+			magnitude.checked_add(&Balance::one()).map(Self::negative)
+		} else {
+			amount.try_into().ok().map(Self::positive)
+		}
+	}
+
+	/// Convert to a signed `Amount`, `None` if it doesn't fit in `Amount`.
+	pub fn try_into_amount<Amount>(self) -> Option<Amount>
+	where
+		Amount: Signed + SimpleArithmetic + TryFrom<Balance> + Copy,
+	{
+		match self {
+			Self::Positive(balance) => Amount::try_from(balance).ok(),
+			Self::Negative(balance) if balance.is_zero() => Some(Amount::zero()),
+			// `-(balance - 1) - 1` can't overflow, even for `Amount::min_value()`
+			Self::Negative(balance) => {
+				let amount = Amount::try_from(balance - Balance::one()).ok()?;
+				Some(-amount - Amount::one())
+			}
+		}
+	}
+
+	pub fn checked_add(&self, other: &Self) -> Option<Self> {
+		match (*self, *other) {
+			(Self::Positive(a), Self::Positive(b)) => a.checked_add(&b).map(Self::positive),
+			(Self::Negative(a), Self::Negative(b)) => a.checked_add(&b).map(Self::negative),
+			(Self::Positive(a), Self::Negative(b)) | (Self::Negative(b), Self::Positive(a)) => {
+				Some(Self::difference(a, b))
+			}
+		}
+	}
+
+	pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+		self.checked_add(&-*other)
+	}
+
+	pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+		let magnitude = self.magnitude().checked_mul(&other.magnitude())?;
+		Some(Self::with_sign(magnitude, self.is_negative() != other.is_negative()))
+	}
+
+	/// `None` if `other` is zero. Rounds toward zero.
+	pub fn checked_div(&self, other: &Self) -> Option<Self> {
+		let magnitude = self.magnitude().checked_div(&other.magnitude())?;
+		Some(Self::with_sign(magnitude, self.is_negative() != other.is_negative()))
+	}
+
+	/// Saturates to plus or minus `Balance::max_value()`.
+	pub fn saturating_add(&self, other: &Self) -> Self {
+		self.checked_add(other)
+			.unwrap_or_else(|| Self::with_sign(Balance::max_value(), self.is_negative()))
+	}
+
+	/// Saturates to plus or minus `Balance::max_value()`.
+	pub fn saturating_sub(&self, other: &Self) -> Self {
+		self.saturating_add(&-*other)
+	}
+
+	/// Saturates to plus or minus `Balance::max_value()`.
+	pub fn saturating_mul(&self, other: &Self) -> Self {
+		self.checked_mul(other)
+			.unwrap_or_else(|| Self::with_sign(Balance::max_value(), self.is_negative() != other.is_negative()))
+	}
+
+	/// Multiply by the fixed point number `n`, rounding toward zero. `None`
+	/// on overflow.
+	pub fn checked_mul_fixed<N: FixedPointNumber>(&self, n: N) -> Option<Self>
+	where
+		Balance: FixedPointOperand,
+	{
+		let magnitude = n.saturating_abs().checked_mul_int(self.magnitude())?;
+		Some(Self::with_sign(magnitude, self.is_negative() != n.is_negative()))
+	}
+
+	/// Multiply by the fixed point number `n`, rounding toward zero.
+	/// Saturates to plus or minus `Balance::max_value()`.
+	pub fn saturating_mul_fixed<N: FixedPointNumber>(&self, n: N) -> Self
+	where
+		Balance: FixedPointOperand,
+	{
+		self.checked_mul_fixed(n)
+			.unwrap_or_else(|| Self::with_sign(Balance::max_value(), self.is_negative() != n.is_negative()))
+	}
+
+	fn with_sign(magnitude: Balance, negative: bool) -> Self {
+		if negative {
+			Self::negative(magnitude)
+		} else {
+			Self::positive(magnitude)
+		}
+	}
+
+	/// `a - b`, which can't overflow.
+	fn difference(a: Balance, b: Balance) -> Self {
+		if a >= b {
+			Self::positive(a - b)
+		} else {
+			Self::negative(b - a)
+		}
+	}
+}
+
+impl<Balance: SimpleArithmetic + Copy> Default for SignedBalance<Balance> {
+	fn default() -> Self {
+		Self::Positive(Balance::zero())
+	}
+}
+
+impl<Balance: SimpleArithmetic + Copy> Neg for SignedBalance<Balance> {
+	type Output = Self;
+
+	fn neg(self) -> Self {
+		match self {
+			Self::Positive(balance) => Self::negative(balance),
+			Self::Negative(balance) => Self::positive(balance),
+		}
+	}
+}
+
+impl<Balance: SimpleArithmetic + Copy> PartialEq for SignedBalance<Balance> {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl<Balance: SimpleArithmetic + Copy> Eq for SignedBalance<Balance> {}
+
+impl<Balance: SimpleArithmetic + Copy> PartialOrd for SignedBalance<Balance> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<Balance: SimpleArithmetic + Copy> Ord for SignedBalance<Balance> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		match (self.is_negative(), other.is_negative()) {
+			(false, false) => self.magnitude().cmp(&other.magnitude()),
+			(true, true) => other.magnitude().cmp(&self.magnitude()),
+			(false, true) => Ordering::Greater,
+			(true, false) => Ordering::Less,
+		}
+	}
+}
+
+macro_rules! impl_signed_balance_from_balance {
+	($($balance:ty),*) => {
+		$(
+			impl From<$balance> for SignedBalance<$balance> {
+				fn from(balance: $balance) -> Self {
+					Self::positive(balance)
+				}
+			}
+		)*
+	};
+}
+
+macro_rules! impl_signed_balance_amount {
+	($($amount:ty),*) => {
+		$(
+			impl<Balance> TryFrom<$amount> for SignedBalance<Balance>
+			where
+				Balance: SimpleArithmetic + Copy,
+				$amount: TryInto<Balance>,
+			{
+				type Error = ();
+
+				fn try_from(amount: $amount) -> Result<Self, ()> {
+					Self::try_from_amount(amount).ok_or(())
+				}
+			}
+
+			impl<Balance> TryFrom<SignedBalance<Balance>> for $amount
+			where
+				Balance: SimpleArithmetic + Copy,
+				$amount: TryFrom<Balance>,
+			{
+				type Error = ();
+
+				fn try_from(balance: SignedBalance<Balance>) -> Result<Self, ()> {
+					balance.try_into_amount().ok_or(())
+				}
+			}
+		)*
+	};
+}
+
+impl_signed_balance_from_balance!(u32, u64, u128);
+impl_signed_balance_amount!(i64, i128);
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::{FixedI128, FixedU128};
+
+	type Balance = u64;
+
+	#[test]
+	fn signed_balance_amount_conversion_should_work() {
+		assert_eq!(
+			SignedBalance::<Balance>::try_from(10i64),
+			Ok(SignedBalance::Positive(10))
+		);
+		assert_eq!(
+			SignedBalance::<Balance>::try_from(-10i64),
+			Ok(SignedBalance::Negative(10))
+		);
+		assert_eq!(
+			SignedBalance::<Balance>::try_from(i64::MIN),
+			Ok(SignedBalance::Negative(i64::MAX as Balance + 1))
+		);
+		assert_eq!(SignedBalance::<u32>::try_from(-(u32::MAX as i64) - 1), Err(()));
+		assert_eq!(
+			SignedBalance::<u32>::try_from(-(u32::MAX as i64)),
+			Ok(SignedBalance::Negative(u32::MAX))
+		);
+		assert_eq!(SignedBalance::from(10u64), SignedBalance::Positive(10));
+
+		assert_eq!(i64::try_from(SignedBalance::<Balance>::negative(10)), Ok(-10));
+		assert_eq!(i64::try_from(SignedBalance::<Balance>::Negative(0)), Ok(0));
+		assert_eq!(
+			i64::try_from(SignedBalance::<Balance>::negative(i64::MAX as Balance + 1)),
+			Ok(i64::MIN)
+		);
+		assert_eq!(
+			i64::try_from(SignedBalance::<Balance>::positive(i64::MAX as Balance + 1)),
+			Err(())
+		);
+	}
+
+	#[test]
+	fn signed_balance_arithmetic_should_work() {
+		let five = SignedBalance::<Balance>::positive(5);
+		let minus_seven = SignedBalance::<Balance>::negative(7);
+
+		assert_eq!(SignedBalance::<Balance>::Negative(0), SignedBalance::default());
+		assert!(minus_seven < five);
+		assert!(minus_seven < SignedBalance::negative(6));
+		assert_eq!(-minus_seven, SignedBalance::positive(7));
+
+		assert_eq!(five.checked_add(&minus_seven), Some(SignedBalance::negative(2)));
+		assert_eq!(five.checked_sub(&minus_seven), Some(SignedBalance::positive(12)));
+		assert_eq!(five.checked_mul(&minus_seven), Some(SignedBalance::negative(35)));
+		assert_eq!(minus_seven.checked_div(&five), Some(SignedBalance::negative(1)));
+		assert_eq!(five.checked_div(&SignedBalance::default()), None);
+
+		let max = SignedBalance::<Balance>::positive(Balance::MAX);
+		assert_eq!(max.checked_add(&five), None);
+		assert_eq!(max.saturating_add(&five), max);
+		assert_eq!((-max).saturating_sub(&five), -max);
+		assert_eq!(max.saturating_mul(&minus_seven), -max);
+	}
+
+	#[test]
+	fn signed_balance_fixed_point_should_work() {
+		let minus_ten = SignedBalance::<Balance>::negative(10);
+
+		assert_eq!(
+			minus_ten.checked_mul_fixed(FixedU128::saturating_from_rational(1, 4)),
+			Some(SignedBalance::negative(2))
+		);
+		assert_eq!(
+			minus_ten.checked_mul_fixed(FixedI128::saturating_from_rational(-3, 2)),
+			Some(SignedBalance::positive(15))
+		);
+		assert_eq!(
+			SignedBalance::<Balance>::positive(Balance::MAX)
+				.saturating_mul_fixed(FixedI128::saturating_from_integer(-2)),
+			SignedBalance::negative(Balance::MAX)
+		);
+	}
+}
@@ -362,6 +362,26 @@ pub trait NamedMultiReservableCurrency<AccountId>: MultiReservableCurrency<Accou
 	}
 }
 
+/// A complete multi-currency system, able to hold the balances of a set of
+/// currencies on behalf of a router like `orml_currencies`.
+pub trait MultiCurrencyBackend<AccountId>:
+	TransferAll<AccountId>
+	+ MultiCurrencyExtended<AccountId>
+	+ MultiLockableCurrency<AccountId>
+	+ MultiReservableCurrency<AccountId>
+	+ NamedMultiReservableCurrency<AccountId>
+{
+}
+
+impl<AccountId, T> MultiCurrencyBackend<AccountId> for T where
+	T: TransferAll<AccountId>
+		+ MultiCurrencyExtended<AccountId>
+		+ MultiLockableCurrency<AccountId>
+		+ MultiReservableCurrency<AccountId>
+		+ NamedMultiReservableCurrency<AccountId>
+{
+}
+
 /// Abstraction over a fungible (single) currency system.
 pub trait BasicCurrency<AccountId> {
 	/// The balance of an account.
@@ -691,6 +711,63 @@ impl<AccountId, CurrencyId, Balance> OnTransfer<AccountId, CurrencyId, Balance>
 	}
 }
 
+/// Hook to run when reserving balance of an account.
+pub trait OnReserve<AccountId, CurrencyId, Balance> {
+	fn on_reserve(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+impl<AccountId, CurrencyId, Balance> OnReserve<AccountId, CurrencyId, Balance> for () {
+	fn on_reserve(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(1, 5)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnReserve<AccountId, CurrencyId, Balance> for Tuple {
+	fn on_reserve(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		for_tuples!( #( Tuple::on_reserve(currency_id, who, amount)?; )* );
+		Ok(())
+	}
+}
+
+/// Hook to run before unreserving balance of an account.
+///
+/// The `amount` is the balance which would actually leave the reserve.
+/// Returning an error vetoes it, then fallible operations fail with the error,
+/// while `MultiReservableCurrency::unreserve` unreserves nothing and returns
+/// the whole value as not unreserved. Slashing reserved balance doesn't run
+/// this hook, `OnSlash` does instead.
+pub trait OnUnreserve<AccountId, CurrencyId, Balance> {
+	fn on_unreserve(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult;
+}
+
+impl<AccountId, CurrencyId, Balance> OnUnreserve<AccountId, CurrencyId, Balance> for () {
+	fn on_unreserve(_: CurrencyId, _: &AccountId, _: Balance) -> DispatchResult {
+		Ok(())
+	}
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(1, 5)]
+impl<AccountId, CurrencyId: Copy, Balance: Copy> OnUnreserve<AccountId, CurrencyId, Balance> for Tuple {
+	fn on_unreserve(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> DispatchResult {
+		for_tuples!( #( Tuple::on_unreserve(currency_id, who, amount)?; )* );
+		Ok(())
+	}
+}
+
+/// Fee charged on transfers from an account to another.
+pub trait TransferFee<AccountId, CurrencyId, Balance> {
+	/// Returns the fee deducted from the transferred `amount` and the account
+	/// receiving it, or `None` if the transfer is free of charge.
+	fn fee(currency_id: CurrencyId, from: &AccountId, to: &AccountId, amount: Balance) -> Option<(Balance, AccountId)>;
+}
+
+impl<AccountId, CurrencyId, Balance> TransferFee<AccountId, CurrencyId, Balance> for () {
+	fn fee(_: CurrencyId, _: &AccountId, _: &AccountId, _: Balance) -> Option<(Balance, AccountId)> {
+		None
+	}
+}
+
 pub trait MutationHooks<AccountId, CurrencyId, Balance> {
 	/// Handler to burn or transfer account's dust.
 	type OnDust: OnDust<AccountId, CurrencyId, Balance>;
@@ -710,6 +787,23 @@ pub trait MutationHooks<AccountId, CurrencyId, Balance> {
 	/// Hook to run after transferring from an account to another.
 	type PostTransfer: OnTransfer<AccountId, CurrencyId, Balance>;
 
+	/// Hook to run before reserving balance of an account.
+	type PreReserve: OnReserve<AccountId, CurrencyId, Balance>;
+
+	/// Hook to run after reserving balance of an account.
+	type PostReserve: OnReserve<AccountId, CurrencyId, Balance>;
+
+	/// Hook to run before unreserving balance of an account. Returning an
+	/// error prevents the balance from leaving the reserve.
+	///
+	/// NOTE: Slashes of reserved balance are excluded from the reserve hooks,
+	/// they only run `OnSlash`.
+	type OnUnreserve: OnUnreserve<AccountId, CurrencyId, Balance>;
+
+	/// Fee deducted from the amount of each transfer and routed to a
+	/// receiver.
+	type TransferFee: TransferFee<AccountId, CurrencyId, Balance>;
+
 	/// Handler for when an account was created.
 	type OnNewTokenAccount: Happened<(AccountId, CurrencyId)>;
 
@@ -724,6 +818,10 @@ impl<AccountId, CurrencyId, Balance> MutationHooks<AccountId, CurrencyId, Balanc
 	type PostDeposit = ();
 	type PreTransfer = ();
 	type PostTransfer = ();
+	type PreReserve = ();
+	type PostReserve = ();
+	type OnUnreserve = ();
+	type TransferFee = ();
 	type OnNewTokenAccount = ();
 	type OnKilledTokenAccount = ();
 }
@@ -8,21 +8,54 @@
 //!
 //! ### Vesting Schedule
 //!
-//! The schedule of a vesting is described by data structure `VestingSchedule`:
-//! from the block number of `start`, for every `period` amount of blocks,
-//! `per_period` amount of balance would unlocked, until number of periods
-//! `period_count` reached. Note in vesting schedules, *time* is measured by
-//! block number. All `VestingSchedule`s under an account could be queried in
-//! chain state.
+//! The schedule of a vesting is described by data structure `VestingSchedule`.
+//! A `V1` schedule is graded: from the time of `start`, for every `period` of
+//! time, `per_period` amount of balance would unlocked, until number of periods
+//! `period_count` reached. A `V2` schedule releases balance by a `VestingCurve`
+//! after `start`, which is graded, linear over time or a list of milestones,
+//! and could have a `cliff` before which nothing is unlocked. All
+//! `VestingSchedule`s under an account and currency could be queried in chain
+//! state.
+//!
+//! ### Time
+//!
+//! Note in vesting schedules, *time* is measured by `Config::Moment` from
+//! `Config::BlockNumberProvider`. It is the block number usually, or a
+//! wall-clock moment with `UnixTimeProvider` or `TimeProvider`, so schedules do
+//! not drift when block times change. The `time` module has helpers to convert
+//! between block numbers and moments, and `Migration` with `BlockTime` converts
+//! existing schedules from block numbers to moments.
+//!
+//! ### Multi-currency
+//!
+//! Vested balances are locked with `MultiLockableCurrency`, so an account could
+//! have independent vesting schedules in each currency. The lock of a currency
+//! is updated only by the schedules of that currency.
+//!
+//! This is a breaking change from the single-currency module:
+//! `Config::Currency` is replaced by `Config::MultiCurrency`,
+//! `Config::MinVestedTransfer` is keyed by currency, the calls, events and
+//! genesis config take a `currency_id`, and `VestingSchedules` is keyed by
+//! `(AccountId, CurrencyId)`. To keep vesting a `LockableCurrency`, use the
+//! native currency of `orml-currencies` as `Config::MultiCurrency`, which
+//! keeps locking it under `VESTING_LOCK_ID`, and run `Migration` with its
+//! currency id to move the existing schedules.
 //!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
-//! - `vested_transfer` - Add a new vesting schedule for an account.
-//! - `claim` - Claim unlocked balances.
-//! - `update_vesting_schedules` - Update all vesting schedules under an
-//!   account, `root` origin required.
+//! - `vested_transfer` - Add a new vesting schedule in a currency for an
+//!   account.
+//! - `claim` - Claim unlocked balances of a currency.
+//! - `claim_for` - Claim unlocked balances of a currency for another account.
+//! - `update_vesting_schedules` - Update all vesting schedules in a currency
+//!   under an account, `root` origin required.
+//! - `revoke_schedule` - Revoke a vesting schedule, only its `revoker` is
+//!   allowed.
+//! - `merge_schedules` - Merge two vesting schedules of the caller into one.
+//! - `transfer_vesting` - Transfer part of a vesting schedule with its locked
+//!   balance to another account, or split it if the account is the caller.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -30,15 +63,20 @@
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
-	traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, WithdrawReasons},
+	traits::{EnsureOrigin, Get, LockIdentifier},
 	BoundedVec,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
+use orml_traits::{GetByKey, MultiCurrency, MultiLockableCurrency};
 use parity_scale_codec::{HasCompact, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{AtLeast32Bit, BlockNumberProvider, CheckedAdd, Saturating, StaticLookup, Zero},
-	ArithmeticError, DispatchResult, RuntimeDebug,
+	helpers_128bit::multiply_by_rational_with_rounding,
+	traits::{
+		AtLeast32Bit, AtLeast32BitUnsigned, BlockNumberProvider, CheckedAdd, Saturating, StaticLookup,
+		UniqueSaturatedInto, Zero,
+	},
+	ArithmeticError, DispatchResult, Percent, Rounding, RuntimeDebug,
 };
 use sp_std::{
 	cmp::{Eq, PartialEq},
@@ -49,61 +87,302 @@ mod mock;
 mod tests;
 mod weights;
 
+pub mod migrations;
+pub mod time;
+
+pub use migrations::{BlockTime, Migration, VestingBlocks};
 pub use module::*;
+pub use time::{TimeProvider, UnixTimeProvider};
 pub use weights::WeightInfo;
 
 pub const VESTING_LOCK_ID: LockIdentifier = *b"ormlvest";
 
+/// The maximum number of milestones of a `VestingCurve::Milestones`.
+pub const MAX_VESTING_MILESTONES: u32 = 16;
+
+/// The release curve of a `VestingSchedule::V2`, measured from its `start`.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum VestingCurve<Moment, Balance: MaxEncodedLen + HasCompact> {
+	/// `per_period` amount is released every `period` of time, until
+	/// `period_count` periods passed.
+	Graded {
+		period: Moment,
+		period_count: u32,
+		#[codec(compact)]
+		per_period: Balance,
+	},
+	/// `total` amount is released linearly over `duration` of time.
+	Linear {
+		duration: Moment,
+		#[codec(compact)]
+		total: Balance,
+	},
+	/// `total` amount is released by `(time after start, cumulative percent)`
+	/// milestones. Times must be strictly increasing, percents non-decreasing
+	/// and the last milestone must release 100%.
+	Milestones {
+		#[codec(compact)]
+		total: Balance,
+		milestones: BoundedVec<(Moment, Percent), ConstU32<MAX_VESTING_MILESTONES>>,
+	},
+}
+
+impl<Moment: AtLeast32Bit + Copy, Balance: AtLeast32Bit + MaxEncodedLen + Copy> VestingCurve<Moment, Balance> {
+	/// Returns the time after start until all released, `None` if calculation
+	/// overflows.
+	pub fn duration(&self) -> Option<Moment> {
+		match self {
+			VestingCurve::Graded {
+				period, period_count, ..
+			} => period.checked_mul(&(*period_count).into()),
+			VestingCurve::Linear { duration, .. } => Some(*duration),
+			VestingCurve::Milestones { milestones, .. } => {
+				Some(milestones.last().map(|(at, _)| *at).unwrap_or_else(Zero::zero))
+			}
+		}
+	}
+
+	/// Returns all locked amount, `None` if calculation overflows.
+	pub fn total_amount(&self) -> Option<Balance> {
+		match self {
+			VestingCurve::Graded {
+				period_count,
+				per_period,
+				..
+			} => per_period.checked_mul(&(*period_count).into()),
+			VestingCurve::Linear { total, .. } | VestingCurve::Milestones { total, .. } => Some(*total),
+		}
+	}
+
+	/// Returns locked amount `elapsed` time after start.
+	///
+	/// Note this func assumes curve is a valid one(non-zero period or
+	/// duration and non-overflow total amount), and it should be guaranteed
+	/// by callers.
+	pub fn locked_amount(&self, elapsed: Moment) -> Balance {
+		match self {
+			VestingCurve::Graded {
+				period,
+				period_count,
+				per_period,
+			} => graded_locked_amount(elapsed, *period, *period_count, *per_period),
+			VestingCurve::Linear { duration, total } => {
+				if elapsed >= *duration {
+					return Zero::zero();
+				}
+				// total - total * elapsed / duration
+				total.saturating_sub(fraction_of(
+					*total,
+					elapsed.unique_saturated_into(),
+					(*duration).unique_saturated_into(),
+				))
+			}
+			VestingCurve::Milestones { total, milestones } => {
+				let released = milestones
+					.iter()
+					.rev()
+					.find(|(at, _)| *at <= elapsed)
+					.map(|(_, percent)| *percent)
+					.unwrap_or_default();
+				total.saturating_sub(fraction_of(*total, released.deconstruct().into(), 100))
+			}
+		}
+	}
+}
+
 /// The vesting schedule.
 ///
-/// Benefits would be granted gradually, `per_period` amount every `period`
-/// of blocks after `start`.
+/// Schedules stored in the original graded layout are wrapped into
+/// `VestingSchedule::V1` by `Migration`, they don't decode as a
+/// `VestingSchedule` before. The encoding of `Event::VestingScheduleAdded`
+/// changes the same way, so clients decoding it must be upgraded.
 #[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
-pub struct VestingSchedule<BlockNumber, Balance: MaxEncodedLen + HasCompact> {
-	/// Vesting starting block
-	pub start: BlockNumber,
-	/// Number of blocks between vest
-	pub period: BlockNumber,
-	/// Number of vest
-	pub period_count: u32,
-	/// Amount of tokens to release per vest
-	#[codec(compact)]
-	pub per_period: Balance,
+pub enum VestingSchedule<Moment, Balance: MaxEncodedLen + HasCompact, AccountId> {
+	/// Benefits would be granted gradually, `per_period` amount every `period`
+	/// of time after `start`.
+	#[codec(index = 0)]
+	V1 {
+		/// Vesting starting time
+		start: Moment,
+		/// Time between vests
+		period: Moment,
+		/// Number of vest
+		period_count: u32,
+		/// Amount of tokens to release per vest
+		#[codec(compact)]
+		per_period: Balance,
+	},
+	/// Benefits would be granted by `curve` after `start`. If there is a
+	/// `cliff`, nothing is granted before it and the amount released by then
+	/// is granted at once. If there is a `revoker`, it could revoke the
+	/// benefits not granted yet.
+	#[codec(index = 1)]
+	V2 {
+		/// Vesting starting time
+		start: Moment,
+		/// Time before which nothing is granted
+		cliff: Option<Moment>,
+		/// How benefits are granted after `start`
+		curve: VestingCurve<Moment, Balance>,
+		/// Account allowed to revoke the schedule
+		revoker: Option<AccountId>,
+	},
 }
 
-impl<BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32Bit + MaxEncodedLen + Copy>
-	VestingSchedule<BlockNumber, Balance>
+impl<Moment: AtLeast32Bit + Copy, Balance: AtLeast32Bit + MaxEncodedLen + Copy, AccountId: Clone>
+	VestingSchedule<Moment, Balance, AccountId>
 {
+	/// Returns the account allowed to revoke the schedule, if any.
+	pub fn revoker(&self) -> Option<&AccountId> {
+		match self {
+			VestingSchedule::V1 { .. } => None,
+			VestingSchedule::V2 { revoker, .. } => revoker.as_ref(),
+		}
+	}
+
 	/// Returns the end of all periods, `None` if calculation overflows.
-	pub fn end(&self) -> Option<BlockNumber> {
-		// period * period_count + start
-		self.period
-			.checked_mul(&self.period_count.into())?
-			.checked_add(&self.start)
+	pub fn end(&self) -> Option<Moment> {
+		match self {
+			// period * period_count + start
+			VestingSchedule::V1 {
+				start,
+				period,
+				period_count,
+				..
+			} => period.checked_mul(&(*period_count).into())?.checked_add(start),
+			VestingSchedule::V2 {
+				start, cliff, curve, ..
+			} => {
+				let end = curve.duration()?.checked_add(start)?;
+				Some(cliff.map_or(end, |cliff| end.max(cliff)))
+			}
+		}
 	}
 
 	/// Returns all locked amount, `None` if calculation overflows.
 	pub fn total_amount(&self) -> Option<Balance> {
-		self.per_period.checked_mul(&self.period_count.into())
+		match self {
+			VestingSchedule::V1 {
+				period_count,
+				per_period,
+				..
+			} => per_period.checked_mul(&(*period_count).into()),
+			VestingSchedule::V2 { curve, .. } => curve.total_amount(),
+		}
 	}
 
 	/// Returns locked amount for a given `time`.
 	///
 	/// Note this func assumes schedule is a valid one(non-zero period and
 	/// non-overflow total amount), and it should be guaranteed by callers.
-	pub fn locked_amount(&self, time: BlockNumber) -> Balance {
-		// full = (time - start) / period
-		// unrealized = period_count - full
-		// per_period * unrealized
-		let full = time
-			.saturating_sub(self.start)
-			.checked_div(&self.period)
-			.expect("ensured non-zero period; qed");
-		let unrealized = self.period_count.saturating_sub(full.unique_saturated_into());
-		self.per_period
-			.checked_mul(&unrealized.into())
-			.expect("ensured non-overflow total amount; qed")
+	pub fn locked_amount(&self, time: Moment) -> Balance {
+		match self {
+			VestingSchedule::V1 {
+				start,
+				period,
+				period_count,
+				per_period,
+			} => graded_locked_amount(time.saturating_sub(*start), *period, *period_count, *per_period),
+			VestingSchedule::V2 {
+				start, cliff, curve, ..
+			} => {
+				if cliff.map_or(false, |cliff| time < cliff) {
+					return curve.total_amount().expect("ensured non-overflow total amount; qed");
+				}
+				curve.locked_amount(time.saturating_sub(*start))
+			}
+		}
 	}
+
+	/// Splits off the part of the schedule locking `amount` out of its
+	/// `locked` amount, and keeps the rest. The rest is rounded down, so it
+	/// never locks more than `locked - amount`. The split part is released by
+	/// the same curve.
+	///
+	/// Note this func assumes `amount` is not greater than a non-zero
+	/// `locked`, and it should be guaranteed by callers.
+	pub fn split_off(&mut self, amount: Balance, locked: Balance) -> Self {
+		let split = |value: &mut Balance| -> Balance {
+			let rest = fraction_of(
+				*value,
+				locked.saturating_sub(amount).unique_saturated_into(),
+				locked.unique_saturated_into(),
+			);
+			let part = value.saturating_sub(rest);
+			*value = rest;
+			part
+		};
+		match self {
+			VestingSchedule::V1 {
+				start,
+				period,
+				period_count,
+				per_period,
+			} => VestingSchedule::V1 {
+				start: *start,
+				period: *period,
+				period_count: *period_count,
+				per_period: split(per_period),
+			},
+			VestingSchedule::V2 {
+				start,
+				cliff,
+				curve,
+				revoker,
+			} => {
+				let curve = match curve {
+					VestingCurve::Graded {
+						period,
+						period_count,
+						per_period,
+					} => VestingCurve::Graded {
+						period: *period,
+						period_count: *period_count,
+						per_period: split(per_period),
+					},
+					VestingCurve::Linear { duration, total } => VestingCurve::Linear {
+						duration: *duration,
+						total: split(total),
+					},
+					VestingCurve::Milestones { total, milestones } => VestingCurve::Milestones {
+						total: split(total),
+						milestones: milestones.clone(),
+					},
+				};
+				VestingSchedule::V2 {
+					start: *start,
+					cliff: *cliff,
+					curve,
+					revoker: revoker.clone(),
+				}
+			}
+		}
+	}
+}
+
+/// Returns locked amount of a graded release `elapsed` time after start.
+fn graded_locked_amount<Moment: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy>(
+	elapsed: Moment,
+	period: Moment,
+	period_count: u32,
+	per_period: Balance,
+) -> Balance {
+	// full = elapsed / period
+	// unrealized = period_count - full
+	// per_period * unrealized
+	let full = elapsed.checked_div(&period).expect("ensured non-zero period; qed");
+	let unrealized = period_count.saturating_sub(full.unique_saturated_into());
+	per_period
+		.checked_mul(&unrealized.into())
+		.expect("ensured non-overflow total amount; qed")
+}
+
+/// Returns `amount * numerator / denominator` rounded down, where `numerator`
+/// is not greater than `denominator`.
+fn fraction_of<N: AtLeast32Bit + Copy>(amount: N, numerator: u128, denominator: u128) -> N {
+	multiply_by_rational_with_rounding(amount.unique_saturated_into(), numerator, denominator, Rounding::Down)
+		.map(|part| part.unique_saturated_into())
+		.unwrap_or(amount)
 }
 
 #[frame_support::pallet]
@@ -111,12 +390,16 @@ pub mod module {
 	use super::*;
 
 	pub(crate) type BalanceOf<T> =
-		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
-	pub(crate) type VestingScheduleOf<T> = VestingSchedule<BlockNumberFor<T>, BalanceOf<T>>;
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub(crate) type CurrencyIdOf<T> =
+		<<T as Config>::MultiCurrency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
+	pub(crate) type VestingScheduleOf<T> =
+		VestingSchedule<<T as Config>::Moment, BalanceOf<T>, <T as frame_system::Config>::AccountId>;
 	pub type ScheduledItem<T> = (
 		<T as frame_system::Config>::AccountId,
-		BlockNumberFor<T>,
-		BlockNumberFor<T>,
+		CurrencyIdOf<T>,
+		<T as Config>::Moment,
+		<T as Config>::Moment,
 		u32,
 		BalanceOf<T>,
 	);
@@ -125,11 +408,11 @@ pub mod module {
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-		type Currency: LockableCurrency<Self::AccountId, Moment = BlockNumberFor<Self>>;
+		type MultiCurrency: MultiLockableCurrency<Self::AccountId, Moment = BlockNumberFor<Self>>;
 
-		#[pallet::constant]
-		/// The minimum amount transferred to call `vested_transfer`.
-		type MinVestedTransfer: Get<BalanceOf<Self>>;
+		/// The minimum amount transferred to call `vested_transfer`, by
+		/// currency.
+		type MinVestedTransfer: GetByKey<CurrencyIdOf<Self>, BalanceOf<Self>>;
 
 		/// Required origin for vested transfer.
 		type VestedTransferOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
@@ -140,13 +423,29 @@ pub mod module {
 		/// The maximum vesting schedules
 		type MaxVestingSchedules: Get<u32>;
 
-		// The block number provider
-		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
+		/// The time vesting schedules are measured in, block numbers or
+		/// wall-clock moments.
+		type Moment: Parameter + Member + AtLeast32BitUnsigned + Copy + MaxEncodedLen + MaybeSerializeDeserialize;
+
+		/// The provider of the current time of vesting schedules, e.g.
+		/// `frame_system::Pallet` for block numbers, or `UnixTimeProvider` for
+		/// wall-clock moments.
+		///
+		/// Note `BlockNumberProvider` is only used as a source of the current
+		/// `Config::Moment`, which is a timestamp rather than a block number
+		/// with `UnixTimeProvider` or `TimeProvider`. Existing schedules are
+		/// read in the same unit, so switching between block numbers and
+		/// moments requires converting them with `Migration` and `BlockTime`.
+		type BlockNumberProvider: BlockNumberProvider<BlockNumber = Self::Moment>;
+
+		/// The account receiving the unvested balance of revoked schedules,
+		/// the revoker if `None`.
+		type RevokedSink: Get<Option<Self::AccountId>>;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Vesting period is zero
+		/// Vesting period or duration is zero
 		ZeroVestingPeriod,
 		/// Number of vests is zero
 		ZeroVestingPeriodCount,
@@ -158,6 +457,18 @@ pub mod module {
 		AmountLow,
 		/// Failed because the maximum vesting schedules was exceeded
 		MaxVestingSchedulesExceeded,
+		/// Vesting milestones are empty, out of order or do not release all
+		InvalidMilestones,
+		/// The vesting schedule does not exist
+		VestingScheduleNotFound,
+		/// The caller is not the revoker of the vesting schedule
+		NotRevoker,
+		/// A vesting schedule could not be merged with itself
+		SameVestingSchedule,
+		/// The merged vesting schedules have different revokers
+		RevokerMismatch,
+		/// The amount exceeds the locked balance of the vesting schedule
+		InsufficientLockedBalance,
 	}
 
 	#[pallet::event]
@@ -167,23 +478,54 @@ pub mod module {
 		VestingScheduleAdded {
 			from: T::AccountId,
 			to: T::AccountId,
+			currency_id: CurrencyIdOf<T>,
 			vesting_schedule: VestingScheduleOf<T>,
 		},
 		/// Claimed vesting.
-		Claimed { who: T::AccountId, amount: BalanceOf<T> },
+		Claimed {
+			who: T::AccountId,
+			currency_id: CurrencyIdOf<T>,
+			amount: BalanceOf<T>,
+		},
 		/// Updated vesting schedules.
-		VestingSchedulesUpdated { who: T::AccountId },
+		VestingSchedulesUpdated {
+			who: T::AccountId,
+			currency_id: CurrencyIdOf<T>,
+		},
+		/// Revoked vesting schedule, the unvested amount is transferred to
+		/// `recipient`.
+		VestingRevoked {
+			who: T::AccountId,
+			currency_id: CurrencyIdOf<T>,
+			revoker: T::AccountId,
+			recipient: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// Merged two vesting schedules.
+		VestingSchedulesMerged {
+			who: T::AccountId,
+			currency_id: CurrencyIdOf<T>,
+		},
+		/// Transferred part of a vesting schedule with its locked balance.
+		VestingTransferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			currency_id: CurrencyIdOf<T>,
+			amount: BalanceOf<T>,
+		},
 	}
 
-	/// Vesting schedules of an account.
+	/// Vesting schedules of an account in a currency.
 	///
-	/// VestingSchedules: map AccountId => Vec<VestingSchedule>
+	/// VestingSchedules: double_map AccountId, CurrencyId => Vec<VestingSchedule>
 	#[pallet::storage]
 	#[pallet::getter(fn vesting_schedules)]
-	pub type VestingSchedules<T: Config> = StorageMap<
+	pub type VestingSchedules<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
+		Twox64Concat,
+		CurrencyIdOf<T>,
 		BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules>,
 		ValueQuery,
 	>;
@@ -206,10 +548,10 @@ pub mod module {
 		fn build(&self) {
 			self.vesting
 				.iter()
-				.for_each(|(who, start, period, period_count, per_period)| {
-					let mut bounded_schedules = VestingSchedules::<T>::get(who);
+				.for_each(|(who, currency_id, start, period, period_count, per_period)| {
+					let mut bounded_schedules = VestingSchedules::<T>::get(who, currency_id);
 					bounded_schedules
-						.try_push(VestingSchedule {
+						.try_push(VestingSchedule::V1 {
 							start: *start,
 							period: *period,
 							period_count: *period_count,
@@ -219,7 +561,7 @@ pub mod module {
 					let total_amount = bounded_schedules
 						.iter()
 						.try_fold::<_, _, Result<BalanceOf<T>, DispatchError>>(Zero::zero(), |acc_amount, schedule| {
-							let amount = ensure_valid_vesting_schedule::<T>(schedule)?;
+							let amount = ensure_valid_vesting_schedule::<T>(*currency_id, schedule)?;
 							acc_amount
 								.checked_add(&amount)
 								.ok_or_else(|| ArithmeticError::Overflow.into())
@@ -227,17 +569,21 @@ pub mod module {
 						.expect("Invalid vesting schedule");
 
 					assert!(
-						T::Currency::free_balance(who) >= total_amount,
+						T::MultiCurrency::free_balance(*currency_id, who) >= total_amount,
 						"Account do not have enough balance"
 					);
 
-					T::Currency::set_lock(VESTING_LOCK_ID, who, total_amount, WithdrawReasons::all());
-					VestingSchedules::<T>::insert(who, bounded_schedules);
+					T::MultiCurrency::set_lock(VESTING_LOCK_ID, *currency_id, who, total_amount)
+						.expect("Set vesting lock failed");
+					VestingSchedules::<T>::insert(who, currency_id, bounded_schedules);
 				});
 		}
 	}
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::hooks]
@@ -247,12 +593,13 @@ pub mod module {
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::claim(<T as Config>::MaxVestingSchedules::get() / 2))]
-		pub fn claim(origin: OriginFor<T>) -> DispatchResult {
+		pub fn claim(origin: OriginFor<T>, currency_id: CurrencyIdOf<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let locked_amount = Self::do_claim(&who);
+			let locked_amount = Self::do_claim(&who, currency_id)?;
 
 			Self::deposit_event(Event::Claimed {
 				who,
+				currency_id,
 				amount: locked_amount,
 			});
 			Ok(())
@@ -263,6 +610,7 @@ pub mod module {
 		pub fn vested_transfer(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
 			schedule: VestingScheduleOf<T>,
 		) -> DispatchResult {
 			let from = T::VestedTransferOrigin::ensure_origin(origin)?;
@@ -270,16 +618,18 @@ pub mod module {
 
 			if to == from {
 				ensure!(
-					T::Currency::free_balance(&from) >= schedule.total_amount().ok_or(ArithmeticError::Overflow)?,
+					T::MultiCurrency::free_balance(currency_id, &from)
+						>= schedule.total_amount().ok_or(ArithmeticError::Overflow)?,
 					Error::<T>::InsufficientBalanceToLock,
 				);
 			}
 
-			Self::do_vested_transfer(&from, &to, schedule.clone())?;
+			Self::do_vested_transfer(&from, &to, currency_id, schedule.clone())?;
 
 			Self::deposit_event(Event::VestingScheduleAdded {
 				from,
 				to,
+				currency_id,
 				vesting_schedule: schedule,
 			});
 			Ok(())
@@ -290,50 +640,137 @@ pub mod module {
 		pub fn update_vesting_schedules(
 			origin: OriginFor<T>,
 			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
 			vesting_schedules: Vec<VestingScheduleOf<T>>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
 
 			let account = T::Lookup::lookup(who)?;
-			Self::do_update_vesting_schedules(&account, vesting_schedules)?;
+			Self::do_update_vesting_schedules(&account, currency_id, vesting_schedules)?;
 
-			Self::deposit_event(Event::VestingSchedulesUpdated { who: account });
+			Self::deposit_event(Event::VestingSchedulesUpdated {
+				who: account,
+				currency_id,
+			});
 			Ok(())
 		}
 
 		#[pallet::call_index(3)]
 		#[pallet::weight(T::WeightInfo::claim(<T as Config>::MaxVestingSchedules::get() / 2))]
-		pub fn claim_for(origin: OriginFor<T>, dest: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
+		pub fn claim_for(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+		) -> DispatchResult {
 			let _ = ensure_signed(origin)?;
 			let who = T::Lookup::lookup(dest)?;
-			let locked_amount = Self::do_claim(&who);
+			let locked_amount = Self::do_claim(&who, currency_id)?;
 
 			Self::deposit_event(Event::Claimed {
 				who,
+				currency_id,
 				amount: locked_amount,
 			});
 			Ok(())
 		}
+
+		/// Revoke the vesting schedule at `index` of `who` in `currency_id`.
+		///
+		/// The vested balance is unlocked, and the unvested balance is
+		/// transferred to `RevokedSink`, or back to the revoker.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::revoke_schedule())]
+		pub fn revoke_schedule(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			index: u32,
+		) -> DispatchResult {
+			let revoker = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			let (recipient, amount) = Self::do_revoke_schedule(&revoker, &who, currency_id, index)?;
+
+			Self::deposit_event(Event::VestingRevoked {
+				who,
+				currency_id,
+				revoker,
+				recipient,
+				amount,
+			});
+			Ok(())
+		}
+
+		/// Merge the vesting schedules at `index1` and `index2` of the caller
+		/// in `currency_id`.
+		///
+		/// Graded schedules sharing the same start, cliff and curve apart from
+		/// the amounts are summed, and so are milestones ones when the sum
+		/// releases exactly as they do. Any others are approximated by a
+		/// milestones schedule starting now, which never unlocks faster than
+		/// the original ones but may unlock up to 1% of the total plus one
+		/// sampling interval later.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::merge_schedules())]
+		pub fn merge_schedules(
+			origin: OriginFor<T>,
+			currency_id: CurrencyIdOf<T>,
+			index1: u32,
+			index2: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_merge_schedules(&who, currency_id, index1, index2)?;
+
+			Self::deposit_event(Event::VestingSchedulesMerged { who, currency_id });
+			Ok(())
+		}
+
+		/// Split off the part of the caller's vesting schedule at `index`
+		/// locking `amount`, and transfer it with its locked balance to
+		/// `dest`.
+		///
+		/// The transferred part is released by the same curve. If `dest` is
+		/// the caller, the schedule is split in two.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::transfer_vesting())]
+		pub fn transfer_vesting(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			index: u32,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(dest)?;
+			let amount = Self::do_transfer_vesting(&from, &to, currency_id, index, amount)?;
+
+			Self::deposit_event(Event::VestingTransferred {
+				from,
+				to,
+				currency_id,
+				amount,
+			});
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	fn do_claim(who: &T::AccountId) -> BalanceOf<T> {
-		let locked = Self::locked_balance(who);
+	fn do_claim(who: &T::AccountId, currency_id: CurrencyIdOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		let locked = Self::locked_balance(who, currency_id);
 		if locked.is_zero() {
 			// cleanup the storage and unlock the fund
-			<VestingSchedules<T>>::remove(who);
-			T::Currency::remove_lock(VESTING_LOCK_ID, who);
+			<VestingSchedules<T>>::remove(who, currency_id);
+			T::MultiCurrency::remove_lock(VESTING_LOCK_ID, currency_id, who)?;
 		} else {
-			T::Currency::set_lock(VESTING_LOCK_ID, who, locked, WithdrawReasons::all());
+			T::MultiCurrency::set_lock(VESTING_LOCK_ID, currency_id, who, locked)?;
 		}
-		locked
+		Ok(locked)
 	}
 
-	/// Returns locked balance based on current block number.
-	fn locked_balance(who: &T::AccountId) -> BalanceOf<T> {
+	/// Returns locked balance in `currency_id` based on the current time.
+	fn locked_balance(who: &T::AccountId, currency_id: CurrencyIdOf<T>) -> BalanceOf<T> {
 		let now = T::BlockNumberProvider::current_block_number();
-		<VestingSchedules<T>>::mutate_exists(who, |maybe_schedules| {
+		<VestingSchedules<T>>::mutate_exists(who, currency_id, |maybe_schedules| {
 			let total = if let Some(schedules) = maybe_schedules.as_mut() {
 				let mut total: BalanceOf<T> = Zero::zero();
 				schedules.retain(|s| {
@@ -352,60 +789,361 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
-	fn do_vested_transfer(from: &T::AccountId, to: &T::AccountId, schedule: VestingScheduleOf<T>) -> DispatchResult {
-		let schedule_amount = ensure_valid_vesting_schedule::<T>(&schedule)?;
+	fn do_vested_transfer(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		schedule: VestingScheduleOf<T>,
+	) -> DispatchResult {
+		let schedule_amount = ensure_valid_vesting_schedule::<T>(currency_id, &schedule)?;
 
-		let total_amount = Self::locked_balance(to)
+		let total_amount = Self::locked_balance(to, currency_id)
 			.checked_add(&schedule_amount)
 			.ok_or(ArithmeticError::Overflow)?;
 
-		T::Currency::transfer(from, to, schedule_amount, ExistenceRequirement::AllowDeath)?;
-		T::Currency::set_lock(VESTING_LOCK_ID, to, total_amount, WithdrawReasons::all());
-		<VestingSchedules<T>>::try_append(to, schedule).map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+		T::MultiCurrency::transfer(currency_id, from, to, schedule_amount)?;
+		T::MultiCurrency::set_lock(VESTING_LOCK_ID, currency_id, to, total_amount)?;
+		<VestingSchedules<T>>::try_append(to, currency_id, schedule)
+			.map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+		Ok(())
+	}
+
+	fn do_revoke_schedule(
+		revoker: &T::AccountId,
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		index: u32,
+	) -> Result<(T::AccountId, BalanceOf<T>), DispatchError> {
+		let schedule = <VestingSchedules<T>>::try_mutate(who, currency_id, |schedules| -> Result<_, DispatchError> {
+			let schedule = schedules
+				.get(index as usize)
+				.ok_or(Error::<T>::VestingScheduleNotFound)?;
+			ensure!(schedule.revoker() == Some(revoker), Error::<T>::NotRevoker);
+			Ok(schedules.remove(index as usize))
+		})?;
+		let unvested = schedule.locked_amount(T::BlockNumberProvider::current_block_number());
+
+		// relock the remaining schedules, which unlocks the revoked one
+		Self::do_claim(who, currency_id)?;
+
+		let recipient = T::RevokedSink::get().unwrap_or_else(|| revoker.clone());
+		T::MultiCurrency::transfer(currency_id, who, &recipient, unvested)?;
+		Ok((recipient, unvested))
+	}
+
+	fn do_merge_schedules(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		index1: u32,
+		index2: u32,
+	) -> DispatchResult {
+		ensure!(index1 != index2, Error::<T>::SameVestingSchedule);
+		let now = T::BlockNumberProvider::current_block_number();
+
+		<VestingSchedules<T>>::try_mutate(who, currency_id, |schedules| -> DispatchResult {
+			let first = schedules
+				.get(index1 as usize)
+				.ok_or(Error::<T>::VestingScheduleNotFound)?;
+			let second = schedules
+				.get(index2 as usize)
+				.ok_or(Error::<T>::VestingScheduleNotFound)?;
+			ensure!(first.revoker() == second.revoker(), Error::<T>::RevokerMismatch);
+			let merged = merge_vesting_schedules::<T>(first, second, now)?;
+
+			// remove the later one first so the index of the other is unchanged
+			schedules.remove(index1.max(index2) as usize);
+			schedules.remove(index1.min(index2) as usize);
+			if let Some(merged) = merged {
+				schedules
+					.try_push(merged)
+					.map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+			}
+			Ok(())
+		})?;
+
+		// the merged schedule locks as much as the original ones for now
+		Self::do_claim(who, currency_id)?;
 		Ok(())
 	}
 
-	fn do_update_vesting_schedules(who: &T::AccountId, schedules: Vec<VestingScheduleOf<T>>) -> DispatchResult {
+	fn do_transfer_vesting(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		index: u32,
+		amount: BalanceOf<T>,
+	) -> Result<BalanceOf<T>, DispatchError> {
+		let now = T::BlockNumberProvider::current_block_number();
+
+		let mut schedules = <VestingSchedules<T>>::get(from, currency_id);
+		let schedule = schedules
+			.get_mut(index as usize)
+			.ok_or(Error::<T>::VestingScheduleNotFound)?;
+		let locked = schedule.locked_amount(now);
+		ensure!(amount <= locked, Error::<T>::InsufficientLockedBalance);
+		let split = schedule.split_off(amount, locked);
+		ensure_valid_vesting_schedule::<T>(currency_id, &split)?;
+		// the parts round their locked amounts separately, so they may lock one
+		// more unit than the original. Move what the rest no longer locks, which
+		// keeps the lock of `from` exactly, the lock of `to` may exceed what it
+		// receives by that unit until the split part releases it.
+		let split_amount = locked.saturating_sub(schedule.locked_amount(now));
+		<VestingSchedules<T>>::insert(from, currency_id, schedules);
+
+		// relock the rest before moving the locked balance of the split part
+		Self::do_claim(from, currency_id)?;
+		T::MultiCurrency::transfer(currency_id, from, to, split_amount)?;
+		<VestingSchedules<T>>::try_append(to, currency_id, split)
+			.map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
+		Self::do_claim(to, currency_id)?;
+		Ok(split_amount)
+	}
+
+	fn do_update_vesting_schedules(
+		who: &T::AccountId,
+		currency_id: CurrencyIdOf<T>,
+		schedules: Vec<VestingScheduleOf<T>>,
+	) -> DispatchResult {
 		let bounded_schedules: BoundedVec<VestingScheduleOf<T>, T::MaxVestingSchedules> = schedules
 			.try_into()
 			.map_err(|_| Error::<T>::MaxVestingSchedulesExceeded)?;
 
 		// empty vesting schedules cleanup the storage and unlock the fund
 		if bounded_schedules.len().is_zero() {
-			<VestingSchedules<T>>::remove(who);
-			T::Currency::remove_lock(VESTING_LOCK_ID, who);
+			<VestingSchedules<T>>::remove(who, currency_id);
+			T::MultiCurrency::remove_lock(VESTING_LOCK_ID, currency_id, who)?;
 			return Ok(());
 		}
 
 		let total_amount = bounded_schedules
 			.iter()
 			.try_fold::<_, _, Result<BalanceOf<T>, DispatchError>>(Zero::zero(), |acc_amount, schedule| {
-				let amount = ensure_valid_vesting_schedule::<T>(schedule)?;
+				let amount = ensure_valid_vesting_schedule::<T>(currency_id, schedule)?;
 				acc_amount
 					.checked_add(&amount)
 					.ok_or_else(|| ArithmeticError::Overflow.into())
 			})?;
 		ensure!(
-			T::Currency::free_balance(who) >= total_amount,
+			T::MultiCurrency::free_balance(currency_id, who) >= total_amount,
 			Error::<T>::InsufficientBalanceToLock,
 		);
 
-		T::Currency::set_lock(VESTING_LOCK_ID, who, total_amount, WithdrawReasons::all());
-		<VestingSchedules<T>>::insert(who, bounded_schedules);
+		T::MultiCurrency::set_lock(VESTING_LOCK_ID, currency_id, who, total_amount)?;
+		<VestingSchedules<T>>::insert(who, currency_id, bounded_schedules);
 
 		Ok(())
 	}
 }
 
 /// Returns `Ok(total_total)` if valid schedule, or error.
-fn ensure_valid_vesting_schedule<T: Config>(schedule: &VestingScheduleOf<T>) -> Result<BalanceOf<T>, DispatchError> {
-	ensure!(!schedule.period.is_zero(), Error::<T>::ZeroVestingPeriod);
-	ensure!(!schedule.period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
+fn ensure_valid_vesting_schedule<T: Config>(
+	currency_id: CurrencyIdOf<T>,
+	schedule: &VestingScheduleOf<T>,
+) -> Result<BalanceOf<T>, DispatchError> {
+	match schedule {
+		VestingSchedule::V1 {
+			period, period_count, ..
+		}
+		| VestingSchedule::V2 {
+			curve: VestingCurve::Graded {
+				period, period_count, ..
+			},
+			..
+		} => {
+			ensure!(!period.is_zero(), Error::<T>::ZeroVestingPeriod);
+			ensure!(!period_count.is_zero(), Error::<T>::ZeroVestingPeriodCount);
+		}
+		VestingSchedule::V2 {
+			curve: VestingCurve::Linear { duration, .. },
+			..
+		} => {
+			ensure!(!duration.is_zero(), Error::<T>::ZeroVestingPeriod);
+		}
+		VestingSchedule::V2 {
+			curve: VestingCurve::Milestones { milestones, .. },
+			..
+		} => {
+			let (_, released) = milestones.last().ok_or(Error::<T>::InvalidMilestones)?;
+			ensure!(*released == Percent::from_percent(100), Error::<T>::InvalidMilestones);
+			ensure!(
+				milestones.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 <= w[1].1),
+				Error::<T>::InvalidMilestones
+			);
+		}
+	}
 	ensure!(schedule.end().is_some(), ArithmeticError::Overflow);
 
 	let total_total = schedule.total_amount().ok_or(ArithmeticError::Overflow)?;
 
-	ensure!(total_total >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+	ensure!(
+		total_total >= T::MinVestedTransfer::get(&currency_id),
+		Error::<T>::AmountLow
+	);
 
 	Ok(total_total)
 }
+
+/// Returns the sum of `first` and `second` if they share the same start, cliff
+/// and curve apart from the amounts, and the sum releases exactly what they
+/// do, `None` otherwise.
+///
+/// Graded schedules always sum exactly. Milestones ones round the release of
+/// the sum down once instead of twice, so the sum is only taken if that
+/// releases no more at any milestone. Linear ones are never summed, as their
+/// release would have to be checked at every moment.
+fn merge_same_schedules<T: Config>(
+	first: &VestingScheduleOf<T>,
+	second: &VestingScheduleOf<T>,
+) -> Result<Option<VestingScheduleOf<T>>, DispatchError> {
+	let add = |a: &BalanceOf<T>, b: &BalanceOf<T>| a.checked_add(b).ok_or(ArithmeticError::Overflow);
+	// a `V1` schedule is a `V2` graded one without cliff
+	let parts = |schedule: &VestingScheduleOf<T>| match schedule {
+		VestingSchedule::V1 {
+			start,
+			period,
+			period_count,
+			per_period,
+		} => (
+			*start,
+			None,
+			VestingCurve::Graded {
+				period: *period,
+				period_count: *period_count,
+				per_period: *per_period,
+			},
+		),
+		VestingSchedule::V2 {
+			start, cliff, curve, ..
+		} => (*start, *cliff, curve.clone()),
+	};
+	let (start, cliff, first_curve) = parts(first);
+	let (second_start, second_cliff, second_curve) = parts(second);
+	if start != second_start || cliff != second_cliff {
+		return Ok(None);
+	}
+
+	let curve = match (first_curve, second_curve) {
+		(
+			VestingCurve::Graded {
+				period,
+				period_count,
+				per_period,
+			},
+			VestingCurve::Graded {
+				period: second_period,
+				period_count: second_period_count,
+				per_period: second_per_period,
+			},
+		) if period == second_period && period_count == second_period_count => VestingCurve::Graded {
+			period,
+			period_count,
+			per_period: add(&per_period, &second_per_period)?,
+		},
+		(
+			VestingCurve::Milestones { total, milestones },
+			VestingCurve::Milestones {
+				total: second_total,
+				milestones: second_milestones,
+			},
+		) if milestones == second_milestones => {
+			let sum = add(&total, &second_total)?;
+			let released =
+				|amount: BalanceOf<T>, percent: &Percent| fraction_of(amount, percent.deconstruct().into(), 100);
+			if milestones.iter().any(|(_, percent)| {
+				released(sum, percent) != released(total, percent).saturating_add(released(second_total, percent))
+			}) {
+				return Ok(None);
+			}
+			VestingCurve::Milestones { total: sum, milestones }
+		}
+		_ => return Ok(None),
+	};
+
+	Ok(Some(match (first, curve) {
+		(
+			VestingSchedule::V1 { .. },
+			VestingCurve::Graded {
+				period,
+				period_count,
+				per_period,
+			},
+		) if matches!(second, VestingSchedule::V1 { .. }) => VestingSchedule::V1 {
+			start,
+			period,
+			period_count,
+			per_period,
+		},
+		(_, curve) => VestingSchedule::V2 {
+			start,
+			cliff,
+			curve,
+			revoker: first.revoker().cloned(),
+		},
+	}))
+}
+
+/// Returns a schedule which locks the sum of what `first` and `second` lock
+/// from `now` on, or `None` if nothing is locked.
+///
+/// Schedules sharing the same start, cliff and curve apart from the amounts
+/// are summed when that releases exactly as they do, see
+/// `merge_same_schedules`. Any others are approximated by a milestones
+/// schedule starting at `now`: the merged release is sampled at most
+/// `MAX_VESTING_MILESTONES` times until the later end, and each milestone
+/// releases what the originals have released by then, rounded down to a whole
+/// percent. The originals only release more over time, so the approximation
+/// never unlocks faster than them, but it is lossy: it may lock up to 1% of the
+/// total, plus what the originals release within one sampling interval, longer
+/// than they do.
+fn merge_vesting_schedules<T: Config>(
+	first: &VestingScheduleOf<T>,
+	second: &VestingScheduleOf<T>,
+	now: T::Moment,
+) -> Result<Option<VestingScheduleOf<T>>, DispatchError> {
+	if let Some(merged) = merge_same_schedules::<T>(first, second)? {
+		return Ok(Some(merged));
+	}
+
+	let locked_at = |time: T::Moment| first.locked_amount(time).saturating_add(second.locked_amount(time));
+	let total = first
+		.locked_amount(now)
+		.checked_add(&second.locked_amount(now))
+		.ok_or(ArithmeticError::Overflow)?;
+	if total.is_zero() {
+		return Ok(None);
+	}
+
+	let end = first
+		.end()
+		.zip(second.end())
+		.map(|(first_end, second_end)| first_end.max(second_end))
+		.ok_or(ArithmeticError::Overflow)?;
+	let duration = end.saturating_sub(now);
+
+	let mut milestones: Vec<(T::Moment, Percent)> = Vec::new();
+	for sample in 1..=MAX_VESTING_MILESTONES {
+		let elapsed = fraction_of(duration, sample.into(), MAX_VESTING_MILESTONES.into());
+		if elapsed.is_zero() || milestones.last().map_or(false, |(last, _)| *last == elapsed) {
+			continue;
+		}
+		let released = total.saturating_sub(locked_at(now.saturating_add(elapsed)));
+		let percent = multiply_by_rational_with_rounding(
+			released.unique_saturated_into(),
+			100,
+			total.unique_saturated_into(),
+			Rounding::Down,
+		)
+		.unwrap_or(100);
+		milestones.push((elapsed, Percent::from_percent(percent.unique_saturated_into())));
+	}
+
+	Ok(Some(VestingSchedule::V2 {
+		start: now,
+		cliff: None,
+		curve: VestingCurve::Milestones {
+			total,
+			milestones: BoundedVec::truncate_from(milestones),
+		},
+		revoker: first.revoker().cloned(),
+	}))
+}
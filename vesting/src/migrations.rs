@@ -0,0 +1,360 @@
+use crate::{
+	time::{block_to_moment, blocks_to_moments},
+	BalanceOf, Config, CurrencyIdOf, Pallet, VestingCurve, VestingSchedule, VestingScheduleOf, VestingSchedules,
+};
+use frame_support::{
+	pallet_prelude::*,
+	storage::{migration::storage_key_iter, unhashed},
+	traits::OnRuntimeUpgrade,
+	weights::Weight,
+	StoragePrefixedMap,
+};
+#[cfg(feature = "try-runtime")]
+use orml_traits::MultiCurrency;
+use parity_scale_codec::{Codec, HasCompact};
+use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumberProvider, Bounded};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::traits::{One, Saturating, Zero};
+
+use sp_std::vec::Vec;
+
+/// The block numbers vesting schedules were measured in before v3, and how
+/// they are converted to `Config::Moment`.
+pub trait VestingBlocks<T: Config> {
+	/// The block number the schedules were measured in.
+	type BlockNumber: Codec + AtLeast32BitUnsigned + Copy;
+
+	/// Returns the moment of `block`, `None` if calculation overflows.
+	fn moment_of(block: Self::BlockNumber) -> Option<T::Moment>;
+
+	/// Returns the moments taken by `blocks` blocks, `None` if calculation
+	/// overflows.
+	fn moments_of(blocks: Self::BlockNumber) -> Option<T::Moment>;
+}
+
+/// Keeps the schedules unchanged, `Config::Moment` being the block number.
+impl<T: Config> VestingBlocks<T> for () {
+	type BlockNumber = T::Moment;
+
+	fn moment_of(block: T::Moment) -> Option<T::Moment> {
+		Some(block)
+	}
+
+	fn moments_of(blocks: T::Moment) -> Option<T::Moment> {
+		Some(blocks)
+	}
+}
+
+/// Converts the block numbers of `Blocks` to moments, estimated from the
+/// current moment of `Config::BlockNumberProvider` and `Moments` per block.
+///
+/// Note the current moment, e.g. of `pallet_timestamp`, could be the one of the
+/// previous block during runtime upgrades, which delays the converted schedules
+/// by one block time at most.
+pub struct BlockTime<Blocks, Moments>(PhantomData<(Blocks, Moments)>);
+impl<T: Config, Blocks: BlockNumberProvider, Moments: Get<T::Moment>> VestingBlocks<T> for BlockTime<Blocks, Moments>
+where
+	Blocks::BlockNumber: Copy,
+{
+	type BlockNumber = Blocks::BlockNumber;
+
+	fn moment_of(block: Blocks::BlockNumber) -> Option<T::Moment> {
+		block_to_moment(
+			block,
+			Blocks::current_block_number(),
+			T::BlockNumberProvider::current_block_number(),
+			Moments::get(),
+		)
+	}
+
+	fn moments_of(blocks: Blocks::BlockNumber) -> Option<T::Moment> {
+		blocks_to_moments(blocks, Moments::get())
+	}
+}
+
+/// Migrate the `VestingSchedules` storage to the latest layout.
+///
+/// - v1: the single-currency `VestingSchedules` are keyed by `(AccountId,
+///   CurrencyId)`. All existing schedules are moved under `LegacyCurrencyId`.
+///   The vesting locks are left untouched, so `Config::MultiCurrency` must
+///   resolve `LegacyCurrencyId` to the `LockableCurrency` the pallet was
+///   previously configured with, e.g. the native currency of
+///   `orml-currencies`.
+/// - v2: the graded schedule struct is wrapped into `VestingSchedule::V1`.
+/// - v3: the schedules are measured in `Config::Moment`. Before, they were
+///   measured in `Blocks::BlockNumber`, which `Blocks` converts: the default
+///   `()` keeps them unchanged, for `Config::Moment` still being the block
+///   number, while `BlockTime` converts them to wall-clock moments.
+///
+/// With `try-runtime`, the number of schedules and their total amount are
+/// checked to be unchanged, and the vesting lock of each account to be still
+/// in effect, i.e. `LegacyCurrencyId` to resolve to the locked currency.
+pub struct Migration<T, LegacyCurrencyId, Blocks = ()>(PhantomData<(T, LegacyCurrencyId, Blocks)>);
+impl<T: Config, LegacyCurrencyId: Get<CurrencyIdOf<T>>, Blocks: VestingBlocks<T>> OnRuntimeUpgrade
+	for Migration<T, LegacyCurrencyId, Blocks>
+{
+	fn on_runtime_upgrade() -> Weight {
+		let mut weight: Weight = Weight::zero();
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		if onchain_version < 1 {
+			let inner_weight = v1::migrate::<T, Blocks>(LegacyCurrencyId::get());
+			weight.saturating_accrue(inner_weight);
+		}
+		if onchain_version < 2 {
+			let inner_weight = v2::migrate::<T, Blocks>();
+			weight.saturating_accrue(inner_weight);
+		}
+		if onchain_version < 3 {
+			let inner_weight = v3::migrate::<T, Blocks>();
+			weight.saturating_accrue(inner_weight);
+		}
+		weight
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		let onchain_version = Pallet::<T>::on_chain_storage_version();
+		let summary = if onchain_version < 1 {
+			summarize::<T, _>(
+				storage_key_iter::<T::AccountId, Vec<OldVestingScheduleOf<T, Blocks>>, Blake2_128Concat>(
+					VestingSchedules::<T>::module_prefix(),
+					VestingSchedules::<T>::storage_prefix(),
+				)
+				.flat_map(|(_, schedules)| schedules.into_iter().map(BlocksScheduleOf::<T, Blocks>::from)),
+			)
+		} else if onchain_version < 2 {
+			summarize::<T, _>(
+				stored_schedules::<T, OldVestingScheduleOf<T, Blocks>>().map(BlocksScheduleOf::<T, Blocks>::from),
+			)
+		} else if onchain_version < 3 {
+			summarize::<T, _>(stored_schedules::<T, BlocksScheduleOf<T, Blocks>>())
+		} else {
+			summarize::<T, _>(VestingSchedules::<T>::iter_values().flatten())
+		}?;
+		Ok(summary.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let summary: (u32, BalanceOf<T>) =
+			Decode::decode(&mut &state[..]).map_err(|_| "Failed to decode the vesting summary")?;
+		ensure!(
+			Pallet::<T>::on_chain_storage_version() == Pallet::<T>::current_storage_version(),
+			"Vesting storage version is not the current one"
+		);
+		ensure!(
+			summarize::<T, _>(VestingSchedules::<T>::iter_values().flatten())? == summary,
+			"Number or total amount of vesting schedules changed"
+		);
+		let now = T::BlockNumberProvider::current_block_number();
+		for (who, currency_id, schedules) in VestingSchedules::<T>::iter() {
+			let locked = schedules.iter().fold(BalanceOf::<T>::zero(), |acc, s| {
+				acc.saturating_add(s.locked_amount(now))
+			});
+			if locked.is_zero() {
+				continue;
+			}
+			// withdrawing any of the locked balance must fail
+			let free = T::MultiCurrency::free_balance(currency_id, &who);
+			ensure!(
+				free >= locked
+					&& T::MultiCurrency::ensure_can_withdraw(
+						currency_id,
+						&who,
+						free.saturating_sub(locked).saturating_add(One::one())
+					)
+					.is_err(),
+				"Vesting lock is not in effect"
+			);
+		}
+		Ok(())
+	}
+}
+
+/// Returns the number of `schedules` and their total amount.
+#[cfg(feature = "try-runtime")]
+fn summarize<T: Config, BlockNumber: AtLeast32BitUnsigned + Copy>(
+	schedules: impl Iterator<Item = VestingSchedule<BlockNumber, BalanceOf<T>, T::AccountId>>,
+) -> Result<(u32, BalanceOf<T>), sp_runtime::TryRuntimeError> {
+	schedules.try_fold((0, Zero::zero()), |(count, total), schedule| {
+		let amount = schedule
+			.total_amount()
+			.ok_or("Total amount of vesting schedule overflows")?;
+		Ok((count + 1, total.saturating_add(amount)))
+	})
+}
+
+/// Returns the schedules stored in `VestingSchedules` in the `Schedule` layout.
+#[cfg(feature = "try-runtime")]
+fn stored_schedules<T: Config, Schedule: Decode>() -> impl Iterator<Item = Schedule> {
+	VestingSchedules::<T>::iter_keys().flat_map(|(who, currency_id)| {
+		unhashed::get::<Vec<Schedule>>(&VestingSchedules::<T>::hashed_key_for(who, currency_id)).unwrap_or_default()
+	})
+}
+
+/// The graded schedule layout before `VestingSchedule` was versioned.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug)]
+pub(crate) struct OldVestingSchedule<BlockNumber, Balance: HasCompact> {
+	pub start: BlockNumber,
+	pub period: BlockNumber,
+	pub period_count: u32,
+	#[codec(compact)]
+	pub per_period: Balance,
+}
+
+pub(crate) type OldVestingScheduleOf<T, Blocks> =
+	OldVestingSchedule<<Blocks as VestingBlocks<T>>::BlockNumber, BalanceOf<T>>;
+
+/// The schedule layout before v3, measured in the block numbers of `Blocks`.
+pub(crate) type BlocksScheduleOf<T, Blocks> =
+	VestingSchedule<<Blocks as VestingBlocks<T>>::BlockNumber, BalanceOf<T>, <T as frame_system::Config>::AccountId>;
+
+impl<BlockNumber, Balance: MaxEncodedLen + HasCompact, AccountId> From<OldVestingSchedule<BlockNumber, Balance>>
+	for VestingSchedule<BlockNumber, Balance, AccountId>
+{
+	fn from(schedule: OldVestingSchedule<BlockNumber, Balance>) -> Self {
+		VestingSchedule::V1 {
+			start: schedule.start,
+			period: schedule.period,
+			period_count: schedule.period_count,
+			per_period: schedule.per_period,
+		}
+	}
+}
+
+pub(crate) mod v1 {
+	use super::*;
+
+	pub(crate) fn migrate<T: Config, Blocks: VestingBlocks<T>>(currency_id: CurrencyIdOf<T>) -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		let module_prefix = VestingSchedules::<T>::module_prefix();
+		let storage_prefix = VestingSchedules::<T>::storage_prefix();
+
+		weight.saturating_accrue(T::DbWeight::get().reads(1));
+
+		// the old layout was `map AccountId => Vec<VestingSchedule>` under the same
+		// prefix, drain it before writing the new keys
+		let old_data = storage_key_iter::<T::AccountId, Vec<OldVestingScheduleOf<T, Blocks>>, Blake2_128Concat>(
+			module_prefix,
+			storage_prefix,
+		)
+		.drain()
+		.collect::<Vec<_>>();
+
+		// the schedules are still in the old layout, they are translated by v2
+		for (who, schedules) in old_data {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+			unhashed::put(&VestingSchedules::<T>::hashed_key_for(who, currency_id), &schedules);
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		weight.saturating_accrue(T::DbWeight::get().writes(1));
+		weight
+	}
+}
+
+pub(crate) mod v2 {
+	use super::*;
+
+	pub(crate) fn migrate<T: Config, Blocks: VestingBlocks<T>>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		// the schedules are still measured in the block numbers of `Blocks`, they
+		// are converted by v3
+		for (who, currency_id) in VestingSchedules::<T>::iter_keys().collect::<Vec<_>>() {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			let key = VestingSchedules::<T>::hashed_key_for(who, currency_id);
+			if let Some(old_schedules) = unhashed::get::<Vec<OldVestingScheduleOf<T, Blocks>>>(&key) {
+				let schedules = old_schedules
+					.into_iter()
+					.map(BlocksScheduleOf::<T, Blocks>::from)
+					.collect::<Vec<_>>();
+				unhashed::put(&key, &schedules);
+			}
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+		weight.saturating_accrue(T::DbWeight::get().writes(1));
+		weight
+	}
+}
+
+pub(crate) mod v3 {
+	use super::*;
+
+	pub(crate) fn migrate<T: Config, Blocks: VestingBlocks<T>>() -> Weight {
+		let mut weight: Weight = Weight::zero();
+
+		VestingSchedules::<T>::translate::<Vec<BlocksScheduleOf<T, Blocks>>, _>(|_, _, old_schedules| {
+			weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			let schedules = old_schedules
+				.into_iter()
+				.map(to_moments::<T, Blocks>)
+				.collect::<Vec<_>>();
+			// the number of schedules is unchanged so the bound is always satisfied
+			Some(BoundedVec::truncate_from(schedules))
+		});
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+		weight.saturating_accrue(T::DbWeight::get().writes(1));
+		weight
+	}
+
+	/// Returns `schedule` measured in moments. Moments which overflow are
+	/// saturated, so the schedule never unlocks earlier than before.
+	fn to_moments<T: Config, Blocks: VestingBlocks<T>>(schedule: BlocksScheduleOf<T, Blocks>) -> VestingScheduleOf<T> {
+		let moment = |block| Blocks::moment_of(block).unwrap_or_else(Bounded::max_value);
+		let moments = |blocks| Blocks::moments_of(blocks).unwrap_or_else(Bounded::max_value);
+		match schedule {
+			VestingSchedule::V1 {
+				start,
+				period,
+				period_count,
+				per_period,
+			} => VestingSchedule::V1 {
+				start: moment(start),
+				period: moments(period),
+				period_count,
+				per_period,
+			},
+			VestingSchedule::V2 {
+				start,
+				cliff,
+				curve,
+				revoker,
+			} => {
+				let curve = match curve {
+					VestingCurve::Graded {
+						period,
+						period_count,
+						per_period,
+					} => VestingCurve::Graded {
+						period: moments(period),
+						period_count,
+						per_period,
+					},
+					VestingCurve::Linear { duration, total } => VestingCurve::Linear {
+						duration: moments(duration),
+						total,
+					},
+					VestingCurve::Milestones { total, milestones } => VestingCurve::Milestones {
+						total,
+						milestones: BoundedVec::truncate_from(
+							milestones
+								.into_iter()
+								.map(|(block, percent)| (moments(block), percent))
+								.collect::<Vec<_>>(),
+						),
+					},
+				};
+				VestingSchedule::V2 {
+					start: moment(start),
+					cliff: cliff.map(moment),
+					curve,
+					revoker,
+				}
+			}
+		}
+	}
+}
@@ -5,9 +5,11 @@
 use super::*;
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{ConstU32, ConstU64, EnsureOrigin, Everything},
+	traits::{ConstU32, ConstU64, EnsureOrigin, Everything, Nothing, Time, UnixTime},
 };
 use frame_system::RawOrigin;
+use orml_currencies::BasicCurrencyAdapter;
+use orml_traits::parameter_type_with_key;
 use sp_core::H256;
 use sp_runtime::{traits::IdentityLookup, BuildStorage};
 
@@ -41,6 +43,10 @@ impl frame_system::Config for Runtime {
 }
 
 type Balance = u64;
+pub type CurrencyId = u32;
+
+pub const NATIVE: CurrencyId = 0;
+pub const DOT: CurrencyId = 1;
 
 impl pallet_balances::Config for Runtime {
 	type Balance = Balance;
@@ -58,6 +64,56 @@ impl pallet_balances::Config for Runtime {
 	type MaxFreezes = ();
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		1
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type Amount = i64;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type CurrencyHooks = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type DustRemovalWhitelist = Nothing;
+	type RuntimeHoldReason = ();
+	type MaxHolds = ();
+	type RuntimeFreezeReason = ();
+	type MaxFreezes = ();
+	type Sufficiency = orml_tokens::AllSufficient;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransfersPerBatch = ();
+	type MaxIssuance = orml_tokens::NoIssuanceCap;
+	type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxSnapshots = ConstU32<10>;
+	type ApprovalDeposit = orml_tokens::NoApprovalDeposit;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkCurrencyId = ConstU32<DOT>;
+}
+
+parameter_types! {
+	pub const SwapReserveId: [u8; 8] = *b"orml/swp";
+}
+
+impl orml_currencies::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MultiCurrency = Tokens;
+	type NativeCurrency = BasicCurrencyAdapter<Runtime, PalletBalances, i64, u64>;
+	type GetNativeCurrencyId = ConstU32<NATIVE>;
+	type ExternalCurrency = Tokens;
+	type CurrencyBackend = orml_currencies::AllMultiCurrency;
+	type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+	type PauseWhitelist = Nothing;
+	type SwapReserveId = SwapReserveId;
+	type WeightInfo = ();
+}
+
 pub struct EnsureAliceOrBob;
 impl EnsureOrigin<RuntimeOrigin> for EnsureAliceOrBob {
 	type Success = AccountId;
@@ -80,6 +136,17 @@ impl EnsureOrigin<RuntimeOrigin> for EnsureAliceOrBob {
 
 parameter_types! {
 	pub static MockBlockNumberProvider: u64 = 0;
+	pub static RevokedSink: Option<AccountId> = None;
+	pub static MockTimestamp: Option<u64> = None;
+}
+
+parameter_type_with_key! {
+	pub MinVestedTransfer: |currency_id: CurrencyId| -> Balance {
+		match *currency_id {
+			DOT => 10,
+			_ => 5,
+		}
+	};
 }
 
 impl BlockNumberProvider for MockBlockNumberProvider {
@@ -90,14 +157,45 @@ impl BlockNumberProvider for MockBlockNumberProvider {
 	}
 }
 
+pub struct MockUnixTime;
+impl UnixTime for MockUnixTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_millis(MockTimestamp::get().unwrap_or_default())
+	}
+}
+impl Time for MockUnixTime {
+	type Moment = u64;
+
+	fn now() -> Self::Moment {
+		MockTimestamp::get().unwrap_or_default()
+	}
+}
+
+/// Measures vesting schedules in milliseconds if `MockTimestamp` is set, or in
+/// block numbers otherwise.
+pub struct MockClock;
+impl BlockNumberProvider for MockClock {
+	type BlockNumber = u64;
+
+	fn current_block_number() -> Self::BlockNumber {
+		if MockTimestamp::get().is_some() {
+			UnixTimeProvider::<MockUnixTime>::current_block_number()
+		} else {
+			MockBlockNumberProvider::current_block_number()
+		}
+	}
+}
+
 impl Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type Currency = PalletBalances;
-	type MinVestedTransfer = ConstU64<5>;
+	type MultiCurrency = Currencies;
+	type MinVestedTransfer = MinVestedTransfer;
 	type VestedTransferOrigin = EnsureAliceOrBob;
 	type WeightInfo = ();
 	type MaxVestingSchedules = ConstU32<2>;
-	type BlockNumberProvider = MockBlockNumberProvider;
+	type Moment = u64;
+	type BlockNumberProvider = MockClock;
+	type RevokedSink = RevokedSink;
 }
 
 type Block = frame_system::mocking::MockBlock<Runtime>;
@@ -107,12 +205,15 @@ construct_runtime!(
 		System: frame_system,
 		Vesting: vesting,
 		PalletBalances: pallet_balances,
+		Tokens: orml_tokens,
+		Currencies: orml_currencies,
 	}
 );
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
 pub const CHARLIE: AccountId = 3;
+pub const DAVE: AccountId = 4;
 
 pub const ALICE_BALANCE: u64 = 100;
 pub const CHARLIE_BALANCE: u64 = 50;
@@ -132,11 +233,17 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		orml_tokens::GenesisConfig::<Runtime> {
+			balances: vec![(ALICE, DOT, ALICE_BALANCE)],
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		vesting::GenesisConfig::<Runtime> {
 			vesting: vec![
-				// who, start, period, period_count, per_period
-				(CHARLIE, 2, 3, 1, 5),
-				(CHARLIE, 2 + 3, 3, 3, 5),
+				// who, currency_id, start, period, period_count, per_period
+				(CHARLIE, NATIVE, 2, 3, 1, 5),
+				(CHARLIE, NATIVE, 2 + 3, 3, 3, 5),
 			],
 		}
 		.assimilate_storage(&mut t)
@@ -3,7 +3,13 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok, error::BadOrigin};
+use crate::time::{block_to_moment, blocks_to_moments, moment_to_block, moments_to_blocks};
+use frame_support::{
+	assert_noop, assert_ok,
+	error::BadOrigin,
+	traits::{ConstU32, ConstU64, Currency, LockableCurrency, OnRuntimeUpgrade, WithdrawReasons},
+	StorageHasher,
+};
 use mock::*;
 use pallet_balances::{BalanceLock, Reasons};
 use sp_runtime::traits::Dispatchable;
@@ -21,15 +27,15 @@ fn vesting_from_chain_spec_works() {
 		assert!(PalletBalances::ensure_can_withdraw(&CHARLIE, 11, WithdrawReasons::TRANSFER, 19).is_err());
 
 		assert_eq!(
-			Vesting::vesting_schedules(&CHARLIE),
+			Vesting::vesting_schedules(&CHARLIE, NATIVE),
 			vec![
-				VestingSchedule {
+				VestingSchedule::V1 {
 					start: 2u64,
 					period: 3u64,
 					period_count: 1u32,
 					per_period: 5u64,
 				},
-				VestingSchedule {
+				VestingSchedule::V1 {
 					start: 2u64 + 3u64,
 					period: 3u64,
 					period_count: 3u32,
@@ -40,7 +46,7 @@ fn vesting_from_chain_spec_works() {
 
 		MockBlockNumberProvider::set(13);
 
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(CHARLIE)));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(CHARLIE), NATIVE));
 
 		assert_ok!(PalletBalances::ensure_can_withdraw(
 			&CHARLIE,
@@ -52,7 +58,7 @@ fn vesting_from_chain_spec_works() {
 
 		MockBlockNumberProvider::set(14);
 
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(CHARLIE)));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(CHARLIE), NATIVE));
 
 		assert_ok!(PalletBalances::ensure_can_withdraw(
 			&CHARLIE,
@@ -68,7 +74,7 @@ fn vested_transfer_works() {
 	ExtBuilder::build().execute_with(|| {
 		System::set_block_number(1);
 
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 1u32,
@@ -77,12 +83,14 @@ fn vested_transfer_works() {
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::signed(ALICE),
 			BOB,
+			NATIVE,
 			schedule.clone()
 		));
-		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule.clone()]);
+		assert_eq!(Vesting::vesting_schedules(&BOB, NATIVE), vec![schedule.clone()]);
 		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingScheduleAdded {
 			from: ALICE,
 			to: BOB,
+			currency_id: NATIVE,
 			vesting_schedule: schedule,
 		}));
 	});
@@ -93,14 +101,14 @@ fn self_vesting() {
 	ExtBuilder::build().execute_with(|| {
 		System::set_block_number(1);
 
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 1u32,
 			per_period: ALICE_BALANCE,
 		};
 
-		let bad_schedule = VestingSchedule {
+		let bad_schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 1u32,
@@ -108,20 +116,22 @@ fn self_vesting() {
 		};
 
 		assert_noop!(
-			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), ALICE, bad_schedule),
+			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), ALICE, NATIVE, bad_schedule),
 			crate::Error::<Runtime>::InsufficientBalanceToLock
 		);
 
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::signed(ALICE),
 			ALICE,
+			NATIVE,
 			schedule.clone()
 		));
 
-		assert_eq!(Vesting::vesting_schedules(&ALICE), vec![schedule.clone()]);
+		assert_eq!(Vesting::vesting_schedules(&ALICE, NATIVE), vec![schedule.clone()]);
 		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingScheduleAdded {
 			from: ALICE,
 			to: ALICE,
+			currency_id: NATIVE,
 			vesting_schedule: schedule,
 		}));
 	});
@@ -130,17 +140,22 @@ fn self_vesting() {
 #[test]
 fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 2u32,
 			per_period: 10u64,
 		};
-		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
 
 		MockBlockNumberProvider::set(12);
 
-		let another_schedule = VestingSchedule {
+		let another_schedule = VestingSchedule::V1 {
 			start: 10u64,
 			period: 13u64,
 			period_count: 1u32,
@@ -149,6 +164,7 @@ fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::signed(ALICE),
 			BOB,
+			NATIVE,
 			another_schedule
 		));
 
@@ -166,13 +182,18 @@ fn add_new_vesting_schedule_merges_with_current_locked_balance_and_until() {
 #[test]
 fn cannot_use_fund_if_not_claimed() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 10u64,
 			period: 10u64,
 			period_count: 1u32,
 			per_period: 50u64,
 		};
-		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
 		assert!(PalletBalances::ensure_can_withdraw(&BOB, 1, WithdrawReasons::TRANSFER, 49).is_err());
 	});
 }
@@ -180,25 +201,25 @@ fn cannot_use_fund_if_not_claimed() {
 #[test]
 fn vested_transfer_fails_if_zero_period_or_count() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 1u64,
 			period: 0u64,
 			period_count: 1u32,
 			per_period: 100u64,
 		};
 		assert_noop!(
-			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule),
+			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, NATIVE, schedule),
 			Error::<Runtime>::ZeroVestingPeriod
 		);
 
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 1u64,
 			period: 1u64,
 			period_count: 0u32,
 			per_period: 100u64,
 		};
 		assert_noop!(
-			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule),
+			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, NATIVE, schedule),
 			Error::<Runtime>::ZeroVestingPeriodCount
 		);
 	});
@@ -207,14 +228,14 @@ fn vested_transfer_fails_if_zero_period_or_count() {
 #[test]
 fn vested_transfer_fails_if_transfer_err() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 1u64,
 			period: 1u64,
 			period_count: 1u32,
 			per_period: 100u64,
 		};
 		assert_noop!(
-			Vesting::vested_transfer(RuntimeOrigin::signed(BOB), ALICE, schedule),
+			Vesting::vested_transfer(RuntimeOrigin::signed(BOB), ALICE, NATIVE, schedule),
 			TokenError::FundsUnavailable,
 		);
 	});
@@ -223,25 +244,25 @@ fn vested_transfer_fails_if_transfer_err() {
 #[test]
 fn vested_transfer_fails_if_overflow() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 1u64,
 			period: 1u64,
 			period_count: 2u32,
 			per_period: u64::MAX,
 		};
 		assert_noop!(
-			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule),
+			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, NATIVE, schedule),
 			ArithmeticError::Overflow,
 		);
 
-		let another_schedule = VestingSchedule {
+		let another_schedule = VestingSchedule::V1 {
 			start: u64::MAX,
 			period: 1u64,
 			period_count: 2u32,
 			per_period: 1u64,
 		};
 		assert_noop!(
-			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, another_schedule),
+			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, NATIVE, another_schedule),
 			ArithmeticError::Overflow,
 		);
 	});
@@ -250,14 +271,14 @@ fn vested_transfer_fails_if_overflow() {
 #[test]
 fn vested_transfer_fails_if_bad_origin() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 1u32,
 			per_period: 100u64,
 		};
 		assert_noop!(
-			Vesting::vested_transfer(RuntimeOrigin::signed(CHARLIE), BOB, schedule),
+			Vesting::vested_transfer(RuntimeOrigin::signed(CHARLIE), BOB, NATIVE, schedule),
 			BadOrigin
 		);
 	});
@@ -266,28 +287,33 @@ fn vested_transfer_fails_if_bad_origin() {
 #[test]
 fn claim_works() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 2u32,
 			per_period: 10u64,
 		};
-		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
 
 		MockBlockNumberProvider::set(11);
 		// remain locked if not claimed
 		assert!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 10).is_err());
 		// unlocked after claiming
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
-		assert!(VestingSchedules::<Runtime>::contains_key(BOB));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert!(VestingSchedules::<Runtime>::contains_key(BOB, NATIVE));
 		assert_ok!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 10));
 		// more are still locked
 		assert!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 1).is_err());
 
 		MockBlockNumberProvider::set(21);
 		// claim more
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
-		assert!(!VestingSchedules::<Runtime>::contains_key(BOB));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert!(!VestingSchedules::<Runtime>::contains_key(BOB, NATIVE));
 		assert_ok!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 10));
 		// all used up
 		assert_eq!(PalletBalances::free_balance(BOB), 0);
@@ -300,15 +326,20 @@ fn claim_works() {
 #[test]
 fn claim_for_works() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 2u32,
 			per_period: 10u64,
 		};
-		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
 
-		assert_ok!(Vesting::claim_for(RuntimeOrigin::signed(ALICE), BOB));
+		assert_ok!(Vesting::claim_for(RuntimeOrigin::signed(ALICE), BOB, NATIVE));
 
 		assert_eq!(
 			PalletBalances::locks(&BOB).get(0),
@@ -318,30 +349,35 @@ fn claim_for_works() {
 				reasons: Reasons::All,
 			})
 		);
-		assert!(VestingSchedules::<Runtime>::contains_key(&BOB));
+		assert!(VestingSchedules::<Runtime>::contains_key(&BOB, NATIVE));
 
 		MockBlockNumberProvider::set(21);
 
-		assert_ok!(Vesting::claim_for(RuntimeOrigin::signed(ALICE), BOB));
+		assert_ok!(Vesting::claim_for(RuntimeOrigin::signed(ALICE), BOB, NATIVE));
 
 		// no locks anymore
 		assert_eq!(PalletBalances::locks(&BOB), vec![]);
-		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB));
+		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB, NATIVE));
 	});
 }
 
 #[test]
 fn update_vesting_schedules_works() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 2u32,
 			per_period: 10u64,
 		};
-		assert_ok!(Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, schedule));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
 
-		let updated_schedule = VestingSchedule {
+		let updated_schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 20u64,
 			period_count: 2u32,
@@ -350,19 +386,20 @@ fn update_vesting_schedules_works() {
 		assert_ok!(Vesting::update_vesting_schedules(
 			RuntimeOrigin::root(),
 			BOB,
+			NATIVE,
 			vec![updated_schedule]
 		));
 
 		MockBlockNumberProvider::set(11);
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
 		assert!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 1).is_err());
 
 		MockBlockNumberProvider::set(21);
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
 		assert_ok!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 10));
 
 		// empty vesting schedules cleanup the storage and unlock the fund
-		assert!(VestingSchedules::<Runtime>::contains_key(BOB));
+		assert!(VestingSchedules::<Runtime>::contains_key(BOB, NATIVE));
 		assert_eq!(
 			PalletBalances::locks(&BOB).get(0),
 			Some(&BalanceLock {
@@ -371,8 +408,13 @@ fn update_vesting_schedules_works() {
 				reasons: Reasons::All,
 			})
 		);
-		assert_ok!(Vesting::update_vesting_schedules(RuntimeOrigin::root(), BOB, vec![]));
-		assert!(!VestingSchedules::<Runtime>::contains_key(BOB));
+		assert_ok!(Vesting::update_vesting_schedules(
+			RuntimeOrigin::root(),
+			BOB,
+			NATIVE,
+			vec![]
+		));
+		assert!(!VestingSchedules::<Runtime>::contains_key(BOB, NATIVE));
 		assert_eq!(PalletBalances::locks(&BOB), vec![]);
 	});
 }
@@ -388,14 +430,14 @@ fn update_vesting_schedules_fails_if_unexpected_existing_locks() {
 #[test]
 fn vested_transfer_check_for_min() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 1u64,
 			period: 1u64,
 			period_count: 1u32,
 			per_period: 3u64,
 		};
 		assert_noop!(
-			Vesting::vested_transfer(RuntimeOrigin::signed(BOB), ALICE, schedule),
+			Vesting::vested_transfer(RuntimeOrigin::signed(BOB), ALICE, NATIVE, schedule),
 			Error::<Runtime>::AmountLow
 		);
 	});
@@ -404,7 +446,7 @@ fn vested_transfer_check_for_min() {
 #[test]
 fn multiple_vesting_schedule_claim_works() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 2u32,
@@ -413,10 +455,11 @@ fn multiple_vesting_schedule_claim_works() {
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::signed(ALICE),
 			BOB,
+			NATIVE,
 			schedule.clone()
 		));
 
-		let schedule2 = VestingSchedule {
+		let schedule2 = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 3u32,
@@ -425,22 +468,26 @@ fn multiple_vesting_schedule_claim_works() {
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::signed(ALICE),
 			BOB,
+			NATIVE,
 			schedule2.clone()
 		));
 
-		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule, schedule2.clone()]);
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB, NATIVE),
+			vec![schedule, schedule2.clone()]
+		);
 
 		MockBlockNumberProvider::set(21);
 
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
 
-		assert_eq!(Vesting::vesting_schedules(&BOB), vec![schedule2]);
+		assert_eq!(Vesting::vesting_schedules(&BOB, NATIVE), vec![schedule2]);
 
 		MockBlockNumberProvider::set(31);
 
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
 
-		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB));
+		assert!(!VestingSchedules::<Runtime>::contains_key(&BOB, NATIVE));
 
 		assert_eq!(PalletBalances::locks(&BOB), vec![]);
 	});
@@ -449,7 +496,7 @@ fn multiple_vesting_schedule_claim_works() {
 #[test]
 fn exceeding_maximum_schedules_should_fail() {
 	ExtBuilder::build().execute_with(|| {
-		let schedule = VestingSchedule {
+		let schedule = VestingSchedule::V1 {
 			start: 0u64,
 			period: 10u64,
 			period_count: 2u32,
@@ -458,16 +505,19 @@ fn exceeding_maximum_schedules_should_fail() {
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::signed(ALICE),
 			BOB,
+			NATIVE,
 			schedule.clone()
 		));
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::signed(ALICE),
 			BOB,
+			NATIVE,
 			schedule.clone()
 		));
 
 		let create = RuntimeCall::Vesting(crate::Call::<Runtime>::vested_transfer {
 			dest: BOB,
+			currency_id: NATIVE,
 			schedule: schedule.clone(),
 		});
 		assert_noop!(
@@ -478,7 +528,7 @@ fn exceeding_maximum_schedules_should_fail() {
 		let schedules = vec![schedule.clone(), schedule.clone(), schedule];
 
 		assert_noop!(
-			Vesting::update_vesting_schedules(RuntimeOrigin::root(), BOB, schedules),
+			Vesting::update_vesting_schedules(RuntimeOrigin::root(), BOB, NATIVE, schedules),
 			Error::<Runtime>::MaxVestingSchedulesExceeded
 		);
 	});
@@ -490,7 +540,7 @@ fn cliff_vesting_works() {
 	const VESTING_PERIOD: u64 = 20;
 
 	ExtBuilder::build().execute_with(|| {
-		let cliff_schedule = VestingSchedule {
+		let cliff_schedule = VestingSchedule::V1 {
 			start: VESTING_PERIOD - 1,
 			period: 1,
 			period_count: 1,
@@ -507,6 +557,7 @@ fn cliff_vesting_works() {
 		assert_ok!(Vesting::vested_transfer(
 			RuntimeOrigin::signed(ALICE),
 			BOB,
+			NATIVE,
 			cliff_schedule
 		));
 		assert_eq!(PalletBalances::free_balance(BOB), VESTING_AMOUNT);
@@ -514,7 +565,7 @@ fn cliff_vesting_works() {
 
 		for i in 1..VESTING_PERIOD {
 			MockBlockNumberProvider::set(i);
-			assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+			assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
 			assert_eq!(PalletBalances::free_balance(BOB), VESTING_AMOUNT);
 			assert_eq!(PalletBalances::locks(&BOB), vec![balance_lock.clone()]);
 			assert_noop!(
@@ -524,7 +575,7 @@ fn cliff_vesting_works() {
 		}
 
 		MockBlockNumberProvider::set(VESTING_PERIOD);
-		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB)));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
 		assert!(PalletBalances::locks(&BOB).is_empty());
 		assert_ok!(PalletBalances::transfer(
 			RuntimeOrigin::signed(BOB),
@@ -533,3 +584,1119 @@ fn cliff_vesting_works() {
 		));
 	});
 }
+
+#[test]
+fn multi_currency_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		let native_schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		let dot_schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 20u64,
+			period_count: 1u32,
+			per_period: 30u64,
+		};
+
+		// the minimum vested transfer is per currency
+		assert_noop!(
+			Vesting::vested_transfer(
+				RuntimeOrigin::signed(ALICE),
+				BOB,
+				DOT,
+				VestingSchedule::V1 {
+					start: 0u64,
+					period: 10u64,
+					period_count: 1u32,
+					per_period: 5u64,
+				}
+			),
+			Error::<Runtime>::AmountLow
+		);
+
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			native_schedule.clone()
+		));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			DOT,
+			dot_schedule.clone()
+		));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingScheduleAdded {
+			from: ALICE,
+			to: BOB,
+			currency_id: DOT,
+			vesting_schedule: dot_schedule.clone(),
+		}));
+
+		assert_eq!(Vesting::vesting_schedules(&BOB, NATIVE), vec![native_schedule]);
+		assert_eq!(Vesting::vesting_schedules(&BOB, DOT), vec![dot_schedule]);
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 20);
+		assert_eq!(Tokens::locks(BOB, DOT)[0].amount, 30);
+
+		MockBlockNumberProvider::set(11);
+
+		// claiming a currency leaves the other one untouched
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), DOT));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::Claimed {
+			who: BOB,
+			currency_id: DOT,
+			amount: 30,
+		}));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 20);
+		assert_eq!(Tokens::locks(BOB, DOT)[0].amount, 30);
+
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 10);
+		assert_eq!(Tokens::locks(BOB, DOT)[0].amount, 30);
+
+		MockBlockNumberProvider::set(21);
+
+		assert_ok!(Vesting::claim_for(RuntimeOrigin::signed(ALICE), BOB, DOT));
+		assert!(Tokens::locks(BOB, DOT).is_empty());
+		assert!(!VestingSchedules::<Runtime>::contains_key(BOB, DOT));
+		assert!(VestingSchedules::<Runtime>::contains_key(BOB, NATIVE));
+		assert_ok!(Tokens::transfer(RuntimeOrigin::signed(BOB), ALICE, DOT, 30));
+		assert!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), ALICE, 20).is_err());
+	});
+}
+
+#[test]
+fn migrate_to_multi_currency_works() {
+	ExtBuilder::build().execute_with(|| {
+		// start from the single-currency layout
+		let _ = VestingSchedules::<Runtime>::clear(u32::MAX, None);
+		StorageVersion::new(0).put::<Pallet<Runtime>>();
+
+		let old_schedule = migrations::OldVestingSchedule {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		frame_support::storage::migration::put_storage_value(
+			b"Vesting",
+			b"VestingSchedules",
+			&Blake2_128Concat::hash(&BOB.encode()),
+			vec![old_schedule.clone()],
+		);
+		frame_support::storage::migration::put_storage_value(
+			b"Vesting",
+			b"VestingSchedules",
+			&Blake2_128Concat::hash(&CHARLIE.encode()),
+			vec![old_schedule.clone(), old_schedule],
+		);
+
+		crate::Migration::<Runtime, ConstU32<NATIVE>>::on_runtime_upgrade();
+
+		// the legacy schedules are readable as `V1`
+		let schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_eq!(StorageVersion::get::<Pallet<Runtime>>(), 3);
+		assert_eq!(Vesting::vesting_schedules(&BOB, NATIVE), vec![schedule.clone()]);
+		assert_eq!(
+			Vesting::vesting_schedules(&CHARLIE, NATIVE),
+			vec![schedule.clone(), schedule]
+		);
+		assert_eq!(VestingSchedules::<Runtime>::iter().count(), 2);
+
+		// the migration runs only once
+		assert_eq!(
+			crate::Migration::<Runtime, ConstU32<NATIVE>>::on_runtime_upgrade(),
+			Weight::zero()
+		);
+	});
+}
+
+#[test]
+fn migrate_to_moments_works() {
+	ExtBuilder::build().execute_with(|| {
+		// start from schedules measured in block numbers
+		let graded = VestingSchedule::V1 {
+			start: 95u64,
+			period: 5u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		let milestones = VestingSchedule::V2 {
+			start: 90u64,
+			cliff: Some(110u64),
+			curve: VestingCurve::Milestones {
+				total: 20u64,
+				milestones: BoundedVec::truncate_from(vec![
+					(20u64, Percent::from_percent(50)),
+					(40u64, Percent::from_percent(100)),
+				]),
+			},
+			revoker: Some(ALICE),
+		};
+		let _ = VestingSchedules::<Runtime>::clear(u32::MAX, None);
+		VestingSchedules::<Runtime>::insert(
+			BOB,
+			NATIVE,
+			BoundedVec::truncate_from(vec![graded.clone(), milestones.clone()]),
+		);
+		StorageVersion::new(2).put::<Pallet<Runtime>>();
+
+		// block 100 is at 1_000_000 ms, with 6_000 ms per block
+		MockBlockNumberProvider::set(100);
+		MockTimestamp::set(Some(1_000_000));
+		type ToMoments = BlockTime<MockBlockNumberProvider, ConstU64<6_000>>;
+		crate::Migration::<Runtime, ConstU32<NATIVE>, ToMoments>::on_runtime_upgrade();
+
+		let converted_graded = VestingSchedule::V1 {
+			start: 970_000u64,
+			period: 30_000u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		let converted_milestones = VestingSchedule::V2 {
+			start: 940_000u64,
+			cliff: Some(1_060_000u64),
+			curve: VestingCurve::Milestones {
+				total: 20u64,
+				milestones: BoundedVec::truncate_from(vec![
+					(120_000u64, Percent::from_percent(50)),
+					(240_000u64, Percent::from_percent(100)),
+				]),
+			},
+			revoker: Some(ALICE),
+		};
+		assert_eq!(StorageVersion::get::<Pallet<Runtime>>(), 3);
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB, NATIVE),
+			vec![converted_graded.clone(), converted_milestones.clone()]
+		);
+
+		// the converted schedules lock as much at the moments of the blocks
+		for block in 90..=140 {
+			let moment = 400_000 + block * 6_000;
+			assert_eq!(converted_graded.locked_amount(moment), graded.locked_amount(block));
+			assert_eq!(
+				converted_milestones.locked_amount(moment),
+				milestones.locked_amount(block)
+			);
+		}
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migrate_to_multi_currency_checks_locks() {
+	let setup = || {
+		let schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
+
+		// move the schedules back to the single-currency layout
+		let old_data = VestingSchedules::<Runtime>::drain().collect::<Vec<_>>();
+		for (who, _, schedules) in old_data {
+			let old_schedules = schedules
+				.into_iter()
+				.map(|schedule| match schedule {
+					VestingSchedule::V1 {
+						start,
+						period,
+						period_count,
+						per_period,
+					} => migrations::OldVestingSchedule {
+						start,
+						period,
+						period_count,
+						per_period,
+					},
+					_ => unreachable!(),
+				})
+				.collect::<Vec<_>>();
+			frame_support::storage::migration::put_storage_value(
+				b"Vesting",
+				b"VestingSchedules",
+				&Blake2_128Concat::hash(&who.encode()),
+				old_schedules,
+			);
+		}
+		StorageVersion::new(0).put::<Pallet<Runtime>>();
+	};
+
+	ExtBuilder::build().execute_with(|| {
+		setup();
+		let state = crate::Migration::<Runtime, ConstU32<NATIVE>>::pre_upgrade().unwrap();
+		crate::Migration::<Runtime, ConstU32<NATIVE>>::on_runtime_upgrade();
+		assert_ok!(crate::Migration::<Runtime, ConstU32<NATIVE>>::post_upgrade(state));
+	});
+
+	// the locks are in the native currency
+	ExtBuilder::build().execute_with(|| {
+		setup();
+		let state = crate::Migration::<Runtime, ConstU32<DOT>>::pre_upgrade().unwrap();
+		crate::Migration::<Runtime, ConstU32<DOT>>::on_runtime_upgrade();
+		assert!(crate::Migration::<Runtime, ConstU32<DOT>>::post_upgrade(state).is_err());
+	});
+}
+
+#[test]
+fn cliff_and_linear_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule::V2 {
+			start: 10u64,
+			cliff: Some(20u64),
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 40u64,
+			},
+			revoker: None,
+		};
+		assert_eq!(schedule.end(), Some(50));
+		assert_eq!(schedule.total_amount(), Some(40));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
+
+		// nothing is unlocked before the cliff
+		MockBlockNumberProvider::set(19);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 40);
+
+		// the amount released since start is unlocked at once at the cliff
+		MockBlockNumberProvider::set(20);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 30);
+
+		// then unlocked per block
+		MockBlockNumberProvider::set(31);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 19);
+
+		MockBlockNumberProvider::set(50);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert!(PalletBalances::locks(&BOB).is_empty());
+		assert!(!VestingSchedules::<Runtime>::contains_key(BOB, NATIVE));
+	});
+}
+
+#[test]
+fn milestones_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule::V2 {
+			start: 10u64,
+			cliff: None,
+			curve: VestingCurve::Milestones {
+				total: 50u64,
+				milestones: vec![
+					(10u64, Percent::from_percent(20)),
+					(20u64, Percent::from_percent(50)),
+					(30u64, Percent::from_percent(100)),
+				]
+				.try_into()
+				.unwrap(),
+			},
+			revoker: None,
+		};
+		assert_eq!(schedule.end(), Some(40));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule.clone()
+		));
+
+		assert_eq!(schedule.locked_amount(19), 50);
+		assert_eq!(schedule.locked_amount(20), 40);
+		assert_eq!(schedule.locked_amount(35), 25);
+		assert_eq!(schedule.locked_amount(40), 0);
+
+		MockBlockNumberProvider::set(35);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 25);
+
+		MockBlockNumberProvider::set(40);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert!(PalletBalances::locks(&BOB).is_empty());
+	});
+}
+
+#[test]
+fn cliff_extends_schedule_end() {
+	let schedule: VestingScheduleOf<Runtime> = VestingSchedule::V2 {
+		start: 0u64,
+		cliff: Some(30u64),
+		curve: VestingCurve::Graded {
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		},
+		revoker: None,
+	};
+	assert_eq!(schedule.end(), Some(30));
+	assert_eq!(schedule.total_amount(), Some(20));
+	assert_eq!(schedule.locked_amount(29), 20);
+	assert_eq!(schedule.locked_amount(30), 0);
+}
+
+#[test]
+fn vested_transfer_fails_if_invalid_curve() {
+	ExtBuilder::build().execute_with(|| {
+		let linear = VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 0u64,
+				total: 10u64,
+			},
+			revoker: None,
+		};
+		assert_noop!(
+			Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, NATIVE, linear),
+			Error::<Runtime>::ZeroVestingPeriod
+		);
+
+		let milestones = |milestones: Vec<(u64, u8)>| VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Milestones {
+				total: 10u64,
+				milestones: milestones
+					.into_iter()
+					.map(|(block, percent)| (block, Percent::from_percent(percent)))
+					.collect::<Vec<_>>()
+					.try_into()
+					.unwrap(),
+			},
+			revoker: None,
+		};
+		for invalid in [
+			vec![],
+			vec![(10, 50)],
+			vec![(10, 50), (10, 100)],
+			vec![(10, 60), (20, 50), (30, 100)],
+		] {
+			assert_noop!(
+				Vesting::vested_transfer(RuntimeOrigin::signed(ALICE), BOB, NATIVE, milestones(invalid)),
+				Error::<Runtime>::InvalidMilestones
+			);
+		}
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			milestones(vec![(0, 50), (10, 100)])
+		));
+	});
+}
+
+#[test]
+fn revoke_schedule_works() {
+	ExtBuilder::build().execute_with(|| {
+		let grant = VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 40u64,
+			},
+			revoker: Some(ALICE),
+		};
+		let schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			grant
+		));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule.clone()
+		));
+		assert_eq!(PalletBalances::free_balance(ALICE), 40);
+
+		MockBlockNumberProvider::set(10);
+
+		assert_ok!(Vesting::revoke_schedule(RuntimeOrigin::signed(ALICE), BOB, NATIVE, 0));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingRevoked {
+			who: BOB,
+			currency_id: NATIVE,
+			revoker: ALICE,
+			recipient: ALICE,
+			amount: 30,
+		}));
+
+		// the unvested balance is returned, the vested one is unlocked
+		assert_eq!(Vesting::vesting_schedules(&BOB, NATIVE), vec![schedule]);
+		assert_eq!(PalletBalances::free_balance(ALICE), 70);
+		assert_eq!(PalletBalances::free_balance(BOB), 30);
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 10);
+		assert_ok!(<Currencies as MultiCurrency<AccountId>>::ensure_can_withdraw(
+			NATIVE, &BOB, 20
+		));
+		assert!(<Currencies as MultiCurrency<AccountId>>::ensure_can_withdraw(NATIVE, &BOB, 21).is_err());
+	});
+}
+
+#[test]
+fn revoke_schedule_to_sink_works() {
+	ExtBuilder::build().execute_with(|| {
+		RevokedSink::set(Some(CHARLIE));
+
+		let grant = VestingSchedule::V2 {
+			start: 10u64,
+			cliff: Some(20u64),
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 40u64,
+			},
+			revoker: Some(ALICE),
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			grant
+		));
+
+		// nothing is vested before the cliff
+		MockBlockNumberProvider::set(15);
+
+		assert_ok!(Vesting::revoke_schedule(RuntimeOrigin::signed(ALICE), BOB, NATIVE, 0));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingRevoked {
+			who: BOB,
+			currency_id: NATIVE,
+			revoker: ALICE,
+			recipient: CHARLIE,
+			amount: 40,
+		}));
+
+		assert!(!VestingSchedules::<Runtime>::contains_key(BOB, NATIVE));
+		assert!(PalletBalances::locks(&BOB).is_empty());
+		assert_eq!(PalletBalances::free_balance(BOB), 0);
+		assert_eq!(PalletBalances::free_balance(ALICE), 60);
+		assert_eq!(PalletBalances::free_balance(CHARLIE), CHARLIE_BALANCE + 40);
+	});
+}
+
+#[test]
+fn revoke_schedule_fails_if_not_revoker() {
+	ExtBuilder::build().execute_with(|| {
+		let grant = VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 40u64,
+			},
+			revoker: Some(ALICE),
+		};
+		let schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			grant
+		));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
+
+		assert_noop!(
+			Vesting::revoke_schedule(RuntimeOrigin::signed(BOB), BOB, NATIVE, 0),
+			Error::<Runtime>::NotRevoker
+		);
+		// schedules without a revoker are not revocable
+		assert_noop!(
+			Vesting::revoke_schedule(RuntimeOrigin::signed(ALICE), BOB, NATIVE, 1),
+			Error::<Runtime>::NotRevoker
+		);
+		assert_noop!(
+			Vesting::revoke_schedule(RuntimeOrigin::signed(ALICE), BOB, NATIVE, 2),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+		assert_noop!(
+			Vesting::revoke_schedule(RuntimeOrigin::signed(ALICE), BOB, DOT, 0),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+	});
+}
+
+#[test]
+fn merge_schedules_works() {
+	ExtBuilder::build().execute_with(|| {
+		let graded = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		let linear = VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 40u64,
+			},
+			revoker: None,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			graded.clone()
+		));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			linear.clone()
+		));
+
+		MockBlockNumberProvider::set(10);
+
+		assert_ok!(Vesting::merge_schedules(RuntimeOrigin::signed(BOB), NATIVE, 1, 0));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingSchedulesMerged {
+			who: BOB,
+			currency_id: NATIVE,
+		}));
+
+		let schedules = Vesting::vesting_schedules(&BOB, NATIVE);
+		assert_eq!(schedules.len(), 1);
+		let merged = schedules[0].clone();
+		assert_eq!(merged.total_amount(), Some(40));
+		assert_eq!(merged.end(), Some(40));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 40);
+
+		// never unlocks faster than the original schedules
+		for time in 10..=45 {
+			assert!(merged.locked_amount(time) >= graded.locked_amount(time) + linear.locked_amount(time));
+		}
+		// but the sampled milestones, rounded down to whole percents, lag behind
+		for (time, locked, original) in [
+			(10, 40, 40),
+			(12, 40, 38),
+			(19, 32, 31),
+			(20, 32, 20),
+			(21, 20, 19),
+			(25, 16, 15),
+			(30, 10, 10),
+			(39, 2, 1),
+			(40, 0, 0),
+		] {
+			assert_eq!(merged.locked_amount(time), locked);
+			assert_eq!(graded.locked_amount(time) + linear.locked_amount(time), original);
+		}
+
+		MockBlockNumberProvider::set(40);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert!(PalletBalances::locks(&BOB).is_empty());
+	});
+}
+
+#[test]
+fn merge_schedules_of_same_curve_works() {
+	ExtBuilder::build().execute_with(|| {
+		let graded = |per_period| VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period,
+		};
+		let milestones = |total| VestingSchedule::V2 {
+			start: 0u64,
+			cliff: Some(10u64),
+			curve: VestingCurve::Milestones {
+				total,
+				milestones: BoundedVec::truncate_from(vec![
+					(20u64, Percent::from_percent(50)),
+					(40u64, Percent::from_percent(100)),
+				]),
+			},
+			revoker: None,
+		};
+
+		MockBlockNumberProvider::set(5);
+
+		for (first, second) in [(graded(10), graded(5)), (milestones(40), milestones(20))] {
+			assert_ok!(Vesting::vested_transfer(
+				RuntimeOrigin::signed(ALICE),
+				BOB,
+				NATIVE,
+				first
+			));
+			assert_ok!(Vesting::vested_transfer(
+				RuntimeOrigin::signed(ALICE),
+				BOB,
+				NATIVE,
+				second
+			));
+			assert_ok!(Vesting::merge_schedules(RuntimeOrigin::signed(BOB), NATIVE, 0, 1));
+		}
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB, NATIVE),
+			vec![graded(15), milestones(60)]
+		);
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 90);
+
+		// merged schedules release exactly as the original ones
+		for (time, graded_locked, milestones_locked) in [
+			(9, 30, 60),
+			(10, 15, 60),
+			(19, 15, 60),
+			(20, 0, 30),
+			(39, 0, 30),
+			(40, 0, 0),
+		] {
+			assert_eq!(graded(15).locked_amount(time), graded_locked);
+			assert_eq!(
+				graded(10).locked_amount(time) + graded(5).locked_amount(time),
+				graded_locked
+			);
+			assert_eq!(milestones(60).locked_amount(time), milestones_locked);
+			assert_eq!(
+				milestones(40).locked_amount(time) + milestones(20).locked_amount(time),
+				milestones_locked
+			);
+		}
+	});
+}
+
+#[test]
+fn merge_schedules_of_uneven_amounts_never_unlocks_faster() {
+	ExtBuilder::build().execute_with(|| {
+		let linear = |total| VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear { duration: 40u64, total },
+			revoker: None,
+		};
+		let milestones = |total| VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Milestones {
+				total,
+				milestones: BoundedVec::truncate_from(vec![
+					(20u64, Percent::from_percent(50)),
+					(40u64, Percent::from_percent(100)),
+				]),
+			},
+			revoker: None,
+		};
+
+		MockBlockNumberProvider::set(5);
+
+		// linear schedules are never summed, and half of 21 and of 11 round down
+		// to 15 together but half of 32 to 16, so these milestones can't be either
+		for (who, first, second) in [(BOB, linear(21), linear(11)), (DAVE, milestones(21), milestones(11))] {
+			assert_ok!(Vesting::vested_transfer(
+				RuntimeOrigin::signed(ALICE),
+				who,
+				NATIVE,
+				first.clone()
+			));
+			assert_ok!(Vesting::vested_transfer(
+				RuntimeOrigin::signed(ALICE),
+				who,
+				NATIVE,
+				second.clone()
+			));
+			assert_ok!(Vesting::merge_schedules(RuntimeOrigin::signed(who), NATIVE, 0, 1));
+
+			let merged = Vesting::vesting_schedules(&who, NATIVE)[0].clone();
+			assert!(matches!(
+				merged,
+				VestingSchedule::V2 {
+					start: 5,
+					curve: VestingCurve::Milestones { .. },
+					..
+				}
+			));
+			assert_eq!(
+				PalletBalances::locks(&who)[0].amount,
+				first.locked_amount(5) + second.locked_amount(5)
+			);
+			for time in 5..=40 {
+				assert!(merged.locked_amount(time) >= first.locked_amount(time) + second.locked_amount(time));
+			}
+		}
+	});
+}
+
+#[test]
+fn merge_schedules_fails_if_invalid() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 2u32,
+			per_period: 10u64,
+		};
+		let revocable = VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 40u64,
+			},
+			revoker: Some(ALICE),
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			revocable
+		));
+
+		assert_noop!(
+			Vesting::merge_schedules(RuntimeOrigin::signed(BOB), NATIVE, 0, 0),
+			Error::<Runtime>::SameVestingSchedule
+		);
+		assert_noop!(
+			Vesting::merge_schedules(RuntimeOrigin::signed(BOB), NATIVE, 0, 2),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+		assert_noop!(
+			Vesting::merge_schedules(RuntimeOrigin::signed(BOB), NATIVE, 0, 1),
+			Error::<Runtime>::RevokerMismatch
+		);
+	});
+}
+
+#[test]
+fn transfer_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		let grant = VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 40u64,
+			},
+			revoker: Some(ALICE),
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			grant.clone()
+		));
+
+		MockBlockNumberProvider::set(10);
+
+		assert_ok!(Vesting::transfer_vesting(
+			RuntimeOrigin::signed(BOB),
+			DAVE,
+			NATIVE,
+			0,
+			15
+		));
+		System::assert_last_event(RuntimeEvent::Vesting(crate::Event::VestingTransferred {
+			from: BOB,
+			to: DAVE,
+			currency_id: NATIVE,
+			amount: 15,
+		}));
+
+		// both parts are released by the same curve and keep the revoker
+		let half = VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 20u64,
+			},
+			revoker: Some(ALICE),
+		};
+		assert_eq!(Vesting::vesting_schedules(&BOB, NATIVE), vec![half.clone()]);
+		assert_eq!(Vesting::vesting_schedules(&DAVE, NATIVE), vec![half.clone()]);
+		assert_eq!(PalletBalances::free_balance(BOB), 25);
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 15);
+		assert_eq!(PalletBalances::free_balance(DAVE), 15);
+		assert_eq!(PalletBalances::locks(&DAVE)[0].amount, 15);
+
+		for time in 10..=40 {
+			assert!(half.locked_amount(time) * 2 >= grant.locked_amount(time));
+		}
+
+		MockBlockNumberProvider::set(40);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(DAVE), NATIVE));
+		assert!(PalletBalances::locks(&BOB).is_empty());
+		assert!(PalletBalances::locks(&DAVE).is_empty());
+	});
+}
+
+#[test]
+fn split_schedule_works() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 4u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
+
+		MockBlockNumberProvider::set(10);
+
+		// transferring to the caller splits the schedule, the split part is
+		// rounded up
+		assert_ok!(Vesting::transfer_vesting(
+			RuntimeOrigin::signed(BOB),
+			BOB,
+			NATIVE,
+			0,
+			10
+		));
+		assert_eq!(
+			Vesting::vesting_schedules(&BOB, NATIVE),
+			vec![
+				VestingSchedule::V1 {
+					start: 0u64,
+					period: 10u64,
+					period_count: 4u32,
+					per_period: 6u64,
+				},
+				VestingSchedule::V1 {
+					start: 0u64,
+					period: 10u64,
+					period_count: 4u32,
+					per_period: 4u64,
+				},
+			]
+		);
+		assert_eq!(PalletBalances::free_balance(BOB), 40);
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 30);
+	});
+}
+
+#[test]
+fn transfer_vesting_keeps_the_lock_of_the_sender() {
+	ExtBuilder::build().execute_with(|| {
+		let grant = VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 40u64,
+				total: 41u64,
+			},
+			revoker: None,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			grant.clone()
+		));
+
+		// BOB spends what was released, only the locked balance is left
+		MockBlockNumberProvider::set(10);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(grant.locked_amount(10), 31);
+		assert_ok!(PalletBalances::transfer(RuntimeOrigin::signed(BOB), CHARLIE, 10));
+		assert_eq!(PalletBalances::free_balance(BOB), 31);
+
+		// splitting the amounts alone would lock 17 and 15, one more than 31
+		assert_ok!(Vesting::transfer_vesting(
+			RuntimeOrigin::signed(BOB),
+			DAVE,
+			NATIVE,
+			0,
+			15
+		));
+		let part = |total| VestingSchedule::V2 {
+			start: 0u64,
+			cliff: None,
+			curve: VestingCurve::Linear { duration: 40u64, total },
+			revoker: None,
+		};
+		assert_eq!(Vesting::vesting_schedules(&BOB, NATIVE), vec![part(21)]);
+		assert_eq!(Vesting::vesting_schedules(&DAVE, NATIVE), vec![part(20)]);
+		assert_eq!(PalletBalances::free_balance(BOB), 16);
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 16);
+		assert_eq!(PalletBalances::free_balance(DAVE), 15);
+		assert_eq!(PalletBalances::locks(&DAVE)[0].amount, 15);
+
+		for time in 10..=40 {
+			assert!(part(21).locked_amount(time) + part(20).locked_amount(time) >= grant.locked_amount(time));
+		}
+	});
+}
+
+#[test]
+fn transfer_vesting_fails_if_invalid() {
+	ExtBuilder::build().execute_with(|| {
+		let schedule = VestingSchedule::V1 {
+			start: 0u64,
+			period: 10u64,
+			period_count: 4u32,
+			per_period: 10u64,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
+
+		MockBlockNumberProvider::set(10);
+
+		assert_noop!(
+			Vesting::transfer_vesting(RuntimeOrigin::signed(BOB), DAVE, NATIVE, 1, 10),
+			Error::<Runtime>::VestingScheduleNotFound
+		);
+		assert_noop!(
+			Vesting::transfer_vesting(RuntimeOrigin::signed(BOB), DAVE, NATIVE, 0, 31),
+			Error::<Runtime>::InsufficientLockedBalance
+		);
+		assert_noop!(
+			Vesting::transfer_vesting(RuntimeOrigin::signed(BOB), DAVE, NATIVE, 0, 1),
+			Error::<Runtime>::AmountLow
+		);
+		// CHARLIE already has the maximum vesting schedules
+		let transfer = RuntimeCall::Vesting(crate::Call::<Runtime>::transfer_vesting {
+			dest: CHARLIE,
+			currency_id: NATIVE,
+			index: 0,
+			amount: 10,
+		});
+		assert_noop!(
+			transfer.dispatch(RuntimeOrigin::signed(BOB)),
+			Error::<Runtime>::MaxVestingSchedulesExceeded
+		);
+	});
+}
+
+#[test]
+fn timestamp_based_vesting_works() {
+	ExtBuilder::build().execute_with(|| {
+		MockTimestamp::set(Some(1_000_000));
+
+		// released linearly over 60 seconds
+		let schedule = VestingSchedule::V2 {
+			start: 1_000_000u64,
+			cliff: None,
+			curve: VestingCurve::Linear {
+				duration: 60_000u64,
+				total: 60u64,
+			},
+			revoker: None,
+		};
+		assert_ok!(Vesting::vested_transfer(
+			RuntimeOrigin::signed(ALICE),
+			BOB,
+			NATIVE,
+			schedule
+		));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 60);
+
+		let next_block = |block_time: u64| {
+			MockBlockNumberProvider::set(MockBlockNumberProvider::get() + 1);
+			MockTimestamp::set(MockTimestamp::get().map(|now| now + block_time));
+		};
+
+		// 5 blocks of 6 seconds
+		for _ in 0..5 {
+			next_block(6_000);
+		}
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 30);
+
+		// the block time jumps to 12 seconds, the release follows the wall clock
+		next_block(12_000);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 18);
+
+		next_block(12_000);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert_eq!(PalletBalances::locks(&BOB)[0].amount, 6);
+
+		// fully released after 60 seconds, only 8 blocks later
+		next_block(12_000);
+		assert_eq!(MockBlockNumberProvider::get(), 8);
+		assert_ok!(Vesting::claim(RuntimeOrigin::signed(BOB), NATIVE));
+		assert!(PalletBalances::locks(&BOB).is_empty());
+		assert!(!VestingSchedules::<Runtime>::contains_key(BOB, NATIVE));
+	});
+}
+
+#[test]
+fn time_providers_work() {
+	ExtBuilder::build().execute_with(|| {
+		assert_eq!(UnixTimeProvider::<MockUnixTime>::current_block_number(), 0);
+		assert_eq!(TimeProvider::<MockUnixTime>::current_block_number(), 0);
+
+		MockTimestamp::set(Some(1_500));
+		assert_eq!(UnixTimeProvider::<MockUnixTime>::current_block_number(), 1_500);
+		assert_eq!(TimeProvider::<MockUnixTime>::current_block_number(), 1_500);
+	});
+}
+
+#[test]
+fn time_conversion_works() {
+	assert_eq!(blocks_to_moments(10u32, 6_000u64), Some(60_000));
+	assert_eq!(blocks_to_moments(u32::MAX, u64::MAX), None);
+	assert_eq!(blocks_to_moments(u64::MAX, 1u32), None);
+
+	// rounded up
+	assert_eq!(moments_to_blocks(60_000u64, 6_000u64), Some(10u32));
+	assert_eq!(moments_to_blocks(60_001u64, 6_000u64), Some(11u32));
+	assert_eq!(moments_to_blocks(u64::MAX, 1u64), None::<u32>);
+	assert_eq!(moments_to_blocks(60_000u64, 0u64), None::<u32>);
+
+	assert_eq!(block_to_moment(15u32, 10, 1_000_000u64, 6_000), Some(1_030_000));
+	assert_eq!(block_to_moment(5u32, 10, 1_000_000u64, 6_000), Some(970_000));
+	assert_eq!(block_to_moment(0u32, 10, 1_000u64, 6_000), None);
+
+	// rounded up
+	assert_eq!(moment_to_block(1_030_000u64, 1_000_000, 10u32, 6_000), Some(15));
+	assert_eq!(moment_to_block(1_030_001u64, 1_000_000, 10u32, 6_000), Some(16));
+	assert_eq!(moment_to_block(970_000u64, 1_000_000, 10u32, 6_000), Some(5));
+	assert_eq!(moment_to_block(970_001u64, 1_000_000, 10u32, 6_000), Some(6));
+	assert_eq!(moment_to_block(0u64, 1_000_000, 10u32, 6_000), None);
+	assert_eq!(moment_to_block(1_030_000u64, 1_000_000, 10u32, 0), None);
+}
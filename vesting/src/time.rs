@@ -0,0 +1,98 @@
+//! Time providers and conversion helpers for vesting schedules measured in
+//! wall-clock moments.
+
+use frame_support::traits::{Time, UnixTime};
+use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumberProvider, One, UniqueSaturatedInto, Zero};
+use sp_std::marker::PhantomData;
+
+/// Provides the current `UnixTime` in milliseconds as the time of vesting
+/// schedules.
+///
+/// It implements `BlockNumberProvider` to be used as
+/// `Config::BlockNumberProvider`, but the provided number is a timestamp.
+pub struct UnixTimeProvider<T>(PhantomData<T>);
+impl<T: UnixTime> BlockNumberProvider for UnixTimeProvider<T> {
+	type BlockNumber = u64;
+
+	fn current_block_number() -> Self::BlockNumber {
+		T::now().as_millis().unique_saturated_into()
+	}
+}
+
+/// Provides the current moment of `Time` as the time of vesting schedules.
+///
+/// Like `UnixTimeProvider`, the provided number is a moment rather than a block
+/// number.
+pub struct TimeProvider<T>(PhantomData<T>);
+impl<T: Time> BlockNumberProvider for TimeProvider<T>
+where
+	T::Moment: AtLeast32BitUnsigned,
+{
+	type BlockNumber = T::Moment;
+
+	fn current_block_number() -> Self::BlockNumber {
+		T::now()
+	}
+}
+
+/// Returns the moments taken by `blocks` blocks of `block_time` each, `None`
+/// if calculation overflows.
+pub fn blocks_to_moments<BlockNumber: AtLeast32BitUnsigned, Moment: AtLeast32BitUnsigned>(
+	blocks: BlockNumber,
+	block_time: Moment,
+) -> Option<Moment> {
+	let blocks: u128 = blocks.unique_saturated_into();
+	Moment::try_from(blocks).ok()?.checked_mul(&block_time)
+}
+
+/// Returns the number of blocks of `block_time` each covering `moments`,
+/// rounded up, `None` if `block_time` is zero or calculation overflows.
+pub fn moments_to_blocks<Moment: AtLeast32BitUnsigned, BlockNumber: AtLeast32BitUnsigned>(
+	moments: Moment,
+	block_time: Moment,
+) -> Option<BlockNumber> {
+	if block_time.is_zero() {
+		return None;
+	}
+	let mut blocks = moments.clone() / block_time.clone();
+	if !(moments % block_time).is_zero() {
+		blocks = blocks.checked_add(&One::one())?;
+	}
+	let blocks: u128 = blocks.unique_saturated_into();
+	BlockNumber::try_from(blocks).ok()
+}
+
+/// Returns the estimated moment of `block`, given the moment `now` of block
+/// `now_block` and `block_time` per block, `None` if calculation overflows.
+pub fn block_to_moment<BlockNumber: AtLeast32BitUnsigned, Moment: AtLeast32BitUnsigned>(
+	block: BlockNumber,
+	now_block: BlockNumber,
+	now: Moment,
+	block_time: Moment,
+) -> Option<Moment> {
+	if block >= now_block {
+		now.checked_add(&blocks_to_moments(block - now_block, block_time)?)
+	} else {
+		now.checked_sub(&blocks_to_moments(now_block - block, block_time)?)
+	}
+}
+
+/// Returns the estimated block of `moment`, rounded up, given the moment `now`
+/// of block `now_block` and `block_time` per block, `None` if `block_time` is
+/// zero or calculation overflows.
+pub fn moment_to_block<Moment: AtLeast32BitUnsigned, BlockNumber: AtLeast32BitUnsigned>(
+	moment: Moment,
+	now: Moment,
+	now_block: BlockNumber,
+	block_time: Moment,
+) -> Option<BlockNumber> {
+	if moment >= now {
+		now_block.checked_add(&moments_to_blocks(moment - now, block_time)?)
+	} else {
+		// rounded up towards `now_block`
+		let blocks: BlockNumber = moments_to_blocks(now - moment, block_time.clone())?;
+		let exact = (now - moment) % block_time;
+		let blocks = if exact.is_zero() { blocks } else { blocks - One::one() };
+		now_block.checked_sub(&blocks)
+	}
+}
@@ -90,6 +90,24 @@ impl orml_tokens::Config for Runtime {
 	type MaxReserves = ConstU32<50>;
 	type ReserveIdentifier = [u8; 8];
 	type DustRemovalWhitelist = Everything;
+	type RuntimeHoldReason = ();
+	type MaxHolds = ();
+	type RuntimeFreezeReason = ();
+	type MaxFreezes = ();
+	type Sufficiency = orml_tokens::AllSufficient;
+	type FreezeOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxTransfersPerBatch = ();
+	type MaxIssuance = orml_tokens::NoIssuanceCap;
+	type SnapshotOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxSnapshots = ConstU32<10>;
+	type ApprovalDeposit = orml_tokens::NoApprovalDeposit;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkCurrencyId = BenchmarkCurrencyId;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+parameter_types! {
+	pub const BenchmarkCurrencyId: CurrencyId = CurrencyId::R;
 }
 
 parameter_types! {